@@ -31,26 +31,37 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             api: gl_generator::registry::Ns::Gl.to_string(),
             extensions: vec![
                 "GL_APPLE_vertex_array_object".to_string(),
+                "GL_ARB_base_instance".to_string(),
                 "GL_ARB_buffer_storage".to_string(),
                 "GL_ARB_compute_shader".to_string(),
                 "GL_ARB_copy_buffer".to_string(),
+                "GL_ARB_copy_image".to_string(),
                 "GL_ARB_debug_output".to_string(),
                 "GL_ARB_depth_texture".to_string(),
                 "GL_ARB_direct_state_access".to_string(),
+                "GL_ARB_draw_elements_base_vertex".to_string(),
                 "GL_ARB_ES2_compatibility".to_string(),
                 "GL_ARB_ES3_compatibility".to_string(),
                 "GL_ARB_ES3_1_compatibility".to_string(),
                 "GL_ARB_framebuffer_sRGB".to_string(),
                 "GL_ARB_geometry_shader4".to_string(),
+                "GL_ARB_get_texture_sub_image".to_string(),
+                "GL_ARB_indirect_parameters".to_string(),
                 "GL_ARB_invalidate_subdata".to_string(),
                 "GL_ARB_multi_draw_indirect".to_string(),
                 "GL_ARB_occlusion_query".to_string(),
                 "GL_ARB_pixel_buffer_object".to_string(),
+                "GL_ARB_shader_image_load_store".to_string(),
                 "GL_ARB_shader_objects".to_string(),
+                "GL_ARB_stencil_texturing".to_string(),
+                "GL_ARB_texture_cube_map_array".to_string(),
                 "GL_ARB_texture_float".to_string(),
                 "GL_ARB_texture_multisample".to_string(),
+                "GL_ARB_texture_rectangle".to_string(),
                 "GL_ARB_texture_rg".to_string(),
                 "GL_ARB_texture_rgb10_a2ui".to_string(),
+                "GL_ARB_texture_swizzle".to_string(),
+                "GL_ARB_texture_view".to_string(),
                 "GL_ARB_transform_feedback3".to_string(),
                 "GL_ARB_vertex_buffer_object".to_string(),
                 "GL_ARB_vertex_shader".to_string(),
@@ -62,13 +73,16 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_EXT_framebuffer_sRGB".to_string(),
                 "GL_EXT_gpu_shader4".to_string(),
                 "GL_EXT_packed_depth_stencil".to_string(),
+                "GL_EXT_texture_array".to_string(),
                 "GL_EXT_texture_compression_s3tc".to_string(),
                 "GL_EXT_texture_filter_anisotropic".to_string(),
                 "GL_EXT_texture_integer".to_string(),
                 "GL_EXT_texture_sRGB".to_string(),
+                "GL_EXT_texture_swizzle".to_string(),
                 "GL_EXT_transform_feedback".to_string(),
                 "GL_GREMEDY_string_marker".to_string(),
                 "GL_KHR_robustness".to_string(),
+                "GL_KHR_texture_compression_astc_ldr".to_string(),
                 "GL_NVX_gpu_memory_info".to_string(),
                 "GL_NV_conditional_render".to_string(),
             ],
@@ -92,6 +106,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_EXT_multi_draw_indirect".to_string(),
                 "GL_EXT_occlusion_query_boolean".to_string(),
                 "GL_KHR_debug".to_string(),
+                "GL_KHR_texture_compression_astc_ldr".to_string(),
                 "GL_NV_copy_buffer".to_string(),
                 "GL_NV_pixel_buffer_object".to_string(),
                 "GL_OES_depth_texture".to_string(),
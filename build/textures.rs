@@ -13,6 +13,16 @@ enum TextureType {
     DepthStencil,
 }
 
+impl TextureType {
+    /// Returns true if the texture can be written from client data of its own kind.
+    fn is_writable(&self) -> bool {
+        match self {
+            &TextureType::Depth | &TextureType::Stencil | &TextureType::DepthStencil => false,
+            _ => true
+        }
+    }
+}
+
 #[derive(PartialEq, Eq)]
 enum TextureDimensions {
     Texture1d,
@@ -22,6 +32,9 @@ enum TextureDimensions {
     Texture1dArray,
     Texture2dArray,
     Texture2dMultisampleArray,
+    Cubemap,
+    CubemapArray,
+    Rectangle,
 }
 
 impl TextureDimensions {
@@ -29,6 +42,15 @@ impl TextureDimensions {
         match self {
             &TextureDimensions::Texture1dArray => true,
             &TextureDimensions::Texture2dArray => true,
+            &TextureDimensions::CubemapArray => true,
+            _ => false
+        }
+    }
+
+    fn is_cube(&self) -> bool {
+        match self {
+            &TextureDimensions::Cubemap => true,
+            &TextureDimensions::CubemapArray => true,
             _ => false
         }
     }
@@ -102,6 +124,26 @@ pub fn build_texture_file<W: Write>(mut dest: &mut W) {
     build_texture(dest, TextureType::Depth, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::Stencil, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::DepthStencil, TextureDimensions::Texture2dMultisampleArray);
+    build_texture(dest, TextureType::Regular, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Compressed, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Srgb, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::CompressedSrgb, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Integral, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Unsigned, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Depth, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Stencil, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::DepthStencil, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Regular, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Compressed, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Srgb, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::CompressedSrgb, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Integral, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Unsigned, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Depth, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Stencil, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::DepthStencil, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Regular, TextureDimensions::Rectangle);
+    build_texture(dest, TextureType::Depth, TextureDimensions::Rectangle);
 }
 
 fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: TextureDimensions) {
@@ -127,6 +169,9 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             TextureDimensions::Texture1dArray => "Texture1dArray",
             TextureDimensions::Texture2dArray => "Texture2dArray",
             TextureDimensions::Texture2dMultisampleArray => "Texture2dMultisampleArray",
+            TextureDimensions::Cubemap => "Cubemap",
+            TextureDimensions::CubemapArray => "CubemapArray",
+            TextureDimensions::Rectangle => "RectangleTexture",
         };
 
         format!("{}{}", prefix, suffix)
@@ -136,7 +181,9 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     let data_source_trait = match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => "Texture1dDataSource",
         TextureDimensions::Texture2d | TextureDimensions::Texture2dArray => "Texture2dDataSource",
+        TextureDimensions::Rectangle => "Texture2dDataSource",
         TextureDimensions::Texture3d => "Texture3dDataSource",
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Texture2dDataSource",
         TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray => {
             "unreachable"
         },
@@ -218,6 +265,20 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 /// Only available if the 'gl_texture_multisample_array' feature is enabled.
                 #[cfg(feature = \"gl_texture_multisample_array\")]"
             },
+            TextureDimensions::CubemapArray => {
+                "///
+                /// # Features
+                ///
+                /// Only available if the 'gl_texture_cubemap_array' feature is enabled.
+                #[cfg(feature = \"gl_texture_cubemap_array\")]"
+            },
+            TextureDimensions::Rectangle => {
+                "///
+                /// # Features
+                ///
+                /// Only available if the 'gl_texture_rectangle' feature is enabled.
+                #[cfg(feature = \"gl_texture_rectangle\")]"
+            },
             _ => ""
         };
 
@@ -233,6 +294,9 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1dArray => "width: u32, array_size: u32",
         TextureDimensions::Texture2dArray => "width: u32, height: u32, array_size: u32",
         TextureDimensions::Texture2dMultisampleArray => "width: u32, height: u32, array_size: u32, samples: u32",
+        TextureDimensions::Cubemap => "dimension: u32",
+        TextureDimensions::CubemapArray => "dimension: u32, array_size: u32",
+        TextureDimensions::Rectangle => "width: u32, height: u32",
     };
 
     let dimensions_parameters_passing = match dimensions {
@@ -243,15 +307,28 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1dArray => "width, None, None, Some(array_size), None",
         TextureDimensions::Texture2dArray => "width, Some(height), None, Some(array_size), None",
         TextureDimensions::Texture2dMultisampleArray => "width, Some(height), None, Some(array_size), Some(samples)",
+        TextureDimensions::Cubemap => "dimension, None",
+        TextureDimensions::CubemapArray => "dimension, Some(array_size)",
+        TextureDimensions::Rectangle => "width, height",
+    };
+
+    // the function of the `any` module that creates the texture
+    let new_texture_fn = match dimensions {
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "new_cubemap",
+        TextureDimensions::Rectangle => "new_rectangle",
+        _ => "new_texture",
     };
 
     // writing the struct with doc-comment
     (write!(dest, "/// ")).unwrap();
     (write!(dest, "{}", match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "A ",
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d |
+        TextureDimensions::Cubemap | TextureDimensions::Rectangle => "A ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisampleArray => "An array of ",
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::CubemapArray => {
+            "An array of "
+        },
     })).unwrap();
     if ty == TextureType::Compressed {
         (write!(dest, "compressed ")).unwrap();
@@ -263,12 +340,17 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             "two-dimensional "
         },
         TextureDimensions::Texture3d => "three-dimensional ",
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "cube ",
+        TextureDimensions::Rectangle => "rectangle ",
     })).unwrap();
     (write!(dest, "{}", match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "texture ",
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d |
+        TextureDimensions::Cubemap | TextureDimensions::Rectangle => "texture ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisampleArray => "textures ",
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::CubemapArray => {
+            "textures "
+        },
     })).unwrap();
     (write!(dest, "{}", match ty {
         TextureType::Regular | TextureType::Compressed => " containing floating-point data",
@@ -280,6 +362,16 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureType::DepthStencil => " containing both depth and stencil data",
     })).unwrap();
     (writeln!(dest, ".")).unwrap();
+    if dimensions.is_cube() {
+        (writeln!(dest, "///
+                         /// The data of the faces is passed and stored in the order of the
+                         /// `CubeLayer` enum.")).unwrap();
+    }
+    if dimensions == TextureDimensions::Rectangle {
+        (writeln!(dest, "///
+                         /// Rectangle textures are sampled with coordinates in pixels instead of
+                         /// normalized coordinates, and never have mipmaps.")).unwrap();
+    }
     (writeln!(dest, "pub struct {}(TextureAny);", name)).unwrap();
 
     // `Texture` trait impl
@@ -337,9 +429,11 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     // `UniformValue` trait impl
     {
         match ty {
-            TextureType::Regular | TextureType::Compressed |
-            TextureType::Srgb | TextureType::CompressedSrgb |
-            TextureType::Integral | TextureType::Unsigned | TextureType::Depth => {
+            // only 2D stencil and depth-stencil textures can be sampled for the moment
+            TextureType::Stencil | TextureType::DepthStencil
+                if dimensions != TextureDimensions::Texture2d => (),
+
+            _ => {
                 (writeln!(dest, "
                             impl<'a> AsUniformValue for &'a {myname} {{
                                 fn as_uniform_value(&self) -> UniformValue {{
@@ -383,12 +477,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                             }}
                         ", myname = name)).unwrap();
             },
-            _ => ()
         }
     }
 
     // `ToXXXAttachment` trait impl
-    if dimensions == TextureDimensions::Texture2d || dimensions == TextureDimensions::Texture2dMultisample {
+    if dimensions == TextureDimensions::Texture2d ||
+       dimensions == TextureDimensions::Texture2dMultisample ||
+       dimensions == TextureDimensions::Rectangle
+    {
         match ty {
             TextureType::Regular => {
                 (writeln!(dest, "
@@ -456,10 +552,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -472,7 +568,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 pub fn new<'a, F, T>(facade: &F, data: {param})
                               -> {name} where T: {data_source_trait}<'a>, F: Facade
                 {{
-                    {name}::new_impl(facade, data, None, true).unwrap()
+                    let mipmaps = MipmapsOption::AutoGeneratedMipmaps;
+                    {name}::new_impl(facade, data, None, mipmaps).unwrap()
                 }}
             ", data_source_trait = data_source_trait, param = param, name = name,
                 cfg_attr = cfg_attribute)).unwrap();
@@ -482,10 +579,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -497,7 +594,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 pub fn new_if_supported<'a, F, T>(facade: &F, data: {param})
                                                -> Option<{name}> where T: {data_source_trait}<'a>, F: Facade
                 {{
-                    match {name}::new_impl(facade, data, None, true) {{
+                    let mipmaps = MipmapsOption::AutoGeneratedMipmaps;
+                    match {name}::new_impl(facade, data, None, mipmaps) {{
                         Ok(t) => Some(t),
                         Err(TextureMaybeSupportedCreationError::NotSupported) => None,
                         Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
@@ -510,10 +608,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -523,11 +621,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 {cfg_attr}
-                pub fn with_mipmaps<'a, F, T>(facade: &F, data: {param}, mipmaps: bool)
-                                           -> {name} where T: {data_source_trait}<'a>, F: Facade
+                pub fn with_mipmaps<'a, F, T, M>(facade: &F, data: {param}, mipmaps: M) -> {name}
+                                              where T: {data_source_trait}<'a>, F: Facade,
+                                                    M: Into<MipmapsOption>
                 {{
-                    {name}::new_impl(facade, data, None, mipmaps).unwrap()
+                    {name}::new_impl(facade, data, None, mipmaps.into()).unwrap()
                 }}
             ", data_source_trait = data_source_trait, param = param, name = name,
                 cfg_attr = cfg_attribute)).unwrap();
@@ -537,10 +638,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -550,11 +651,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
-                pub fn with_mipmaps_if_supported<'a, F, T>(facade: &F, data: {param},
-                                                        mipmaps: bool) -> Option<{name}>
-                                                        where T: {data_source_trait}<'a>, F: Facade
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
+                pub fn with_mipmaps_if_supported<'a, F, T, M>(facade: &F, data: {param},
+                                                           mipmaps: M) -> Option<{name}>
+                                                           where T: {data_source_trait}<'a>,
+                                                                 F: Facade, M: Into<MipmapsOption>
                 {{
-                    match {name}::new_impl(facade, data, None, mipmaps) {{
+                    match {name}::new_impl(facade, data, None, mipmaps.into()) {{
                         Ok(t) => Some(t),
                         Err(TextureMaybeSupportedCreationError::NotSupported) => None,
                         Err(TextureMaybeSupportedCreationError::CreationError(_)) => unreachable!()
@@ -567,10 +671,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -580,13 +684,16 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 {cfg_attr}
-                pub fn with_format<'a, F, T>(facade: &F, data: {param},
-                                          format: {format}, mipmaps: bool)
-                                          -> Result<{name}, TextureCreationError>
-                                          where T: {data_source_trait}<'a>, F: Facade
+                pub fn with_format<'a, F, T, M>(facade: &F, data: {param},
+                                             format: {format}, mipmaps: M)
+                                             -> Result<{name}, TextureCreationError>
+                                             where T: {data_source_trait}<'a>, F: Facade,
+                                                   M: Into<MipmapsOption>
                 {{
-                    match {name}::new_impl(facade, data, Some(format), mipmaps) {{
+                    match {name}::new_impl(facade, data, Some(format), mipmaps.into()) {{
                         Ok(t) => Ok(t),
                         Err(TextureMaybeSupportedCreationError::CreationError(e)) => Err(e),
                         Err(TextureMaybeSupportedCreationError::NotSupported) => unreachable!()
@@ -600,10 +707,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if cfg_attribute.len() >= 1 && !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
@@ -613,12 +720,15 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
-                pub fn with_format_if_supported<'a, F, T>(facade: &F, data: {param},
-                                                       format: {format}, mipmaps: bool)
-                                                       -> Result<{name}, TextureMaybeSupportedCreationError>
-                                                       where T: {data_source_trait}<'a>, F: Facade
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
+                pub fn with_format_if_supported<'a, F, T, M>(facade: &F, data: {param},
+                                                          format: {format}, mipmaps: M)
+                                                          -> Result<{name}, TextureMaybeSupportedCreationError>
+                                                          where T: {data_source_trait}<'a>, F: Facade,
+                                                                M: Into<MipmapsOption>
                 {{
-                    {name}::new_impl(facade, data, Some(format), mipmaps)
+                    {name}::new_impl(facade, data, Some(format), mipmaps.into())
                 }}
             ", data_source_trait = data_source_trait, param = param,
                format = relevant_format, name = name)).unwrap();
@@ -628,17 +738,17 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     if !dimensions.is_multisample() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-            TextureDimensions::Texture3d => "T",
+            TextureDimensions::Texture3d | TextureDimensions::Rectangle => "T",
 
-            TextureDimensions::Texture1dArray |
-            TextureDimensions::Texture2dArray => "Vec<T>",
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "Vec<T>",
 
             _ => unreachable!()
         };
 
         (writeln!(dest, "
                 fn new_impl<'a, F, T>(facade: &F, data: {param},
-                                   format: Option<{relevant_format}>, mipmaps: bool)
+                                   format: Option<{relevant_format}>, mipmaps: MipmapsOption)
                                    -> Result<{name}, TextureMaybeSupportedCreationError>
                                    where T: {data_source_trait}<'a>, F: Facade
                 {{
@@ -657,7 +767,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     let RawImage1d {{ data, width, format: client_format }} = data.into_raw();
                 ")).unwrap(),
 
-            TextureDimensions::Texture2d => (write!(dest, "
+            TextureDimensions::Texture2d | TextureDimensions::Rectangle => (write!(dest, "
                     let RawImage2d {{ data, width, height, format: client_format }} =
                                             data.into_raw();
                 ")).unwrap(),
@@ -677,18 +787,195 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     let RawImage3d {{data, width, height, depth: array_size, format: client_format }} = RawImage3d::from_vec_raw2d(&vec_raw);
                 ")).unwrap(),   // TODO: panic if dimensions are inconsistent
 
+            TextureDimensions::Cubemap => (write!(dest, "
+                    assert_eq!(data.len(), 6);
+                    let vec_raw = data.into_iter().map(|e| e.into_raw()).collect();
+                    let RawImage3d {{data, width: dimension, height, format: client_format, .. }} =
+                                            RawImage3d::from_vec_raw2d(&vec_raw);
+                    assert_eq!(dimension, height);
+                ")).unwrap(),
+
+            TextureDimensions::CubemapArray => (write!(dest, "
+                    assert!(data.len() != 0 && data.len() % 6 == 0);
+                    let array_size = data.len() as u32 / 6;
+                    let vec_raw = data.into_iter().map(|e| e.into_raw()).collect();
+                    let RawImage3d {{data, width: dimension, height, format: client_format, .. }} =
+                                            RawImage3d::from_vec_raw2d(&vec_raw);
+                    assert_eq!(dimension, height);
+                ")).unwrap(),
+
             _ => unreachable!()
         }
 
         // writing the constructor
-        (write!(dest, "Ok({}(try!(any::new_texture(facade, format, \
-                       Some((client_format, data)), mipmaps, {}", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "Ok({}(try!(any::{}(facade, format, \
+                       Some((client_format, data)), mipmaps, {}", name, new_texture_fn,
+                dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "))))")).unwrap();
 
         // end of "new" function block
         (writeln!(dest, "}}")).unwrap();
     }
 
+    // writing the `with_mipmap_data` function
+    if !dimensions.is_multisample() && !dimensions.is_cube() &&
+       dimensions != TextureDimensions::Rectangle
+    {
+        let param = match dimensions {
+            TextureDimensions::Texture1d | TextureDimensions::Texture2d |
+            TextureDimensions::Texture3d => "T",
+            _ => "Vec<T>",
+        };
+
+        (writeln!(dest, "
+                /// Builds a new texture with the content of each of its mipmap levels, starting
+                /// with the main level.
+                ///
+                /// Only the levels that are passed are allocated. Each level must be half the
+                /// size of the previous one, rounded down. The layers of arrays are not divided.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if `levels` is empty or if the dimensions of a level are wrong.
+                pub fn with_mipmap_data<'a, F, T>(facade: &F, levels: Vec<{param}>)
+                                               -> Result<{name}, TextureMaybeSupportedCreationError>
+                                               where T: {data_source_trait}<'a>, F: Facade
+                {{
+                    assert!(levels.len() >= 1);
+
+                    let mipmaps = MipmapsOption::EmptyMipmapsMax(levels.len() as u32 - 1);
+                    let mut levels = levels.into_iter();
+                    let texture = try!({name}::new_impl(facade, levels.next().unwrap(), None,
+                                                        mipmaps));
+
+                    for (level, data) in levels.enumerate() {{
+                        let level = level as u32 + 1;
+            ", data_source_trait = data_source_trait, param = param, name = name)).unwrap();
+
+        match dimensions {
+            TextureDimensions::Texture1d => (write!(dest, "
+                    let RawImage1d {{ data, width, format: client_format }} = data.into_raw();
+                    any::upload_texture(&texture.0, 0, 0, 0, (client_format, data), width, None,
+                                        None, level, false);
+                ")).unwrap(),
+
+            TextureDimensions::Texture2d => (write!(dest, "
+                    let RawImage2d {{ data, width, height, format: client_format }} =
+                                            data.into_raw();
+                    any::upload_texture(&texture.0, 0, 0, 0, (client_format, data), width,
+                                        Some(height), None, level, false);
+                ")).unwrap(),
+
+            TextureDimensions::Texture3d => (write!(dest, "
+                    let RawImage3d {{ data, width, height, depth, format: client_format }} =
+                                            data.into_raw();
+                    any::upload_texture(&texture.0, 0, 0, 0, (client_format, data), width,
+                                        Some(height), Some(depth), level, false);
+                ")).unwrap(),
+
+            TextureDimensions::Texture1dArray => (write!(dest, "
+                    let vec_raw = data.into_iter().map(|e| e.into_raw()).collect();
+                    let RawImage2d {{ data, width, height: array_size, format: client_format }} =
+                                            RawImage2d::from_vec_raw1d(&vec_raw);
+                    any::upload_texture(&texture.0, 0, 0, 0, (client_format, data), width, None,
+                                        Some(array_size), level, false);
+                ")).unwrap(),
+
+            TextureDimensions::Texture2dArray => (write!(dest, "
+                    let vec_raw = data.into_iter().map(|e| e.into_raw()).collect();
+                    let RawImage3d {{ data, width, height, depth: array_size,
+                                      format: client_format }} =
+                                            RawImage3d::from_vec_raw2d(&vec_raw);
+                    any::upload_texture(&texture.0, 0, 0, 0, (client_format, data), width,
+                                        Some(height), Some(array_size), level, false);
+                ")).unwrap(),
+
+            _ => unreachable!()
+        }
+
+        // end of the loop and of the function
+        (writeln!(dest, "}}
+                Ok(texture)
+            }}")).unwrap();
+    }
+
+    // writing the `with_compressed_data` function
+    if (ty == TextureType::Compressed || ty == TextureType::CompressedSrgb) &&
+       (dimensions == TextureDimensions::Texture2d ||
+        dimensions == TextureDimensions::Texture2dArray)
+    {
+        let (array_doc, array_param, array_size) = if dimensions.is_array() {
+            (" Each element contains the layers of the level one after another.",
+             ", array_size: u32", "Some(array_size)")
+        } else {
+            ("", "", "None")
+        };
+
+        (write!(dest, "
+                /// Builds a new texture from data that is already compressed in `format`.
+                ///
+                /// `levels` contains the compressed blocks of each mipmap level, starting with
+                /// the main level. Only the levels that are passed are allocated.{array_doc}
+                ///
+                /// ## Panic
+                ///
+                /// Panics if `levels` is empty or if the size of a level doesn't match the
+                /// dimensions.
+                pub fn with_compressed_data<F>(facade: &F, format: {format}, levels: &[&[u8]],
+                                               width: u32, height: u32{array_param})
+                                               -> Result<{name}, TextureMaybeSupportedCreationError>
+                                               where F: Facade
+                {{
+                    let format = format.to_texture_format();
+                    any::new_compressed_texture(facade, format, levels, width, height,
+                                                {array_size}).map(|t| {name}(t))
+                }}
+            ", format = relevant_format, name = name, array_doc = array_doc,
+               array_param = array_param, array_size = array_size)).unwrap();
+    }
+
+    // writing the `new_view` function
+    {
+        let texture_type = match dimensions {
+            TextureDimensions::Texture1d => "TextureType::Texture1d",
+            TextureDimensions::Texture2d => "TextureType::Texture2d",
+            TextureDimensions::Texture2dMultisample => "TextureType::Texture2dMultisample",
+            TextureDimensions::Texture3d => "TextureType::Texture3d",
+            TextureDimensions::Texture1dArray => "TextureType::Texture1dArray",
+            TextureDimensions::Texture2dArray => "TextureType::Texture2dArray",
+            TextureDimensions::Texture2dMultisampleArray => {
+                "TextureType::Texture2dMultisampleArray"
+            },
+            TextureDimensions::Cubemap => "TextureType::Cubemap",
+            TextureDimensions::CubemapArray => "TextureType::CubemapArray",
+            TextureDimensions::Rectangle => "TextureType::Rectangle",
+        };
+
+        (write!(dest, "
+                /// Builds a view of some of the mipmaps and layers of another texture.
+                ///
+                /// The view shares its storage with `texture`, but interprets its content with
+                /// `format`. The storage of `texture` must have been allocated in a way that
+                /// allows views, and `format` must belong to the same compatibility class as
+                /// the format of `texture`.
+                ///
+                /// For cubemaps, the layers are `6 * cube + face`.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the ranges are empty or out of range, or if the number of layers
+                /// doesn't match this type of texture.
+                pub fn new_view(texture: &TextureAny, format: {format},
+                                levels: ::std::ops::Range<u32>, layers: ::std::ops::Range<u32>)
+                                -> Result<{name}, TextureViewCreationError>
+                {{
+                    let format = format.to_texture_format();
+                    any::new_view(texture, format, {texture_type}, levels, layers)
+                        .map(|t| {name}(t))
+                }}
+            ", format = relevant_format, name = name, texture_type = texture_type)).unwrap();
+    }
+
     // writing the `new_empty` function
     if ty != TextureType::Compressed && ty != TextureType::CompressedSrgb {
         // opening function
@@ -700,7 +987,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = relevant_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(any::new_texture::<_, u8>(facade, format, None, true, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(any::{}::<_, u8>(facade, format, None, MipmapsOption::AutoGeneratedMipmaps, {}).unwrap())", name, new_texture_fn, dimensions_parameters_passing)).unwrap();
 
         // closing function
         (writeln!(dest, "}}")).unwrap();
@@ -722,7 +1009,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(any::new_texture::<_, u8>(facade, format, None, false, {}).unwrap())", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(any::{}::<_, u8>(facade, format, None, MipmapsOption::NoMipmap, {}).unwrap())", name, new_texture_fn, dimensions_parameters_passing)).unwrap();
 
         // closing function
         (writeln!(dest, "}}")).unwrap();
@@ -742,7 +1029,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ", format = default_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "match any::new_texture::<_, u8>(facade, format, None, false, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "match any::{}::<_, u8>(facade, format, None, MipmapsOption::NoMipmap, {})", new_texture_fn, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             {{
                 Ok(t) => Some({}(t)),
@@ -762,17 +1049,19 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 ///
                 /// The texture (and its mipmaps, if you pass `true`) will contain undefined data.
                 {cfg_attr}
-                pub fn empty_with_format<F>(facade: &F, format: {format}, mipmaps: bool, {dim_params}) -> Result<{name}, TextureCreationError> where F: Facade {{
+                pub fn empty_with_format<F, M>(facade: &F, format: {format}, mipmaps: M, {dim_params}) -> Result<{name}, TextureCreationError> where F: Facade, M: Into<MipmapsOption> {{
                     let format = format.to_texture_format();
                     let format = TextureFormatRequest::Specific(format);
             ", format = relevant_format, dim_params = dimensions_parameters_input, name = name,
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "let t = any::new_texture::<_, u8>(facade, format, None, mipmaps, {});", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "let t = any::{}::<_, u8>(facade, format, None, mipmaps.into(), {});", new_texture_fn, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             match t {{
                 Ok(t) => Ok({}(t)),
@@ -792,19 +1081,22 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 ///
                 /// The texture (and its mipmaps, if you pass `true`) will contain undefined data.
-                pub fn empty_with_format_if_supported<F>(facade: &F, format: {format},
-                                                      mipmaps: bool, {dim_params})
-                                                      -> Result<{name},
-                                                                TextureMaybeSupportedCreationError> where F: Facade
+                pub fn empty_with_format_if_supported<F, M>(facade: &F, format: {format},
+                                                         mipmaps: M, {dim_params})
+                                                         -> Result<{name},
+                                                                   TextureMaybeSupportedCreationError>
+                                                         where F: Facade, M: Into<MipmapsOption>
                 {{
                     let format = format.to_texture_format();
                     let format = TextureFormatRequest::Specific(format);
             ", format = relevant_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "any::new_texture::<_, u8>(facade, format, None, mipmaps, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "any::{}::<_, u8>(facade, format, None, mipmaps.into(), {})", new_texture_fn, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, ".map(|t| {}(t))", name)).unwrap();
 
         // closing function
@@ -819,16 +1111,18 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 ///
                 /// The texture (and its mipmaps, if you pass `true`) will contain undefined data.
                 {cfg_attr}
-                pub fn empty_with_mipmaps<F>(facade: &F, mipmaps: bool, {dim_params}) -> {name} where F: Facade {{
+                pub fn empty_with_mipmaps<F, M>(facade: &F, mipmaps: M, {dim_params}) -> {name} where F: Facade, M: Into<MipmapsOption> {{
                     let format = {format};
             ", format = default_format, dim_params = dimensions_parameters_input, name = name,
                 cfg_attr = cfg_attribute)).unwrap();
 
         // writing the constructor
-        (write!(dest, "{}(any::new_texture::<_, u8>(facade, format, None, mipmaps, {})", name, dimensions_parameters_passing)).unwrap();
+        (write!(dest, "{}(any::{}::<_, u8>(facade, format, None, mipmaps.into(), {})", name, new_texture_fn, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, ".unwrap())")).unwrap();
 
         // closing function
@@ -843,15 +1137,19 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// Note that passing `true` for `mipmaps` does not mean that you will get mipmaps.
                 /// Instead it indicates that mipmaps are *allowed* to be created if possible.
+                /// Pass a `MipmapsOption` to choose how many mipmaps are allocated and whether
+                /// they are generated.
                 ///
                 /// The texture (and its mipmaps, if you pass `true`) will contain undefined data.
-                pub fn empty_with_mipmaps_if_supported<F>(facade: &F, mipmaps: bool,
-                                                       {dim_params}) -> Option<{name}> where F: Facade {{
+                pub fn empty_with_mipmaps_if_supported<F, M>(facade: &F, mipmaps: M,
+                                                          {dim_params}) -> Option<{name}>
+                                                          where F: Facade, M: Into<MipmapsOption>
+                {{
                     let format = {format};
             ", format = default_format, dim_params = dimensions_parameters_input, name = name)).unwrap();
 
         // writing the constructor
-        (write!(dest, "match any::new_texture::<_, u8>(facade, format, None, mipmaps, {})", dimensions_parameters_passing)).unwrap();
+        (write!(dest, "match any::{}::<_, u8>(facade, format, None, mipmaps.into(), {})", new_texture_fn, dimensions_parameters_passing)).unwrap();
         (writeln!(dest, "
             {{
                 Ok(t) => Some({}(t)),
@@ -866,7 +1164,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
 
     // writing the `as_surface` function
     if (dimensions == TextureDimensions::Texture2d ||
-        dimensions == TextureDimensions::Texture2dMultisample) && ty == TextureType::Regular
+        dimensions == TextureDimensions::Texture2dMultisample ||
+        dimensions == TextureDimensions::Rectangle) && ty == TextureType::Regular
     {
         (write!(dest, "
                 /// Starts drawing on the texture.
@@ -896,9 +1195,130 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             }}
         ")).unwrap();
 
+    // writing the `generate_mipmaps` function
+    if !dimensions.is_multisample() && dimensions != TextureDimensions::Rectangle &&
+       ty != TextureType::Compressed && ty != TextureType::CompressedSrgb &&
+       ty != TextureType::Depth && ty != TextureType::Stencil && ty != TextureType::DepthStencil
+    {
+        (write!(dest, "
+                /// Generates the content of all the mipmaps of the texture from its main level.
+                ///
+                /// This is usually done after drawing on the texture. Does nothing if the texture
+                /// has no mipmap.
+                ///
+                /// Returns an error if generating mipmaps is not supported by the backend.
+                pub fn generate_mipmaps(&self) -> Result<(), MipmapsGenerationNotSupportedError> {{
+                    self.0.generate_mipmaps()
+                }}
+            ")).unwrap();
+    }
+
+    // writing the `into_image` function
+    if (dimensions == TextureDimensions::Texture2d ||
+        dimensions == TextureDimensions::Rectangle) &&
+       ty != TextureType::Stencil && ty != TextureType::DepthStencil
+    {
+        (write!(dest, "
+                /// Reads the content of the texture into an image.
+                ///
+                /// The pixel type of the image is chosen from the internal format of the texture,
+                /// so that 16-bits and floating-point data keep their precision. Depth textures
+                /// are read as `f32` luminance, and integral and unsigned textures as `i32` and
+                /// `u32`.
+                ///
+                /// Returns `None` if the backend doesn't support reading this texture, for
+                /// example depth and compressed textures on OpenGL ES.
+                ///
+                /// You should avoid doing this during performance-critical operations.
+                ///
+                /// # Features
+                ///
+                /// Only available if the `image` feature is enabled.
+                #[cfg(feature = \"image\")]
+                pub fn into_image(&self) -> Option<TextureImage> {{
+                    any::read_image(&self.0, 0)
+                }}
+            ")).unwrap();
+    }
+
+    // writing the functions specific to multisample textures
+    if dimensions.is_multisample() {
+        (write!(dest, "
+                /// Returns the number of samples of each pixel of the texture.
+                ///
+                /// This can be more than the number of samples that was requested when creating
+                /// the texture.
+                pub fn get_samples(&self) -> u32 {{
+                    self.0.get_samples().unwrap()
+                }}
+
+                /// Returns the position of each sample within a pixel.
+                ///
+                /// The positions are between `0.0` and `1.0`, where `(0.5, 0.5)` is the center
+                /// of the pixel. The index of an element corresponds to the index of the sample
+                /// when using `texelFetch` in a shader.
+                ///
+                /// Returns `None` if the texture can't be attached to a framebuffer by the
+                /// backend.
+                pub fn get_sample_positions(&self) -> Option<Vec<(f32, f32)>> {{
+                    self.0.get_sample_positions()
+                }}
+            ")).unwrap();
+    }
+
+    if dimensions == TextureDimensions::Texture2dMultisample {
+        let target_name = name.replace("Texture2dMultisample", "Texture2d");
+
+        (write!(dest, "
+                /// Resolves the texture into a regular texture of the same dimensions.
+                ///
+                /// The samples of each pixel are averaged, except for integral, depth and
+                /// stencil formats for which the value of one of the samples is picked by the
+                /// implementation. Returns an error if the two textures don't have the same
+                /// internal format.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the dimensions of the two textures don't match.
+                pub fn resolve_into(&self, target: &{target}) -> Result<(), ResolveError> {{
+                    any::resolve_multisample(&self.0, &target.0)
+                }}
+            ", target = target_name)).unwrap();
+
+        let samples_kind = match ty {
+            TextureType::Regular | TextureType::Srgb => Some("Float"),
+            TextureType::Integral => Some("Integral"),
+            TextureType::Unsigned => Some("Unsigned"),
+            _ => None
+        };
+
+        if let Some(samples_kind) = samples_kind {
+            (write!(dest, "
+                    /// Copies the value of one sample of each pixel to a regular texture of the
+                    /// same dimensions.
+                    ///
+                    /// Contrary to `resolve_into`, the sample to copy is chosen. This is done by
+                    /// drawing with a built-in program, which is compiled the first time this
+                    /// function is called.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if `sample` is out of range or if the dimensions of the two textures
+                    /// don't match.
+                    pub fn resolve_sample_into(&self, sample: u32, target: &{target})
+                                               -> Result<(), ResolveError>
+                    {{
+                        multisample::copy_sample(self, &self.0, &target.0,
+                                                 multisample::SamplesKind::{kind}, sample)
+                    }}
+                ", target = target_name, kind = samples_kind)).unwrap();
+        }
+    }
+
     // writing the `read` functions
     // TODO: implement for other types too
-    if dimensions == TextureDimensions::Texture2d &&
+    if (dimensions == TextureDimensions::Texture2d ||
+        dimensions == TextureDimensions::Rectangle) &&
        (ty == TextureType::Regular || ty == TextureType::Compressed)
     {
         (write!(dest, r#"
@@ -924,10 +1344,26 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             "#)).unwrap();
     }
 
+    // writing the `read_compressed_data` function
+    if (ty == TextureType::Compressed || ty == TextureType::CompressedSrgb) &&
+       dimensions == TextureDimensions::Texture2d
+    {
+        (write!(dest, "
+                /// Reads the compressed blocks of the main level of the texture, along with their
+                /// format.
+                ///
+                /// Returns `None` if the backend doesn't support reading compressed data (for
+                /// example OpenGL ES), or if the texture isn't stored in a compressed format known
+                /// by glium.
+                pub fn read_compressed_data(&self) -> Option<({format}, Vec<u8>)> {{
+                    self.main_level().read_compressed_data()
+                }}
+            ", format = relevant_format)).unwrap();
+    }
+
     // writing the `write` function
-    // TODO: implement for other types too
-    if dimensions == TextureDimensions::Texture2d &&
-       (ty == TextureType::Regular || ty == TextureType::Compressed)
+    if (dimensions == TextureDimensions::Texture2d ||
+        dimensions == TextureDimensions::Rectangle) && ty.is_writable()
     {
         (write!(dest, r#"
                 /// Uploads some data in the texture.
@@ -1127,7 +1563,420 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ")).unwrap();
         }
 
+        // writing the `write` function
+        if ty.is_writable() {
+            build_mipmap_write(dest, &dimensions);
+        }
+
+        // writing the `read` function
+        build_mipmap_read(dest, &dimensions);
+
+        // writing the `read_compressed_data` function
+        if (ty == TextureType::Compressed || ty == TextureType::CompressedSrgb) &&
+           (dimensions == TextureDimensions::Texture2d ||
+            dimensions == TextureDimensions::Texture2dArray)
+        {
+            (write!(dest, "
+                    /// Reads the compressed blocks of this mipmap level, along with their format.
+                    ///
+                    /// Returns `None` if the backend doesn't support reading compressed data (for
+                    /// example OpenGL ES), or if the texture isn't stored in a compressed format
+                    /// known by glium.
+                    pub fn read_compressed_data(&self) -> Option<({format}, Vec<u8>)> {{
+                        let (format, data) = match any::download_compressed_data(&self.texture.0,
+                                                                                 self.level)
+                        {{
+                            Some(d) => d,
+                            None => return None,
+                        }};
+                ", format = relevant_format)).unwrap();
+
+            // the data of arrays contains all the layers
+            if dimensions.is_array() {
+                (write!(dest, "
+                        let layers = self.texture.get_array_size().unwrap() as usize;
+                        let layer_size = data.len() / layers;
+                        let start = self.layer as usize * layer_size;
+                        let data = data[start .. start + layer_size].to_vec();
+                    ")).unwrap();
+            }
+
+            (write!(dest, "
+                        match format {{
+                            TextureFormat::{format}(format) => Some((format, data)),
+                            _ => None
+                        }}
+                    }}
+                ", format = relevant_format)).unwrap();
+        }
+
+        // writing the `face()` function
+        if dimensions.is_cube() {
+            (write!(dest, r#"
+                    /// Access a single face of this mipmap level.
+                    pub fn face(&self, face: CubeLayer) -> {name}Face<'t> {{
+                        {name}Face {{
+                            texture: self.texture,
+                            {layer}
+                            level: self.level,
+                            face: face,
+                        }}
+                    }}
+                "#, name = name,
+                    layer = if dimensions.is_array() { "layer: self.layer," } else { "" }))
+                .unwrap();
+        }
+
         // closing `impl Mipmap` block
         (writeln!(dest, "}}")).unwrap();
+
+        // conversion to `TextureAnyMipmap`, cubemaps are converted face by face
+        if !dimensions.is_cube() {
+            (write!(dest, r#"
+                    impl<'t> From<{name}Mipmap<'t>> for TextureAnyMipmap<'t> {{
+                        fn from(mipmap: {name}Mipmap<'t>) -> TextureAnyMipmap<'t> {{
+                            mipmap.texture.0.mipmap({layer}, mipmap.level).unwrap()
+                        }}
+                    }}
+                "#, name = name,
+                    layer = if dimensions.is_array() { "mipmap.layer" } else { "0" })).unwrap();
+        }
+    }
+
+    // the `Face` struct
+    if dimensions.is_cube() {
+        build_cubemap_face(dest, &ty, &dimensions, &name);
+    }
+}
+
+fn build_mipmap_write<W: Write>(mut dest: &mut W, dimensions: &TextureDimensions) {
+    let layer = if dimensions.is_array() { "self.layer" } else { "0" };
+
+    match *dimensions {
+        TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => {
+            (write!(dest, r#"
+                    /// Uploads some data in this mipmap level, starting at `x_offset`.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the data doesn't fit in the mipmap level.
+                    pub fn write<'a, T>(&self, x_offset: u32, data: T)
+                                        where T: Texture1dDataSource<'a>
+                    {{
+                        let RawImage1d {{ data, width, format: client_format }} = data.into_raw();
+
+                        any::upload_texture(&self.texture.0, x_offset, 0, {layer},
+                                            (client_format, data), width, None, None,
+                                            self.level, self.level == 0);
+                    }}
+                "#, layer = layer)).unwrap();
+        },
+
+        TextureDimensions::Texture2d | TextureDimensions::Texture2dArray |
+        TextureDimensions::Rectangle => {
+            (write!(dest, r#"
+                    /// Uploads some data in this mipmap level.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the the dimensions of `data` don't match the `Rect`, or if the
+                    /// `Rect` doesn't fit in the mipmap level.
+                    pub fn write<'a, T>(&self, rect: Rect, data: T)
+                                        where T: Texture2dDataSource<'a>
+                    {{
+                        let RawImage2d {{ data, width, height, format: client_format }} =
+                                                data.into_raw();
+
+                        assert_eq!(width, rect.width);
+                        assert_eq!(height, rect.height);
+
+                        any::upload_texture(&self.texture.0, rect.left, rect.bottom, {layer},
+                                            (client_format, data), width, Some(height), None,
+                                            self.level, self.level == 0);
+                    }}
+                "#, layer = layer)).unwrap();
+        },
+
+        TextureDimensions::Texture3d => {
+            (write!(dest, r#"
+                    /// Uploads some data in this mipmap level. The offset is the position of the
+                    /// first pixel of `data` in the texture, in the order `(x, y, z)`.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the data doesn't fit in the mipmap level.
+                    pub fn write<'a, T>(&self, offset: (u32, u32, u32), data: T)
+                                        where T: Texture3dDataSource<'a>
+                    {{
+                        let RawImage3d {{ data, width, height, depth, format: client_format }} =
+                                                data.into_raw();
+
+                        any::upload_texture(&self.texture.0, offset.0, offset.1, offset.2,
+                                            (client_format, data), width, Some(height),
+                                            Some(depth), self.level, self.level == 0);
+                    }}
+                "#)).unwrap();
+        },
+
+        // cubemaps are written face by face, and multisample textures can't be written
+        _ => ()
     }
 }
+
+fn build_mipmap_read<W: Write>(mut dest: &mut W, dimensions: &TextureDimensions) {
+    let layer = if dimensions.is_array() { "self.layer" } else { "0" };
+
+    match *dimensions {
+        TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => {
+            (write!(dest, r#"
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// The pixels are returned in the format of `P`, which must be compatible
+                    /// with the kind of texture. For example depth textures can only be read as
+                    /// `f32`.
+                    pub fn read<T, P>(&self) -> T where T: Texture1dDataSink<P>, P: PixelValue {{
+                        let (data, width, _, _) = any::download_texture(&self.texture.0, {layer},
+                                                                        self.level);
+                        T::from_raw(Cow::Owned(data), width)
+                    }}
+
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// Same as `read`, but returns `None` instead of panicking if the backend
+                    /// doesn't support reading this texture.
+                    pub fn read_if_supported<T, P>(&self) -> Option<T>
+                                                   where T: Texture1dDataSink<P>, P: PixelValue
+                    {{
+                        any::download_texture_if_supported(&self.texture.0, {layer}, self.level)
+                            .map(|(data, width, _, _)| T::from_raw(Cow::Owned(data), width))
+                    }}
+                "#, layer = layer)).unwrap();
+        },
+
+        TextureDimensions::Texture2d | TextureDimensions::Texture2dArray |
+        TextureDimensions::Rectangle => {
+            (write!(dest, r#"
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// The pixels are returned in the format of `P`, which must be compatible
+                    /// with the kind of texture. For example depth textures can only be read as
+                    /// `f32`.
+                    pub fn read<T, P>(&self) -> T where T: Texture2dDataSink<P>, P: PixelValue {{
+                        let (data, width, height, _) = any::download_texture(&self.texture.0,
+                                                                             {layer}, self.level);
+                        T::from_raw(Cow::Owned(data), width, height)
+                    }}
+
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// Same as `read`, but returns `None` instead of panicking if the backend
+                    /// doesn't support reading this texture.
+                    pub fn read_if_supported<T, P>(&self) -> Option<T>
+                                                   where T: Texture2dDataSink<P>, P: PixelValue
+                    {{
+                        any::download_texture_if_supported(&self.texture.0, {layer}, self.level)
+                            .map(|(data, width, height, _)| {{
+                                T::from_raw(Cow::Owned(data), width, height)
+                            }})
+                    }}
+                "#, layer = layer)).unwrap();
+        },
+
+        TextureDimensions::Texture3d => {
+            (write!(dest, r#"
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// The pixels are returned in the format of `P`, which must be compatible
+                    /// with the kind of texture. For example depth textures can only be read as
+                    /// `f32`.
+                    pub fn read<T, P>(&self) -> T where T: Texture3dDataSink<P>, P: PixelValue {{
+                        let (data, width, height, depth) =
+                                            any::download_texture(&self.texture.0, 0, self.level);
+                        T::from_raw(Cow::Owned(data), width, height, depth)
+                    }}
+
+                    /// Reads the content of this mipmap level to RAM.
+                    ///
+                    /// Same as `read`, but returns `None` instead of panicking if the backend
+                    /// doesn't support reading this texture.
+                    pub fn read_if_supported<T, P>(&self) -> Option<T>
+                                                   where T: Texture3dDataSink<P>, P: PixelValue
+                    {{
+                        any::download_texture_if_supported(&self.texture.0, 0, self.level)
+                            .map(|(data, width, height, depth)| {{
+                                T::from_raw(Cow::Owned(data), width, height, depth)
+                            }})
+                    }}
+                "#)).unwrap();
+        },
+
+        // cubemaps are read face by face, and multisample textures can't be read
+        _ => ()
+    }
+}
+
+fn build_cubemap_face<W: Write>(mut dest: &mut W, ty: &TextureType, dimensions: &TextureDimensions,
+                                name: &str)
+{
+    // writing the struct
+    (write!(dest, r#"
+            /// Represents a single face of a mipmap level of a `{name}`.
+            ///
+            /// Can be obtained by calling `{name}Mipmap::face()`.
+            #[derive(Copy, Clone)]
+            pub struct {name}Face<'t> {{
+                texture: &'t {name},
+                {layer}
+                level: u32,
+                face: CubeLayer,
+            }}
+        "#, name = name,
+            layer = if dimensions.is_array() { "layer: u32," } else { "" })).unwrap();
+
+    // opening `impl Face` block
+    (writeln!(dest, "impl<'t> {}Face<'t> {{", name)).unwrap();
+
+    // writing the getters
+    (write!(dest, "
+            /// Returns the corresponding texture.
+            pub fn get_texture(&self) -> &'t {name} {{
+                self.texture
+            }}
+
+            /// Returns the mipmap level.
+            pub fn get_level(&self) -> u32 {{
+                self.level
+            }}
+
+            /// Returns the face.
+            pub fn get_face(&self) -> CubeLayer {{
+                self.face
+            }}
+        ", name = name)).unwrap();
+
+    if dimensions.is_array() {
+        (write!(dest, "
+                /// Returns the index of the cubemap within the array.
+                pub fn get_layer(&self) -> u32 {{
+                    self.layer
+                }}
+
+                /// Returns the index of the face among the layers of the texture.
+                fn get_layer_face(&self) -> u32 {{
+                    self.layer * 6 + self.face.get_layer_index()
+                }}
+            ")).unwrap();
+
+    } else {
+        (write!(dest, "
+                /// Returns the index of the face among the layers of the texture.
+                fn get_layer_face(&self) -> u32 {{
+                    self.face.get_layer_index()
+                }}
+            ")).unwrap();
+    }
+
+    // writing the `write` function
+    match *ty {
+        TextureType::Depth | TextureType::Stencil | TextureType::DepthStencil => (),
+        _ => {
+            (write!(dest, r#"
+                    /// Uploads some data in this face.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the the dimensions of `data` don't match the `Rect`.
+                    pub fn write<'a, T>(&self, rect: Rect, data: T)
+                                        where T: Texture2dDataSource<'a>
+                    {{
+                        let RawImage2d {{ data, width, height, format: client_format }} =
+                                                data.into_raw();
+
+                        assert_eq!(width, rect.width);
+                        assert_eq!(height, rect.height);
+
+                        any::upload_texture(&self.texture.0, rect.left, rect.bottom,
+                                            self.get_layer_face(), (client_format, data), width,
+                                            Some(height), None, self.level, self.level == 0);
+                    }}
+                "#)).unwrap();
+        }
+    }
+
+    // writing the `read` function
+    (write!(dest, r#"
+            /// Reads the content of this face to RAM.
+            ///
+            /// The pixels are returned in the format of `P`, which must be compatible with the
+            /// kind of texture.
+            pub fn read<T, P>(&self) -> T where T: Texture2dDataSink<P>, P: PixelValue {{
+                let (data, width, height, _) = any::download_texture(&self.texture.0,
+                                                                     self.get_layer_face(),
+                                                                     self.level);
+                T::from_raw(Cow::Owned(data), width, height)
+            }}
+
+            /// Reads the content of this face to RAM.
+            ///
+            /// Same as `read`, but returns `None` instead of panicking if the backend doesn't
+            /// support reading this texture.
+            pub fn read_if_supported<T, P>(&self) -> Option<T>
+                                           where T: Texture2dDataSink<P>, P: PixelValue
+            {{
+                any::download_texture_if_supported(&self.texture.0, self.get_layer_face(),
+                                                   self.level)
+                    .map(|(data, width, height, _)| T::from_raw(Cow::Owned(data), width, height))
+            }}
+        "#)).unwrap();
+
+    // writing the `as_surface` function
+    if *ty == TextureType::Regular {
+        (write!(dest, "
+                /// Starts drawing on this face.
+                ///
+                /// All the function calls to the `TextureSurface` will draw on the face instead
+                /// of the screen.
+                pub fn as_surface<'a>(&'a self) -> TextureSurface<'a> {{
+                    TextureSurface(framebuffer::SimpleFrameBuffer::new(self.texture.0.get_context(),
+                                                                       self))
+                }}
+            ")).unwrap();
+    }
+
+    // closing `impl Face` block
+    (writeln!(dest, "}}")).unwrap();
+
+    // conversion to `TextureAnyMipmap`
+    (write!(dest, r#"
+            impl<'t> From<{name}Face<'t>> for TextureAnyMipmap<'t> {{
+                fn from(face: {name}Face<'t>) -> TextureAnyMipmap<'t> {{
+                    face.texture.0.mipmap(face.get_layer_face(), face.level).unwrap()
+                }}
+            }}
+        "#, name = name)).unwrap();
+
+    // `ToXXXAttachment` trait impl
+    let (attachment_trait, function, attachment) = match *ty {
+        TextureType::Regular | TextureType::Srgb => {
+            ("ToColorAttachment", "to_color_attachment", "ColorAttachment")
+        },
+        TextureType::Depth => ("ToDepthAttachment", "to_depth_attachment", "DepthAttachment"),
+        TextureType::Stencil => {
+            ("ToStencilAttachment", "to_stencil_attachment", "StencilAttachment")
+        },
+        TextureType::DepthStencil => {
+            ("ToDepthStencilAttachment", "to_depth_stencil_attachment", "DepthStencilAttachment")
+        },
+        _ => return
+    };
+
+    (writeln!(dest, "
+            impl<'t> ::framebuffer::{attachment_trait} for {name}Face<'t> {{
+                fn {function}(&self) -> ::framebuffer::{attachment} {{
+                    let mipmap = self.texture.0.mipmap(self.get_layer_face(), self.level);
+                    ::framebuffer::{attachment}::Texture(mipmap.unwrap())
+                }}
+            }}
+        ", attachment_trait = attachment_trait, function = function, attachment = attachment,
+           name = name)).unwrap();
+}
@@ -1,4 +1,5 @@
 use backend::Facade;
+use context;
 use context::CommandContext;
 use context::Context;
 use version::Version;
@@ -808,6 +809,12 @@ fn is_buffer_type_supported(ctxt: &mut CommandContext, ty: BufferType) -> bool {
             ctxt.extensions.gl_ext_multi_draw_indirect
         },
 
+        BufferType::DispatchIndirectBuffer => {
+            context::is_compute_supported(ctxt.version, ctxt.extensions)
+        },
+
+        BufferType::ParameterBuffer => ctxt.extensions.gl_arb_indirect_parameters,
+
         _ => false,     // FIXME: 
     }
 }
@@ -852,6 +859,7 @@ unsafe fn bind_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty:
     check!(ctxt, id, ty, CopyWriteBuffer, copy_write_buffer_binding);
     check!(ctxt, id, ty, DispatchIndirectBuffer, dispatch_indirect_buffer_binding);
     check!(ctxt, id, ty, DrawIndirectBuffer, draw_indirect_buffer_binding);
+    check!(ctxt, id, ty, ParameterBuffer, parameter_buffer_binding);
     check!(ctxt, id, ty, QueryBuffer, query_buffer_binding);
     check!(ctxt, id, ty, TextureBuffer, texture_buffer_binding);
     check!(ctxt, id, ty, AtomicCounterBuffer, atomic_counter_buffer_binding);
@@ -1080,6 +1088,10 @@ unsafe fn destroy_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint) {
         ctxt.state.draw_indirect_buffer_binding = 0;
     }
 
+    if ctxt.state.parameter_buffer_binding == id {
+        ctxt.state.parameter_buffer_binding = 0;
+    }
+
     if ctxt.state.query_buffer_binding == id {
         ctxt.state.query_buffer_binding = 0;
     }
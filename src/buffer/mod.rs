@@ -45,6 +45,7 @@ pub enum BufferType {
     AtomicCounterBuffer,
     DispatchIndirectBuffer,
     DrawIndirectBuffer,
+    ParameterBuffer,
     QueryBuffer,
     ShaderStorageBuffer,
     TextureBuffer,
@@ -64,6 +65,7 @@ impl BufferType {
             BufferType::AtomicCounterBuffer => gl::ATOMIC_COUNTER_BUFFER,
             BufferType::DispatchIndirectBuffer => gl::DISPATCH_INDIRECT_BUFFER,
             BufferType::DrawIndirectBuffer => gl::DRAW_INDIRECT_BUFFER,
+            BufferType::ParameterBuffer => gl::PARAMETER_BUFFER_ARB,
             BufferType::QueryBuffer => gl::QUERY_BUFFER,
             BufferType::ShaderStorageBuffer => gl::SHADER_STORAGE_BUFFER,
             BufferType::TextureBuffer => gl::TEXTURE_BUFFER,
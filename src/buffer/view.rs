@@ -521,6 +521,26 @@ impl<'a, T> BufferViewMutSlice<'a, T> where T: PixelValue {
 }
 
 impl BufferViewAny {
+    /// Builds a new buffer containing raw data, considered as a list of elements of
+    /// `elements_size` bytes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of `data` is not a multiple of `elements_size`.
+    pub fn new_raw<F>(facade: &F, data: &[u8], elements_size: usize, ty: BufferType,
+                      dynamic: bool) -> Result<BufferViewAny, BufferCreationError>
+                      where F: Facade
+    {
+        assert!(elements_size != 0 && data.len() % elements_size == 0);
+
+        BufferView::new(facade, data, ty, dynamic).map(|buffer| {
+            let mut buffer: BufferViewAny = buffer.into();
+            buffer.elements_size = elements_size;
+            buffer.elements_count = data.len() / elements_size;
+            buffer
+        })
+    }
+
     /// Builds a slice-any containing the whole subbuffer.
     pub fn as_slice_any(&self) -> BufferViewAnySlice {
         BufferViewAnySlice {
@@ -614,12 +634,52 @@ impl<'a> BufferViewAnySlice<'a> {
         self.elements_size * self.elements_count
     }
 
+    /// Builds a subslice of this slice. Returns `None` if out of range.
+    pub fn slice(&self, range: Range<usize>) -> Option<BufferViewAnySlice<'a>> {
+        if range.start > self.elements_count || range.end > self.elements_count {
+            return None;
+        }
+
+        Some(BufferViewAnySlice {
+            alloc: self.alloc,
+            offset_bytes: self.offset_bytes + range.start * self.elements_size,
+            elements_size: self.elements_size,
+            elements_count: range.end - range.start,
+            fence: self.fence,
+        })
+    }
+
     /// Invalidates the content of the slice. The data becomes undefined.
     ///
     /// This operation is a no-op if the backend doesn't support it.
     pub fn invalidate(&self) {
         self.alloc.invalidate(self.offset_bytes, self.get_size());
     }
+
+    /// Considers that the slice is filled with elements of type `T` and reads them.
+    ///
+    /// # Panic
+    ///
+    /// Panicks if the size of the slice is not a multiple of the size of the data.
+    ///
+    pub unsafe fn read_if_supported<T>(&self) -> Option<Vec<T>> where T: Copy + Send + 'static {
+        assert!(self.get_size() % mem::size_of::<T>() == 0);
+
+        consume_fence(self.alloc.get_context(), self.fence);
+
+        // the content of `data` is uninitialized until the read below overwrites it ; `T` is
+        // `Copy` and thus has no destructor, so nothing uninitialized is dropped if it fails
+        let len = self.get_size() / mem::size_of::<T>();
+        let mut data = Vec::with_capacity(len);
+        data.set_len(len);
+
+        match self.alloc.read_if_supported(self.offset_bytes, &mut data) {
+            Err(_) => return None,
+            Ok(_) => ()
+        };
+
+        Some(data)
+    }
 }
 
 impl<T> BufferViewExt for BufferView<T> where T: Copy + Send + 'static {
@@ -28,6 +28,10 @@ pub struct Capabilities {
     /// `glActiveTexture` must be between `GL_TEXTURE0` and `GL_TEXTURE0` + this value - 1.
     pub max_combined_texture_image_units: gl::types::GLint,
 
+    /// Number of image units that can be used with `glBindImageTexture`. `0` if image load
+    /// and store is not supported.
+    pub max_image_units: gl::types::GLint,
+
     /// Maximum value for `GL_TEXTURE_MAX_ANISOTROPY_EXT​`.
     ///
     /// `None` if the extension is not supported by the hardware.
@@ -42,6 +46,18 @@ pub struct Capabilities {
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
+    /// Maximum number of work groups that can be dispatched in each dimension. `(0, 0, 0)` if
+    /// compute shaders are not supported.
+    pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
+
+    /// Maximum size of a work group in each dimension. `(0, 0, 0)` if compute shaders are
+    /// not supported.
+    pub max_compute_work_group_size: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
+
+    /// Maximum number of invocations in a single work group. `0` if compute shaders are not
+    /// supported.
+    pub max_compute_work_group_invocations: gl::types::GLint,
+
     /// Number of available buffer bind points for `GL_ATOMIC_COUNTER_BUFFER`.
     pub max_indexed_atomic_counter_buffer: gl::types::GLint,
 
@@ -142,6 +158,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             val
         },
 
+        max_image_units: if version >= &Version(Api::Gl, 4, 2) ||
+            version >= &Version(Api::GlEs, 3, 1) || extensions.gl_arb_shader_image_load_store
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_IMAGE_UNITS, &mut val);
+            val
+        } else {
+            0
+        },
+
         max_texture_max_anisotropy: if !extensions.gl_ext_texture_filter_anisotropic {
             None
 
@@ -184,6 +210,36 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             None
         },
 
+        max_compute_work_group_count: if is_compute_supported(version, extensions) {
+            let mut val: [gl::types::GLint; 3] = [0, 0, 0];
+            for (index, value) in val.iter_mut().enumerate() {
+                gl.GetIntegeri_v(gl::MAX_COMPUTE_WORK_GROUP_COUNT, index as gl::types::GLuint,
+                                 value);
+            }
+            (val[0], val[1], val[2])
+        } else {
+            (0, 0, 0)
+        },
+
+        max_compute_work_group_size: if is_compute_supported(version, extensions) {
+            let mut val: [gl::types::GLint; 3] = [0, 0, 0];
+            for (index, value) in val.iter_mut().enumerate() {
+                gl.GetIntegeri_v(gl::MAX_COMPUTE_WORK_GROUP_SIZE, index as gl::types::GLuint,
+                                 value);
+            }
+            (val[0], val[1], val[2])
+        } else {
+            (0, 0, 0)
+        },
+
+        max_compute_work_group_invocations: if is_compute_supported(version, extensions) {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_COMPUTE_WORK_GROUP_INVOCATIONS, &mut val);
+            val
+        } else {
+            0
+        },
+
         max_indexed_atomic_counter_buffer: if version >= &Version(Api::Gl, 4, 2) {      // TODO: ARB_shader_atomic_counters   // TODO: GLES
             let mut val = mem::uninitialized();
             gl.GetIntegerv(gl::MAX_ATOMIC_COUNTER_BUFFER_BINDINGS, &mut val);
@@ -224,6 +280,12 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
     }
 }
 
+/// Returns true if compute shaders are supported by the backend.
+pub fn is_compute_supported(version: &Version, extensions: &ExtensionsList) -> bool {
+    version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 1) ||
+    extensions.gl_arb_compute_shader
+}
+
 /// Gets the list of GLSL versions supported by the backend.
 ///
 /// *Safety*: the OpenGL context corresponding to `gl` must be current in the thread.
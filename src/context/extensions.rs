@@ -8,12 +8,16 @@ use gl;
 pub struct ExtensionsList {
     /// GL_APPLE_vertex_array_object
     pub gl_apple_vertex_array_object: bool,
+    /// GL_ARB_base_instance
+    pub gl_arb_base_instance: bool,
     /// GL_ARB_buffer_storage
     pub gl_arb_buffer_storage: bool,
     /// GL_ARB_compute_shader
     pub gl_arb_compute_shader: bool,
     /// GL_ARB_copy_buffer
     pub gl_arb_copy_buffer: bool,
+    /// GL_ARB_copy_image
+    pub gl_arb_copy_image: bool,
     /// GL_ARB_debug_output
     pub gl_arb_debug_output: bool,
     /// GL_ARB_depth_texture
@@ -22,6 +26,8 @@ pub struct ExtensionsList {
     pub gl_arb_direct_state_access: bool,
     /// GL_ARB_compatibility
     pub gl_arb_compatibility: bool,
+    /// GL_ARB_draw_elements_base_vertex
+    pub gl_arb_draw_elements_base_vertex: bool,
     /// GL_ARB_ES2_compatibility
     pub gl_arb_es2_compatibility: bool,
     /// GL_ARB_ES3_compatibility
@@ -36,6 +42,10 @@ pub struct ExtensionsList {
     pub gl_arb_geometry_shader4: bool,
     /// GL_ARB_get_program_binary
     pub gl_arb_get_programy_binary: bool,
+    /// GL_ARB_get_texture_sub_image
+    pub gl_arb_get_texture_sub_image: bool,
+    /// GL_ARB_indirect_parameters
+    pub gl_arb_indirect_parameters: bool,
     /// GL_ARB_instanced_arrays
     pub gl_arb_instanced_arrays: bool,
     /// GL_ARB_invalidate_subdata
@@ -52,26 +62,38 @@ pub struct ExtensionsList {
     pub gl_arb_pixel_buffer_object: bool,
     /// GL_ARB_sampler_objects
     pub gl_arb_sampler_objects: bool,
+    /// GL_ARB_shader_image_load_store
+    pub gl_arb_shader_image_load_store: bool,
     /// GL_ARB_shader_objects
     pub gl_arb_shader_objects: bool,
+    /// GL_ARB_stencil_texturing
+    pub gl_arb_stencil_texturing: bool,
     /// GL_ARB_sync
     pub gl_arb_sync: bool,
     /// GL_ARB_tessellation_shader
     pub gl_arb_tessellation_shader: bool,
     /// GL_ARB_texture_compression_bptc
     pub gl_arb_texture_compression_bptc: bool,
+    /// GL_ARB_texture_cube_map_array
+    pub gl_arb_texture_cube_map_array: bool,
     /// GL_ARB_texture_float
     pub gl_arb_texture_float: bool,
     /// GL_ARB_texture_multisample
     pub gl_arb_texture_multisample: bool,
     /// GL_ARB_texture_non_power_of_two
     pub gl_arb_texture_non_power_of_two: bool,
+    /// GL_ARB_texture_rectangle
+    pub gl_arb_texture_rectangle: bool,
     /// GL_ARB_texture_rg
     pub gl_arb_texture_rg: bool,
     /// GL_ARB_texture_rgb10_a2ui
     pub gl_arb_texture_rgb10_a2ui: bool,
     /// GL_ARB_texture_storage
     pub gl_arb_texture_storage: bool,
+    /// GL_ARB_texture_swizzle
+    pub gl_arb_texture_swizzle: bool,
+    /// GL_ARB_texture_view
+    pub gl_arb_texture_view: bool,
     /// GL_ARB_timer_query
     pub gl_arb_timer_query: bool,
     /// GL_ARB_transform_feedback3
@@ -110,6 +132,8 @@ pub struct ExtensionsList {
     pub gl_ext_occlusion_query_boolean: bool,
     /// GL_EXT_packed_depth_stencil
     pub gl_ext_packed_depth_stencil: bool,
+    /// GL_EXT_texture_array
+    pub gl_ext_texture_array: bool,
     /// GL_EXT_texture_compression_s3tc
     pub gl_ext_texture_compression_s3tc: bool,
     /// GL_EXT_texture_filter_anisotropic
@@ -118,12 +142,16 @@ pub struct ExtensionsList {
     pub gl_ext_texture_integer: bool,
     /// GL_EXT_texture_sRGB
     pub gl_ext_texture_srgb: bool,
+    /// GL_EXT_texture_swizzle
+    pub gl_ext_texture_swizzle: bool,
     /// GL_EXT_transform_feedback
     pub gl_ext_transform_feedback: bool,
     /// GL_GREMEDY_string_marker
     pub gl_gremedy_string_marker: bool,
     /// GL_KHR_debug
     pub gl_khr_debug: bool,
+    /// GL_KHR_texture_compression_astc_ldr
+    pub gl_khr_texture_compression_astc_ldr: bool,
     /// GL_NV_copy_buffer
     pub gl_nv_copy_buffer: bool,
     /// GL_NV_conditional_render
@@ -158,13 +186,16 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
 
     let mut extensions = ExtensionsList {
         gl_apple_vertex_array_object: false,
+        gl_arb_base_instance: false,
         gl_arb_buffer_storage: false,
         gl_arb_copy_buffer: false,
         gl_arb_compute_shader: false,
+        gl_arb_copy_image: false,
         gl_arb_debug_output: false,
         gl_arb_depth_texture: false,
         gl_arb_direct_state_access: false,
         gl_arb_compatibility: false,
+        gl_arb_draw_elements_base_vertex: false,
         gl_arb_es2_compatibility: false,
         gl_arb_es3_compatibility: false,
         gl_arb_es3_1_compatibility: false,
@@ -172,6 +203,8 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_framebuffer_srgb: false,
         gl_arb_geometry_shader4: false,
         gl_arb_get_programy_binary: false,
+        gl_arb_get_texture_sub_image: false,
+        gl_arb_indirect_parameters: false,
         gl_arb_instanced_arrays: false,
         gl_arb_invalidate_subdata: false,
         gl_arb_occlusion_query: false,
@@ -180,16 +213,22 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_multi_draw_indirect: false,
         gl_arb_pixel_buffer_object: false,
         gl_arb_sampler_objects: false,
+        gl_arb_shader_image_load_store: false,
         gl_arb_shader_objects: false,
+        gl_arb_stencil_texturing: false,
         gl_arb_sync: false,
         gl_arb_tessellation_shader: false,
         gl_arb_texture_compression_bptc: false,
+        gl_arb_texture_cube_map_array: false,
         gl_arb_texture_float: false,
         gl_arb_texture_multisample: false,
         gl_arb_texture_non_power_of_two: false,
+        gl_arb_texture_rectangle: false,
         gl_arb_texture_rg: false,
         gl_arb_texture_rgb10_a2ui: false,
         gl_arb_texture_storage: false,
+        gl_arb_texture_swizzle: false,
+        gl_arb_texture_view: false,
         gl_arb_timer_query: false,
         gl_arb_transform_feedback3: false,
         gl_arb_uniform_buffer_object: false,
@@ -209,13 +248,16 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_ext_multi_draw_indirect: false,
         gl_ext_occlusion_query_boolean: false,
         gl_ext_packed_depth_stencil: false,
+        gl_ext_texture_array: false,
         gl_ext_texture_compression_s3tc: false,
         gl_ext_texture_filter_anisotropic: false,
         gl_ext_texture_integer: false,
         gl_ext_texture_srgb: false,
+        gl_ext_texture_swizzle: false,
         gl_ext_transform_feedback: false,
         gl_gremedy_string_marker: false,
         gl_khr_debug: false,
+        gl_khr_texture_compression_astc_ldr: false,
         gl_nv_conditional_render: false,
         gl_nv_copy_buffer: false,
         gl_nv_pixel_buffer_object: false,
@@ -229,13 +271,16 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
     for extension in strings.into_iter() {
         match &extension[..] {
             "GL_APPLE_vertex_array_object" => extensions.gl_apple_vertex_array_object = true,
+            "GL_ARB_base_instance" => extensions.gl_arb_base_instance = true,
             "GL_ARB_buffer_storage" => extensions.gl_arb_buffer_storage = true,
             "GL_ARB_compute_shader" => extensions.gl_arb_compute_shader = true,
             "GL_ARB_copy_buffer" => extensions.gl_arb_copy_buffer = true,
+            "GL_ARB_copy_image" => extensions.gl_arb_copy_image = true,
             "GL_ARB_debug_output" => extensions.gl_arb_debug_output = true,
             "GL_ARB_depth_texture" => extensions.gl_arb_depth_texture = true,
             "GL_ARB_direct_state_access" => extensions.gl_arb_direct_state_access = true,
             "GL_ARB_compatibility" => extensions.gl_arb_compatibility = true,
+            "GL_ARB_draw_elements_base_vertex" => extensions.gl_arb_draw_elements_base_vertex = true,
             "GL_ARB_ES2_compatibility" => extensions.gl_arb_es2_compatibility = true,
             "GL_ARB_ES3_compatibility" => extensions.gl_arb_es3_compatibility = true,
             "GL_ARB_ES3_1_compatibility" => extensions.gl_arb_es3_1_compatibility = true,
@@ -243,6 +288,8 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_framebuffer_sRGB" => extensions.gl_arb_framebuffer_srgb = true,
             "GL_ARB_geometry_shader4" => extensions.gl_arb_geometry_shader4 = true,
             "GL_ARB_get_program_binary" => extensions.gl_arb_get_programy_binary = true,
+            "GL_ARB_get_texture_sub_image" => extensions.gl_arb_get_texture_sub_image = true,
+            "GL_ARB_indirect_parameters" => extensions.gl_arb_indirect_parameters = true,
             "GL_ARB_instanced_arrays" => extensions.gl_arb_instanced_arrays = true,
             "GL_ARB_invalidate_subdata" => extensions.gl_arb_invalidate_subdata = true,
             "GL_ARB_occlusion_query" => extensions.gl_arb_occlusion_query = true,
@@ -251,16 +298,22 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_map_buffer_range" => extensions.gl_arb_map_buffer_range = true,
             "GL_ARB_multi_draw_indirect" => extensions.gl_arb_multi_draw_indirect = true,
             "GL_ARB_sampler_objects" => extensions.gl_arb_sampler_objects = true,
+            "GL_ARB_shader_image_load_store" => extensions.gl_arb_shader_image_load_store = true,
             "GL_ARB_shader_objects" => extensions.gl_arb_shader_objects = true,
+            "GL_ARB_stencil_texturing" => extensions.gl_arb_stencil_texturing = true,
             "GL_ARB_sync" => extensions.gl_arb_sync = true,
             "GL_ARB_tessellation_shader" => extensions.gl_arb_tessellation_shader = true,
             "GL_ARB_texture_compression_bptc" => extensions.gl_arb_texture_compression_bptc = true,
+            "GL_ARB_texture_cube_map_array" => extensions.gl_arb_texture_cube_map_array = true,
             "GL_ARB_texture_float" => extensions.gl_arb_texture_float = true,
             "GL_ARB_texture_multisample" => extensions.gl_arb_texture_multisample = true,
             "GL_ARB_texture_non_power_of_two" => extensions.gl_arb_texture_non_power_of_two = true,
+            "GL_ARB_texture_rectangle" => extensions.gl_arb_texture_rectangle = true,
             "GL_ARB_texture_rg" => extensions.gl_arb_texture_rg = true,
             "GL_ARB_texture_rgb10_a2ui" => extensions.gl_arb_texture_rgb10_a2ui = true,
             "GL_ARB_texture_storage" => extensions.gl_arb_texture_storage = true,
+            "GL_ARB_texture_swizzle" => extensions.gl_arb_texture_swizzle = true,
+            "GL_ARB_texture_view" => extensions.gl_arb_texture_view = true,
             "GL_ARB_timer_query" => extensions.gl_arb_timer_query = true,
             "GL_ARB_transform_feedback3" => extensions.gl_arb_transform_feedback3 = true,
             "GL_ARB_uniform_buffer_object" => extensions.gl_arb_uniform_buffer_object = true,
@@ -280,13 +333,16 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_EXT_multi_draw_indirect" => extensions.gl_ext_multi_draw_indirect = true,
             "GL_EXT_occlusion_query_boolean" => extensions.gl_ext_occlusion_query_boolean = true,
             "GL_EXT_packed_depth_stencil" => extensions.gl_ext_packed_depth_stencil = true,
+            "GL_EXT_texture_array" => extensions.gl_ext_texture_array = true,
             "GL_EXT_texture_compression_s3tc" => extensions.gl_ext_texture_compression_s3tc = true,
             "GL_EXT_texture_filter_anisotropic" => extensions.gl_ext_texture_filter_anisotropic = true,
             "GL_EXT_texture_integer" => extensions.gl_ext_texture_integer = true,
             "GL_EXT_texture_sRGB" => extensions.gl_ext_texture_srgb = true,
+            "GL_EXT_texture_swizzle" => extensions.gl_ext_texture_swizzle = true,
             "GL_EXT_transform_feedback" => extensions.gl_ext_transform_feedback = true,
             "GL_GREMEDY_string_marker" => extensions.gl_gremedy_string_marker = true,
             "GL_KHR_debug" => extensions.gl_khr_debug = true,
+            "GL_KHR_texture_compression_astc_ldr" => extensions.gl_khr_texture_compression_astc_ldr = true,
             "GL_NV_conditional_render" => extensions.gl_nv_conditional_render = true,
             "GL_NV_copy_buffer" => extensions.gl_nv_copy_buffer = true,
             "GL_NV_pixel_buffer_object" => extensions.gl_nv_pixel_buffer_object = true,
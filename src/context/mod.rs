@@ -15,6 +15,8 @@ use std::rc::Rc;
 
 use GliumCreationError;
 use ContextExt;
+use ToGlEnum;
+use Program;
 use backend::Backend;
 use version;
 use version::Api;
@@ -27,9 +29,9 @@ use texture;
 use uniforms;
 use vertex_array_object;
 
-pub use self::capabilities::Capabilities;
+pub use self::capabilities::{Capabilities, is_compute_supported};
 pub use self::extensions::ExtensionsList;
-pub use self::state::GlState;
+pub use self::state::{GlState, ImageUnitState, TextureParametersState};
 
 mod capabilities;
 mod extensions;
@@ -78,6 +80,11 @@ pub struct Context {
 
     /// We maintain a list of samplers for each possible behavior.
     samplers: RefCell<HashMap<uniforms::SamplerBehavior, sampler_object::SamplerObject>>,
+
+    /// Programs that copy one sample of a multisample texture, indexed by the type of sampler
+    /// that they use. They are built the first time they are needed and don't keep the context
+    /// alive.
+    copy_sample_programs: RefCell<HashMap<gl::types::GLenum, Program>>,
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -177,6 +184,7 @@ impl Context {
             framebuffer_objects: Some(fbo::FramebuffersContainer::new()),
             vertex_array_objects: vertex_array_objects,
             samplers: RefCell::new(HashMap::new()),
+            copy_sample_programs: RefCell::new(HashMap::new()),
         });
 
         init_debug_callback(&context);
@@ -367,6 +375,20 @@ impl Context {
         unsafe { ctxt.gl.Finish(); }
     }
 
+    /// Makes sure that the stores done by the previous commands through image units are
+    /// visible to the kinds of accesses given as parameter.
+    ///
+    /// Does nothing if image load and store is not supported by the backend.
+    pub fn memory_barrier(&self, barrier: uniforms::MemoryBarrier) {
+        let ctxt = self.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+           ctxt.extensions.gl_arb_shader_image_load_store
+        {
+            unsafe { ctxt.gl.MemoryBarrier(barrier.to_glenum()); }
+        }
+    }
+
     /// Inserts a debugging string in the commands queue. If you use an OpenGL debugger, you will
     /// be able to see that string.
     ///
@@ -440,6 +462,10 @@ impl ContextExt for Context {
         &self.samplers
     }
 
+    fn get_copy_sample_programs(&self) -> &RefCell<HashMap<gl::types::GLenum, Program>> {
+        &self.copy_sample_programs
+    }
+
     fn capabilities(&self) -> &Capabilities {
         &self.capabilities
     }
@@ -461,6 +487,11 @@ impl Drop for Context {
                 }
             }
 
+            // the programs don't keep the context alive, so they must be destroyed first
+            let programs = mem::replace(&mut *self.copy_sample_programs.borrow_mut(),
+                                        HashMap::with_capacity(0));
+            mem::drop(programs);
+
             let mut ctxt = CommandContext {
                 gl: &self.gl,
                 state: self.state.borrow_mut(),
@@ -588,6 +619,18 @@ fn check_gl_compatibility<T>(ctxt: &mut CommandContext) -> Result<(), GliumCreat
         result.push("OpenGL implementation doesn't support arrays of multisample textures");
     }
 
+    if cfg!(feature = "gl_texture_cubemap_array") && !(ctxt.version >= &Version(Api::Gl, 4, 0)) &&
+        !ctxt.extensions.gl_arb_texture_cube_map_array
+    {
+        result.push("OpenGL implementation doesn't support arrays of cubemaps");
+    }
+
+    if cfg!(feature = "gl_texture_rectangle") && !(ctxt.version >= &Version(Api::Gl, 3, 1)) &&
+        !ctxt.extensions.gl_arb_texture_rectangle
+    {
+        result.push("OpenGL implementation doesn't support rectangle textures");
+    }
+
     if result.len() == 0 {
         Ok(())
     } else {
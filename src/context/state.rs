@@ -1,6 +1,10 @@
 use Handle;
 use gl;
 
+use uniforms::SamplerBehavior;
+
+use std::collections::HashMap;
+
 /// Represents the current OpenGL state.
 ///
 /// The current state is passed to each function and can be freely updated.
@@ -32,6 +36,12 @@ pub struct GlState {
     /// Whether GL_POLYGON_OFFSET_FILL is enabled
     pub enabled_polygon_offset_fill: bool,
 
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
+    pub enabled_primitive_restart_fixed_index: bool,
+
     /// Whether GL_RASTERIZER_DISCARD is enabled
     pub enabled_rasterizer_discard: bool,
 
@@ -94,6 +104,9 @@ pub struct GlState {
     /// The latest buffer bound to `GL_DRAW_INDIRECT_BUFFER`.
     pub draw_indirect_buffer_binding: gl::types::GLuint,
 
+    /// The latest buffer bound to `GL_PARAMETER_BUFFER_ARB`.
+    pub parameter_buffer_binding: gl::types::GLuint,
+
     /// The latest buffer bound to `GL_QUERY_BUFFER`.
     pub query_buffer_binding: gl::types::GLuint,
 
@@ -190,6 +203,9 @@ pub struct GlState {
     /// The latest value passed to `glPatchParameter` with `GL_PATCH_VERTICES`.
     pub patch_patch_vertices: gl::types::GLint,
 
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
     /// The id of the active texture unit.
     /// IMPORTANT: this is a raw number (0, 1, 2, ...), not an
     ///            enumeration (GL_TEXTURE0, GL_TEXTURE1, ...).
@@ -198,6 +214,13 @@ pub struct GlState {
     /// List of texture units.
     pub texture_units: Vec<TextureUnitState>,
 
+    /// List of image units (the ones designated by `glBindImageTexture`).
+    pub image_units: Vec<ImageUnitState>,
+
+    /// Sampling parameters that have been set on textures because sampler objects are not
+    /// supported. Textures that are not in this list have the parameters they were created with.
+    pub texture_parameters: HashMap<gl::types::GLuint, TextureParametersState>,
+
     /// Current query being used for GL_SAMPLES_PASSED​.
     pub samples_passed_query: gl::types::GLuint,
 
@@ -238,6 +261,38 @@ pub struct TextureUnitState {
     pub sampler: gl::types::GLuint,
 }
 
+/// Sampling parameters of a texture, when sampler objects are not supported.
+#[derive(Copy, Clone, Debug)]
+pub struct TextureParametersState {
+    /// Parameters that the texture had before they were changed for the first time.
+    pub initial: SamplerBehavior,
+
+    /// Parameters that are currently set on the texture.
+    pub current: SamplerBehavior,
+}
+
+/// State of an image unit (the one designated by `glBindImageTexture`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImageUnitState {
+    /// Id of the texture.
+    pub texture: gl::types::GLuint,
+
+    /// Mipmap level of the texture.
+    pub level: gl::types::GLint,
+
+    /// True if all the layers of the texture are bound.
+    pub layered: bool,
+
+    /// The layer that is bound if `layered` is false.
+    pub layer: gl::types::GLint,
+
+    /// `GL_READ_ONLY`, `GL_WRITE_ONLY` or `GL_READ_WRITE`.
+    pub access: gl::types::GLenum,
+
+    /// Format used when loading from or storing to the image.
+    pub format: gl::types::GLenum,
+}
+
 /// State of an indexed buffer target (`glBindBufferRange`/`glBindBufferBase`).
 #[derive(Copy, Clone, Debug)]
 pub struct IndexedBufferState {
@@ -264,6 +319,8 @@ impl Default for GlState {
             enabled_framebuffer_srgb: false,
             enabled_multisample: true,
             enabled_polygon_offset_fill: false,
+            enabled_primitive_restart: false,
+            enabled_primitive_restart_fixed_index: false,
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
             enabled_sample_coverage: false,
@@ -285,6 +342,7 @@ impl Default for GlState {
             copy_write_buffer_binding: 0,
             dispatch_indirect_buffer_binding: 0,
             draw_indirect_buffer_binding: 0,
+            parameter_buffer_binding: 0,
             query_buffer_binding: 0,
             texture_buffer_binding: 0,
             atomic_counter_buffer_binding: 0,
@@ -316,8 +374,11 @@ impl Default for GlState {
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
             patch_patch_vertices: 3,
+            primitive_restart_index: 0,
             active_texture: 0,
             texture_units: vec![Default::default()],
+            image_units: Vec::new(),
+            texture_parameters: HashMap::new(),
             samples_passed_query: 0,
             any_samples_passed_query: 0,
             any_samples_passed_conservative_query: 0,
@@ -340,6 +401,19 @@ impl Default for TextureUnitState {
     }
 }
 
+impl Default for ImageUnitState {
+    fn default() -> ImageUnitState {
+        ImageUnitState {
+            texture: 0,
+            level: 0,
+            layered: false,
+            layer: 0,
+            access: gl::READ_ONLY,
+            format: gl::R32UI,
+        }
+    }
+}
+
 impl Default for IndexedBufferState {
     fn default() -> IndexedBufferState {
         IndexedBufferState {
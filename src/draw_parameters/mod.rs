@@ -754,6 +754,44 @@ pub struct DrawParameters<'a> {
 
     /// If set, then the generated primitives will be written back to a buffer.
     pub transform_feedback: Option<&'a TransformFeedbackSession<'a>>,
+
+    /// Number of instances to draw.
+    ///
+    /// `None` means "deduce the number of instances from the per-instance vertex sources". If
+    /// there is no per-instance source, then instancing is not used.
+    ///
+    /// If you pass a value while also using per-instance sources, these sources must be long
+    /// enough for this number of instances or a `DrawError::InstancesCountMismatch` will be
+    /// returned. Passing a value returns a `DrawError::InstancingNotSupported` if the backend
+    /// doesn't support instancing. The default value is `None`.
+    pub instances_count: Option<u32>,
+
+    /// Value that is added to each index before fetching the vertex in the vertices sources.
+    ///
+    /// If you don't use indices, this is the first vertex to draw. The number of vertices
+    /// drawn is then the length of the vertices sources minus this value.
+    ///
+    /// This allows you to draw sub-meshes of a shared vertex buffer without having to modify
+    /// the indices. If the backend doesn't support `glDrawElementsBaseVertex`, glium emulates
+    /// this by offsetting the vertices sources, which doesn't work with negative values.
+    /// The default value is `0`.
+    pub base_vertex: i32,
+
+    /// Index of the first element to use in the per-instance vertex sources.
+    ///
+    /// This doesn't change the value of `gl_InstanceID` in the vertex shader. If the backend
+    /// doesn't support `glDrawElementsInstancedBaseVertexBaseInstance`, glium emulates this by
+    /// offsetting the per-instance sources. The default value is `0`.
+    pub base_instance: u32,
+
+    /// If true, the maximum value of the index type (`0xff` for `u8`, `0xffff` for `u16` and
+    /// `0xffffffff` for `u32`) ends the current primitive and starts a new one.
+    ///
+    /// This allows you to draw multiple strips or fans with a single index buffer and a single
+    /// draw call. Using this with primitives that are not strips or fans will return a
+    /// `DrawError::PrimitiveRestartWithWrongPrimitives`. It has no effect if you don't use
+    /// indices. The default value is `false`.
+    pub primitive_restart_index: bool,
 }
 
 /// Condition whether to render or not.
@@ -827,6 +865,10 @@ impl<'a> Default for DrawParameters<'a> {
             transform_feedback_primitives_written_query: None,
             condition: None,
             transform_feedback: None,
+            instances_count: None,
+            base_vertex: 0,
+            base_instance: 0,
+            primitive_restart_index: false,
         }
     }
 }
@@ -970,6 +1012,39 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets the number of instances to draw, instead of deducing it from the per-instance
+    /// vertex sources.
+    pub fn with_instances_count(mut self, count: u32) -> DrawParametersBuilder<'a> {
+        self.params.instances_count = Some(count);
+        self
+    }
+
+    /// Sets the value that is added to each index before fetching the vertex, and the index of
+    /// the first element to use in the per-instance vertex sources.
+    pub fn with_base_vertex_and_instance(mut self, base_vertex: i32, base_instance: u32)
+                                         -> DrawParametersBuilder<'a>
+    {
+        self.params.base_vertex = base_vertex;
+        self.params.base_instance = base_instance;
+        self
+    }
+
+    /// Sets that the maximum value of the index type ends the current primitive and starts
+    /// a new one.
+    ///
+    /// Returns `Err` if the backend doesn't support this operation.
+    pub fn with_primitive_restart_index_if_supported(mut self)
+                                                     -> Result<DrawParametersBuilder<'a>,
+                                                               DrawParametersBuilder<'a>>
+    {
+        if !is_primitive_restart_supported(&self.context) {
+            return Err(self);
+        }
+
+        self.params.primitive_restart_index = true;
+        Ok(self)
+    }
+
     /// See the `ConditionalRendering` struct.
     pub fn with_conditional_rendering<Q>(mut self, query: Q, wait: bool, per_region: bool)
                                          -> DrawParametersBuilder<'a>
@@ -1013,5 +1088,16 @@ pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawEr
         return Err(DrawError::TransformFeedbackNotSupported);
     }
 
+    if params.primitive_restart_index && !is_primitive_restart_supported(context) {
+        return Err(DrawError::FixedIndexRestartingNotSupported);
+    }
+
     Ok(())
 }
+
+/// Returns true if the backend supports restarting primitives with the maximum index value.
+fn is_primitive_restart_supported(context: &Context) -> bool {
+    context.get_version() >= &Version(Api::Gl, 3, 1) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+    context.get_extensions().gl_arb_es3_compatibility
+}
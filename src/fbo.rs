@@ -447,6 +447,37 @@ impl FramebuffersContainer {
         (framebuffer, gl::COLOR_ATTACHMENT0)
     }
 
+    /// Binds a framebuffer that contains the attachment to `GL_READ_FRAMEBUFFER` and selects
+    /// the attachment with `glReadBuffer`.
+    ///
+    /// The read buffer of each framebuffer object is cached, so `glReadBuffer` is only called
+    /// when it changes.
+    ///
+    /// # Unsafety
+    ///
+    /// After calling this function, you **must** make sure to call `purge_texture`
+    /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub fn bind_framebuffer_for_reading(&self, attachment: &Attachment,
+                                        ctxt: &mut CommandContext)
+    {
+        let (fbo_id, read_buffer) = self.get_framebuffer_for_reading(attachment, ctxt);
+        bind_framebuffer(ctxt, fbo_id, false, true);
+
+        let mut framebuffers = self.framebuffers.borrow_mut();
+        let fbo = framebuffers.values_mut().find(|fbo| fbo.id == fbo_id).unwrap();
+
+        if fbo.current_read_buffer == read_buffer {
+            return;
+        }
+
+        // OpenGL ES 2.0 doesn't have `glReadBuffer`, but only supports `GL_COLOR_ATTACHMENT0`
+        if ctxt.version >= &Version(Api::Gl, 1, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            unsafe { ctxt.gl.ReadBuffer(read_buffer); }
+        }
+
+        fbo.current_read_buffer = read_buffer;
+    }
+
     ///
     /// # Unsafety
     ///
@@ -643,9 +674,40 @@ pub fn bind_framebuffer(ctxt: &mut CommandContext, fbo_id: gl::types::GLuint,
     }
 }
 
+/// Binds the default framebuffer to `GL_READ_FRAMEBUFFER` and selects one of its buffers with
+/// `glReadBuffer`.
+///
+/// The read buffer of the default framebuffer is cached in the state, so `glReadBuffer` is only
+/// called when it changes.
+pub fn bind_default_framebuffer_for_reading(ctxt: &mut CommandContext,
+                                            read_buffer: gl::types::GLenum)
+{
+    bind_framebuffer(ctxt, 0, false, true);
+
+    if ctxt.state.default_framebuffer_read == Some(read_buffer) {
+        return;
+    }
+
+    // OpenGL ES 2.0 doesn't have `glReadBuffer`, but only supports `GL_BACK`
+    if ctxt.version >= &Version(Api::Gl, 1, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        unsafe { ctxt.gl.ReadBuffer(read_buffer); }
+    }
+
+    ctxt.state.default_framebuffer_read = Some(read_buffer);
+}
+
 unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                  id: gl::types::GLuint, attachment: RawAttachment)
 {
+    // attaching a whole cubemap would create a layered attachment, so faces are handled
+    // separately
+    if let RawAttachment::Texture { bind_point, texture: tex_id, level, layer } = attachment {
+        if bind_point == gl::TEXTURE_CUBE_MAP || bind_point == gl::TEXTURE_CUBE_MAP_ARRAY {
+            attach_cubemap_face(ctxt, slot, id, bind_point, tex_id, level, layer);
+            return;
+        }
+    }
+
     if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
         match attachment {
             RawAttachment::Texture { texture: tex_id, level, layer, .. } => {
@@ -711,13 +773,14 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         match attachment {
             RawAttachment::Texture { bind_point, texture: tex_id, level, layer } => {
                 match bind_point {
-                    gl::TEXTURE_1D | gl::TEXTURE_RECTANGLE => {
+                    gl::TEXTURE_1D => {
                         assert!(layer == 0);
                         ctxt.gl.FramebufferTexture1D(gl::DRAW_FRAMEBUFFER,
                                                      slot, bind_point, tex_id,
                                                      level as gl::types::GLint);
                     },
-                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_1D_ARRAY => {
+                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_1D_ARRAY |
+                    gl::TEXTURE_RECTANGLE => {
                         assert!(layer == 0);
                         ctxt.gl.FramebufferTexture2D(gl::DRAW_FRAMEBUFFER,
                                                      slot, bind_point, tex_id,
@@ -765,13 +828,14 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         match attachment {
             RawAttachment::Texture { bind_point, texture: tex_id, level, layer } => {
                 match bind_point {
-                    gl::TEXTURE_1D | gl::TEXTURE_RECTANGLE => {
+                    gl::TEXTURE_1D => {
                         assert!(layer == 0);
                         ctxt.gl.FramebufferTexture1DEXT(gl::FRAMEBUFFER_EXT,
                                                         slot, bind_point, tex_id,
                                                         level as gl::types::GLint);
                     },
-                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_1D_ARRAY => {
+                    gl::TEXTURE_2D | gl::TEXTURE_2D_MULTISAMPLE | gl::TEXTURE_1D_ARRAY |
+                    gl::TEXTURE_RECTANGLE => {
                         assert!(layer == 0);
                         ctxt.gl.FramebufferTexture2DEXT(gl::FRAMEBUFFER_EXT,
                                                         slot, bind_point, tex_id,
@@ -796,3 +860,46 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
         unreachable!();
     }
 }
+
+/// Attaches a single face of a cubemap or of an array of cubemaps to the framebuffer.
+///
+/// For arrays, `layer` is `6 * cube + face`.
+unsafe fn attach_cubemap_face(ctxt: &mut CommandContext, slot: gl::types::GLenum,
+                              id: gl::types::GLuint, bind_point: gl::types::GLenum,
+                              tex_id: gl::types::GLuint, level: u32, layer: u32)
+{
+    if bind_point == gl::TEXTURE_CUBE_MAP_ARRAY {
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+            ctxt.gl.NamedFramebufferTextureLayer(id, slot, tex_id, level as gl::types::GLint,
+                                                 layer as gl::types::GLint);
+        } else {
+            bind_framebuffer(ctxt, id, true, false);
+            ctxt.gl.FramebufferTextureLayer(gl::DRAW_FRAMEBUFFER, slot, tex_id,
+                                            level as gl::types::GLint,
+                                            layer as gl::types::GLint);
+        }
+
+        return;
+    }
+
+    let target = gl::TEXTURE_CUBE_MAP_POSITIVE_X + layer;
+
+    if ctxt.version >= &Version(Api::Gl, 3, 0) {
+        bind_framebuffer(ctxt, id, true, false);
+        ctxt.gl.FramebufferTexture2D(gl::DRAW_FRAMEBUFFER, slot, target, tex_id,
+                                     level as gl::types::GLint);
+
+    } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        bind_framebuffer(ctxt, id, true, true);
+        ctxt.gl.FramebufferTexture2D(gl::FRAMEBUFFER, slot, target, tex_id,
+                                     level as gl::types::GLint);
+
+    } else if ctxt.extensions.gl_ext_framebuffer_object {
+        bind_framebuffer(ctxt, id, true, true);
+        ctxt.gl.FramebufferTexture2DEXT(gl::FRAMEBUFFER_EXT, slot, target, tex_id,
+                                        level as gl::types::GLint);
+
+    } else {
+        unreachable!();
+    }
+}
@@ -0,0 +1,1131 @@
+/*!
+Loading glTF 2.0 scenes.
+
+This module is only available if the `gltf` feature is enabled.
+
+A `Scene` contains the glium objects that correspond to the content of a `.gltf` or `.glb`
+file: vertex and index buffers for each primitive, textures for each image, the materials,
+and a flat list of the meshes to draw with their transformation.
+
+```no_run
+# #[macro_use] extern crate glium;
+# fn main() {
+# use glium::Surface;
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+# let mut frame = display.draw();
+let scene = glium::gltf::load(&display, "scene.gltf").unwrap();
+
+for item in scene.draw_list.iter() {
+    for primitive in scene.meshes[item.mesh].primitives.iter() {
+        let uniforms = uniform! { model: item.transform };
+        frame.draw(&primitive.vertex_buffer, primitive.get_indices_source(), &program,
+                   &uniforms, &Default::default()).unwrap();
+    }
+}
+# }
+```
+
+# Vertex attributes
+
+The vertex buffer of each primitive contains all the attributes of the primitive, named
+after their glTF semantic (`POSITION`, `NORMAL`, `TEXCOORD_0`, `COLOR_0`, `JOINTS_0`, etc.).
+
+Floating-point and normalized integer attributes are converted to `f32`s, while the other
+integer attributes keep their type.
+
+# Textures
+
+Images are uploaded with their first row at the texture coordinate `0.0`, which matches
+the convention of glTF. The images used as base color or emissive textures are uploaded
+as `SrgbTexture2d`s, and the others as `Texture2d`s.
+
+*/
+use std::borrow::Cow;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::{fmt, mem, str};
+
+use image::{self, GenericImage};
+use rustc_serialize::base64::FromBase64;
+use rustc_serialize::json::Json;
+
+use backend::Facade;
+use index::{IndexBuffer, IndexBufferAny, IndicesSource, NoIndices, PrimitiveType};
+use texture::{ClientFormat, RawImage2d, SrgbTexture2d, Texture2d};
+use uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerBehavior, SamplerWrapFunction};
+use vertex::{AttributeType, VertexBufferAny};
+
+/// Error that can happen while loading a glTF file.
+#[derive(Debug)]
+pub enum GltfError {
+    /// Error while reading a file.
+    IoError(io::Error),
+
+    /// The JSON content of the file is not valid.
+    InvalidJson(String),
+
+    /// The file is valid JSON but not a valid glTF document.
+    InvalidGltf(String),
+
+    /// The file uses a feature that is not supported.
+    UnsupportedFeature(String),
+
+    /// Error while decoding an image.
+    ImageError(image::ImageError),
+}
+
+impl fmt::Display for GltfError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &GltfError::IoError(ref err) =>
+                formatter.write_fmt(format_args!("Error while reading a file: {}", err)),
+            &GltfError::InvalidJson(ref s) =>
+                formatter.write_fmt(format_args!("Invalid JSON: {}", s)),
+            &GltfError::InvalidGltf(ref s) =>
+                formatter.write_fmt(format_args!("Invalid glTF document: {}", s)),
+            &GltfError::UnsupportedFeature(ref s) =>
+                formatter.write_fmt(format_args!("Unsupported glTF feature: {}", s)),
+            &GltfError::ImageError(ref err) =>
+                formatter.write_fmt(format_args!("Error while decoding an image: {}", err)),
+        }
+    }
+}
+
+impl Error for GltfError {
+    fn description(&self) -> &str {
+        match self {
+            &GltfError::IoError(_) => "Error while reading a file",
+            &GltfError::InvalidJson(_) => "The JSON content of the file is not valid",
+            &GltfError::InvalidGltf(_) => "The file is not a valid glTF document",
+            &GltfError::UnsupportedFeature(_) => "The file uses an unsupported feature",
+            &GltfError::ImageError(_) => "Error while decoding an image",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &GltfError::IoError(ref err) => Some(err as &Error),
+            &GltfError::ImageError(ref err) => Some(err as &Error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for GltfError {
+    fn from(err: io::Error) -> GltfError {
+        GltfError::IoError(err)
+    }
+}
+
+impl From<image::ImageError> for GltfError {
+    fn from(err: image::ImageError) -> GltfError {
+        GltfError::ImageError(err)
+    }
+}
+
+/// The content of a glTF file, uploaded in video memory.
+pub struct Scene {
+    /// The meshes of the file.
+    pub meshes: Vec<Mesh>,
+
+    /// The materials of the file.
+    pub materials: Vec<Material>,
+
+    /// The images of the file.
+    pub images: Vec<Image>,
+
+    /// The meshes to draw, in the order in which the nodes of the scene are visited.
+    pub draw_list: Vec<DrawItem>,
+}
+
+/// A mesh made of one or more primitives.
+pub struct Mesh {
+    /// Name of the mesh.
+    pub name: Option<String>,
+
+    /// The primitives of the mesh.
+    pub primitives: Vec<Primitive>,
+}
+
+/// Geometry to draw with a single draw call.
+pub struct Primitive {
+    /// The vertices of the primitive.
+    pub vertex_buffer: VertexBufferAny,
+
+    /// The indices of the primitive, or `None` if the primitive doesn't use indices.
+    pub index_buffer: Option<IndexBufferAny>,
+
+    /// The type of primitives.
+    pub primitive_type: PrimitiveType,
+
+    /// Index of the material in `Scene::materials`.
+    pub material: Option<usize>,
+}
+
+impl Primitive {
+    /// Returns the source of indices to pass to the `draw` function.
+    pub fn get_indices_source(&self) -> IndicesSource {
+        match self.index_buffer {
+            Some(ref buffer) => buffer.into(),
+            None => NoIndices(self.primitive_type).into(),
+        }
+    }
+}
+
+/// An image of the file, uploaded as a texture.
+pub struct Image {
+    /// The image as a linear texture. Present if the image is not only used as a base color or
+    /// emissive texture.
+    pub texture: Option<Texture2d>,
+
+    /// The image as an sRGB texture. Present if the image is used as a base color or emissive
+    /// texture.
+    pub srgb_texture: Option<SrgbTexture2d>,
+}
+
+/// Reference to an image from a material.
+#[derive(Debug, Clone, Copy)]
+pub struct TextureRef {
+    /// Index of the image in `Scene::images`.
+    pub image: usize,
+
+    /// How the texture must be sampled.
+    pub sampler: SamplerBehavior,
+
+    /// Number of the `TEXCOORD_n` attribute to use.
+    pub texcoord: u32,
+}
+
+/// How the alpha value of the base color must be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaMode {
+    /// The alpha value is ignored.
+    Opaque,
+    /// The fragment is discarded if the alpha value is below `alpha_cutoff`.
+    Mask,
+    /// The alpha value is used for blending.
+    Blend,
+}
+
+/// A physically-based material.
+#[derive(Debug, Clone)]
+pub struct Material {
+    /// Name of the material.
+    pub name: Option<String>,
+
+    /// Base color, multiplied with the base color texture.
+    pub base_color_factor: [f32; 4],
+
+    /// Base color texture. The image is available as an sRGB texture.
+    pub base_color_texture: Option<TextureRef>,
+
+    /// Metalness, multiplied with the blue channel of the metallic-roughness texture.
+    pub metallic_factor: f32,
+
+    /// Roughness, multiplied with the green channel of the metallic-roughness texture.
+    pub roughness_factor: f32,
+
+    /// Metallic-roughness texture.
+    pub metallic_roughness_texture: Option<TextureRef>,
+
+    /// Tangent-space normal map.
+    pub normal_texture: Option<TextureRef>,
+
+    /// Ambient occlusion texture.
+    pub occlusion_texture: Option<TextureRef>,
+
+    /// Emissive color, multiplied with the emissive texture.
+    pub emissive_factor: [f32; 3],
+
+    /// Emissive texture. The image is available as an sRGB texture.
+    pub emissive_texture: Option<TextureRef>,
+
+    /// How the alpha value of the base color must be interpreted.
+    pub alpha_mode: AlphaMode,
+
+    /// Threshold used when `alpha_mode` is `Mask`.
+    pub alpha_cutoff: f32,
+
+    /// If true, back faces must not be culled.
+    pub double_sided: bool,
+}
+
+/// A mesh to draw.
+#[derive(Debug, Clone)]
+pub struct DrawItem {
+    /// Index of the mesh in `Scene::meshes`.
+    pub mesh: usize,
+
+    /// Name of the node that contains the mesh.
+    pub node_name: Option<String>,
+
+    /// Column-major matrix that transforms the mesh into the coordinates of the scene.
+    pub transform: [[f32; 4]; 4],
+}
+
+/// Loads a `.gltf` or `.glb` file.
+///
+/// External buffers and images are loaded relative to the directory of the file.
+pub fn load<F, P>(facade: &F, path: P) -> Result<Scene, GltfError>
+                  where F: Facade, P: AsRef<Path>
+{
+    let path = path.as_ref();
+
+    let mut data = Vec::new();
+    try!(try!(File::open(path)).read_to_end(&mut data));
+
+    load_from_memory(facade, &data, path.parent())
+}
+
+/// Loads the content of a `.gltf` or `.glb` file.
+///
+/// External buffers and images are loaded relative to `base_dir`. If `base_dir` is `None`,
+/// the file must not reference any external file.
+pub fn load_from_memory<F>(facade: &F, data: &[u8], base_dir: Option<&Path>)
+                           -> Result<Scene, GltfError> where F: Facade
+{
+    let (json, binary_chunk) = if data.len() >= 4 && &data[.. 4] == b"glTF" {
+        try!(parse_glb(data))
+    } else {
+        (data, None)
+    };
+
+    let json = match str::from_utf8(json) {
+        Ok(j) => j,
+        Err(_) => return Err(GltfError::InvalidJson("the document is not valid UTF-8"
+                                                    .to_string())),
+    };
+
+    let json = match Json::from_str(json) {
+        Ok(j) => j,
+        Err(err) => return Err(GltfError::InvalidJson(format!("{:?}", err))),
+    };
+
+    let version = try!(get_string(try!(get_object(&json, "asset")), "version"));
+    if !version.map(|v| v.starts_with("2.")).unwrap_or(false) {
+        return Err(GltfError::UnsupportedFeature("only glTF 2.0 is supported".to_string()));
+    }
+
+    let required = try!(get_array(&json, "extensionsRequired"));
+    if !required.is_empty() {
+        let names = required.iter().filter_map(|e| e.as_string()).collect::<Vec<_>>();
+        return Err(GltfError::UnsupportedFeature(format!("required extensions: {}",
+                                                         names.connect(", "))));
+    }
+
+    let mut buffers = Vec::new();
+    for buffer in try!(get_array(&json, "buffers")).into_iter() {
+        let length = try!(require_usize(buffer, "byteLength"));
+
+        let data = match try!(get_string(buffer, "uri")) {
+            Some(uri) => try!(load_uri(uri, base_dir)),
+            None => match binary_chunk {
+                Some(chunk) if buffers.is_empty() => chunk.to_vec(),
+                _ => return Err(invalid("a buffer doesn't have any data")),
+            },
+        };
+
+        if data.len() < length {
+            return Err(invalid("a buffer is shorter than its `byteLength`"));
+        }
+
+        buffers.push(data);
+    }
+
+    let document = Document {
+        json: &json,
+        buffers: buffers,
+        base_dir: base_dir,
+    };
+
+    let materials = try!(document.load_materials());
+    let images = try!(document.load_images(facade, &materials));
+    let meshes = try!(document.load_meshes(facade));
+    let draw_list = try!(document.load_draw_list(meshes.len()));
+
+    Ok(Scene {
+        meshes: meshes,
+        materials: materials,
+        images: images,
+        draw_list: draw_list,
+    })
+}
+
+/// Splits a binary glTF file into its JSON chunk and its binary chunk.
+fn parse_glb(data: &[u8]) -> Result<(&[u8], Option<&[u8]>), GltfError> {
+    if data.len() < 12 {
+        return Err(invalid("the binary header is truncated"));
+    }
+
+    let version = read_u32(data, 4);
+    if version != 2 {
+        return Err(GltfError::UnsupportedFeature(format!("binary glTF version {}", version)));
+    }
+
+    let length = ::std::cmp::min(read_u32(data, 8) as usize, data.len());
+
+    let mut json = None;
+    let mut binary = None;
+    let mut offset = 12;
+
+    while offset + 8 <= length {
+        let chunk_length = read_u32(data, offset) as usize;
+        let chunk_type = read_u32(data, offset + 4);
+        let start = offset + 8;
+        let end = start + chunk_length;
+
+        if end > length {
+            return Err(invalid("a chunk of the binary file is truncated"));
+        }
+
+        match chunk_type {
+            0x4E4F534A if json.is_none() => json = Some(&data[start .. end]),
+            0x004E4942 if binary.is_none() => binary = Some(&data[start .. end]),
+            _ => ()     // unknown chunks must be ignored
+        }
+
+        offset = end;
+    }
+
+    match json {
+        Some(json) => Ok((json, binary)),
+        None => Err(invalid("the binary file doesn't have a JSON chunk")),
+    }
+}
+
+/// Loads the content of a URI, which is either a data URI or a relative path.
+fn load_uri(uri: &str, base_dir: Option<&Path>) -> Result<Vec<u8>, GltfError> {
+    if uri.starts_with("data:") {
+        let data = match uri.find(";base64,") {
+            Some(pos) => &uri[pos + 8 ..],
+            None => return Err(GltfError::UnsupportedFeature("data URIs that are not encoded \
+                                                              in base64".to_string())),
+        };
+
+        return match data.from_base64() {
+            Ok(data) => Ok(data),
+            Err(_) => Err(invalid("invalid base64 data URI")),
+        };
+    }
+
+    let base_dir = match base_dir {
+        Some(b) => b,
+        None => return Err(invalid(&format!("the external file `{}` can't be loaded without \
+                                             a base directory", uri))),
+    };
+
+    let mut data = Vec::new();
+    try!(try!(File::open(base_dir.join(uri))).read_to_end(&mut data));
+    Ok(data)
+}
+
+/// Data of an accessor, with the elements packed one after another.
+struct Accessor {
+    component_type: u32,
+    components: usize,
+    ty: String,
+    normalized: bool,
+    count: usize,
+    data: Vec<u8>,
+}
+
+struct Document<'a> {
+    json: &'a Json,
+    buffers: Vec<Vec<u8>>,
+    base_dir: Option<&'a Path>,
+}
+
+impl<'a> Document<'a> {
+    /// Returns the content of a buffer view and its stride.
+    fn get_buffer_view(&self, index: usize) -> Result<(&[u8], Option<usize>), GltfError> {
+        let view = try!(get_element(self.json, "bufferViews", index));
+
+        let buffer = try!(require_usize(view, "buffer"));
+        let offset = try!(get_usize(view, "byteOffset")).unwrap_or(0);
+        let length = try!(require_usize(view, "byteLength"));
+        let stride = try!(get_usize(view, "byteStride"));
+
+        let buffer = match self.buffers.get(buffer) {
+            Some(b) => b,
+            None => return Err(invalid("a buffer view references a buffer that doesn't exist")),
+        };
+
+        if offset + length > buffer.len() {
+            return Err(invalid("a buffer view is out of the range of its buffer"));
+        }
+
+        Ok((&buffer[offset .. offset + length], stride))
+    }
+
+    fn get_accessor(&self, index: usize) -> Result<Accessor, GltfError> {
+        let accessor = try!(get_element(self.json, "accessors", index));
+
+        if accessor.find("sparse").is_some() {
+            return Err(GltfError::UnsupportedFeature("sparse accessors".to_string()));
+        }
+
+        let component_type = try!(require_usize(accessor, "componentType")) as u32;
+        let count = try!(require_usize(accessor, "count"));
+        let ty = match try!(get_string(accessor, "type")) {
+            Some(ty) => ty.to_string(),
+            None => return Err(invalid("an accessor doesn't have a type")),
+        };
+        let normalized = try!(get_bool(accessor, "normalized")).unwrap_or(false);
+
+        let component_size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            t => return Err(invalid(&format!("unknown component type {}", t))),
+        };
+
+        let components = match &ty[..] {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" | "MAT2" => 4,
+            "MAT3" => 9,
+            "MAT4" => 16,
+            t => return Err(invalid(&format!("unknown accessor type `{}`", t))),
+        };
+
+        let element_size = component_size * components;
+
+        let data = match try!(get_usize(accessor, "bufferView")) {
+            Some(view) => {
+                let (view, stride) = try!(self.get_buffer_view(view));
+                let offset = try!(get_usize(accessor, "byteOffset")).unwrap_or(0);
+                let stride = stride.unwrap_or(element_size);
+
+                if count != 0 && offset + (count - 1) * stride + element_size > view.len() {
+                    return Err(invalid("an accessor is out of the range of its buffer view"));
+                }
+
+                let mut data = Vec::with_capacity(count * element_size);
+                for element in 0 .. count {
+                    let start = offset + element * stride;
+                    data.extend(view[start .. start + element_size].iter().cloned());
+                }
+                data
+            },
+
+            // accessors without a buffer view are filled with zeroes
+            None => vec![0; count * element_size],
+        };
+
+        Ok(Accessor {
+            component_type: component_type,
+            components: components,
+            ty: ty,
+            normalized: normalized,
+            count: count,
+            data: data,
+        })
+    }
+
+    fn load_materials(&self) -> Result<Vec<Material>, GltfError> {
+        let mut materials = Vec::new();
+
+        for material in try!(get_array(self.json, "materials")).into_iter() {
+            let pbr = material.find("pbrMetallicRoughness");
+
+            let base_color_factor = match pbr {
+                Some(pbr) => try!(get_floats(pbr, "baseColorFactor", 4)),
+                None => None
+            }.unwrap_or(vec![1.0, 1.0, 1.0, 1.0]);
+
+            let emissive_factor = try!(get_floats(material, "emissiveFactor", 3))
+                                      .unwrap_or(vec![0.0, 0.0, 0.0]);
+
+            let alpha_mode = match try!(get_string(material, "alphaMode")) {
+                None | Some("OPAQUE") => AlphaMode::Opaque,
+                Some("MASK") => AlphaMode::Mask,
+                Some("BLEND") => AlphaMode::Blend,
+                Some(m) => return Err(invalid(&format!("unknown alpha mode `{}`", m))),
+            };
+
+            materials.push(Material {
+                name: try!(get_string(material, "name")).map(|s| s.to_string()),
+                base_color_factor: [base_color_factor[0], base_color_factor[1],
+                                    base_color_factor[2], base_color_factor[3]],
+                base_color_texture: match pbr {
+                    Some(pbr) => try!(self.get_texture_ref(pbr, "baseColorTexture")),
+                    None => None
+                },
+                metallic_factor: match pbr {
+                    Some(pbr) => try!(get_f32(pbr, "metallicFactor")).unwrap_or(1.0),
+                    None => 1.0
+                },
+                roughness_factor: match pbr {
+                    Some(pbr) => try!(get_f32(pbr, "roughnessFactor")).unwrap_or(1.0),
+                    None => 1.0
+                },
+                metallic_roughness_texture: match pbr {
+                    Some(pbr) => try!(self.get_texture_ref(pbr, "metallicRoughnessTexture")),
+                    None => None
+                },
+                normal_texture: try!(self.get_texture_ref(material, "normalTexture")),
+                occlusion_texture: try!(self.get_texture_ref(material, "occlusionTexture")),
+                emissive_factor: [emissive_factor[0], emissive_factor[1], emissive_factor[2]],
+                emissive_texture: try!(self.get_texture_ref(material, "emissiveTexture")),
+                alpha_mode: alpha_mode,
+                alpha_cutoff: try!(get_f32(material, "alphaCutoff")).unwrap_or(0.5),
+                double_sided: try!(get_bool(material, "doubleSided")).unwrap_or(false),
+            });
+        }
+
+        Ok(materials)
+    }
+
+    /// Reads a texture info object of a material.
+    fn get_texture_ref(&self, json: &Json, key: &str) -> Result<Option<TextureRef>, GltfError> {
+        let info = match json.find(key) {
+            Some(i) => i,
+            None => return Ok(None)
+        };
+
+        let texture = try!(get_element(self.json, "textures", try!(require_usize(info, "index"))));
+
+        let image = match try!(get_usize(texture, "source")) {
+            Some(s) => s,
+            None => return Err(GltfError::UnsupportedFeature("textures without a source image"
+                                                             .to_string())),
+        };
+
+        if image >= try!(get_array(self.json, "images")).len() {
+            return Err(invalid("a texture references an image that doesn't exist"));
+        }
+
+        let sampler = match try!(get_usize(texture, "sampler")) {
+            Some(sampler) => try!(get_sampler(try!(get_element(self.json, "samplers",
+                                                                 sampler)))),
+            None => try!(get_sampler(&Json::Null)),
+        };
+
+        Ok(Some(TextureRef {
+            image: image,
+            sampler: sampler,
+            texcoord: try!(get_usize(info, "texCoord")).unwrap_or(0) as u32,
+        }))
+    }
+
+    fn load_images<F>(&self, facade: &F, materials: &[Material])
+                      -> Result<Vec<Image>, GltfError> where F: Facade
+    {
+        let images = try!(get_array(self.json, "images"));
+
+        // finding out which images must be uploaded as sRGB textures
+        let mut srgb = vec![false; images.len()];
+        let mut linear = vec![false; images.len()];
+        for material in materials.iter() {
+            for texture in material.base_color_texture.iter()
+                                   .chain(material.emissive_texture.iter())
+            {
+                srgb[texture.image] = true;
+            }
+
+            for texture in material.metallic_roughness_texture.iter()
+                                   .chain(material.normal_texture.iter())
+                                   .chain(material.occlusion_texture.iter())
+            {
+                linear[texture.image] = true;
+            }
+        }
+
+        let mut result = Vec::with_capacity(images.len());
+
+        for (num, json) in images.into_iter().enumerate() {
+            let uri = try!(get_string(json, "uri"));
+            let data = match (uri, try!(get_usize(json, "bufferView"))) {
+                (Some(uri), _) => try!(load_uri(uri, self.base_dir)),
+                (None, Some(view)) => try!(self.get_buffer_view(view)).0.to_vec(),
+                (None, None) => return Err(invalid("an image doesn't have any data")),
+            };
+
+            let image = try!(image::load_from_memory(&data));
+            let (width, height) = image.dimensions();
+            let data = image.to_rgba().into_raw();
+
+            // the rows are not flipped, so that the first row is at the texture coordinate 0.0
+            let raw = || RawImage2d {
+                data: Cow::Borrowed(&data[..]),
+                width: width,
+                height: height,
+                format: ClientFormat::U8U8U8U8,
+            };
+
+            result.push(Image {
+                texture: if linear[num] || !srgb[num] {
+                    Some(Texture2d::new(facade, raw()))
+                } else {
+                    None
+                },
+                srgb_texture: if srgb[num] {
+                    Some(SrgbTexture2d::new(facade, raw()))
+                } else {
+                    None
+                },
+            });
+        }
+
+        Ok(result)
+    }
+
+    fn load_meshes<F>(&self, facade: &F) -> Result<Vec<Mesh>, GltfError> where F: Facade {
+        let mut meshes = Vec::new();
+
+        for mesh in try!(get_array(self.json, "meshes")).into_iter() {
+            let mut primitives = Vec::new();
+
+            for primitive in try!(get_array(mesh, "primitives")).into_iter() {
+                primitives.push(try!(self.load_primitive(facade, primitive)));
+            }
+
+            meshes.push(Mesh {
+                name: try!(get_string(mesh, "name")).map(|s| s.to_string()),
+                primitives: primitives,
+            });
+        }
+
+        Ok(meshes)
+    }
+
+    fn load_primitive<F>(&self, facade: &F, primitive: &Json) -> Result<Primitive, GltfError>
+                         where F: Facade
+    {
+        let primitive_type = match try!(get_usize(primitive, "mode")).unwrap_or(4) {
+            0 => PrimitiveType::Points,
+            1 => PrimitiveType::LinesList,
+            2 => return Err(GltfError::UnsupportedFeature("line loops".to_string())),
+            3 => PrimitiveType::LineStrip,
+            4 => PrimitiveType::TrianglesList,
+            5 => PrimitiveType::TriangleStrip,
+            6 => PrimitiveType::TriangleFan,
+            m => return Err(invalid(&format!("unknown primitive mode {}", m))),
+        };
+
+        let attributes = match primitive.find("attributes").and_then(|a| a.as_object()) {
+            Some(a) => a,
+            None => return Err(invalid("a primitive doesn't have any attribute")),
+        };
+
+        // converting each attribute and computing the layout of the vertices
+        let mut converted = Vec::with_capacity(attributes.len());
+        let mut format = Vec::with_capacity(attributes.len());
+        let mut stride = 0;
+        let mut count = None;
+
+        for (name, accessor) in attributes.iter() {
+            let accessor = match accessor.as_u64() {
+                Some(a) => try!(self.get_accessor(a as usize)),
+                None => return Err(invalid("an attribute is not an accessor index")),
+            };
+
+            if count.is_some() && count != Some(accessor.count) {
+                return Err(invalid("the attributes of a primitive have different lengths"));
+            }
+            count = Some(accessor.count);
+
+            let (ty, data, size) = try!(convert_attribute(&accessor));
+            format.push((Cow::Owned(name.clone()), stride, ty));
+            converted.push((data, size, stride));
+            stride += size;
+        }
+
+        let count = count.unwrap_or(0);
+        if stride == 0 {
+            return Err(invalid("a primitive doesn't have any attribute"));
+        }
+
+        // interleaving the attributes
+        let mut vertices = vec![0u8; count * stride];
+        for &(ref data, size, offset) in converted.iter() {
+            for vertex in 0 .. count {
+                let dest = vertex * stride + offset;
+                for (d, s) in vertices[dest .. dest + size].iter_mut()
+                                                           .zip(data[vertex * size ..].iter())
+                {
+                    *d = *s;
+                }
+            }
+        }
+
+        let vertex_buffer = unsafe {
+            VertexBufferAny::new_raw(facade, &vertices, Cow::Owned(format), stride)
+        };
+
+        let index_buffer = match try!(get_usize(primitive, "indices")) {
+            Some(indices) => {
+                let accessor = try!(self.get_accessor(indices));
+                if accessor.components != 1 {
+                    return Err(invalid("the indices accessor must contain scalars"));
+                }
+
+                let data = &accessor.data;
+                Some(match accessor.component_type {
+                    5121 => IndexBuffer::new(facade, primitive_type, data.clone()).into(),
+                    5123 => {
+                        let data = data.chunks(2).map(|c| c[0] as u16 | (c[1] as u16) << 8)
+                                       .collect::<Vec<_>>();
+                        IndexBuffer::new(facade, primitive_type, data).into()
+                    },
+                    5125 => {
+                        let data = data.chunks(4).map(|c| read_u32(c, 0)).collect::<Vec<_>>();
+                        IndexBuffer::new(facade, primitive_type, data).into()
+                    },
+                    _ => return Err(invalid("invalid component type for indices")),
+                })
+            },
+            None => None
+        };
+
+        let material = try!(get_usize(primitive, "material"));
+        if let Some(material) = material {
+            if material >= try!(get_array(self.json, "materials")).len() {
+                return Err(invalid("a primitive references a material that doesn't exist"));
+            }
+        }
+
+        Ok(Primitive {
+            vertex_buffer: vertex_buffer,
+            index_buffer: index_buffer,
+            primitive_type: primitive_type,
+            material: material,
+        })
+    }
+
+    /// Visits the nodes of the default scene and returns the list of meshes to draw.
+    fn load_draw_list(&self, meshes_count: usize) -> Result<Vec<DrawItem>, GltfError> {
+        let scenes = try!(get_array(self.json, "scenes"));
+        if scenes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let scene = try!(get_usize(self.json, "scene")).unwrap_or(0);
+        let scene = match scenes.get(scene) {
+            Some(s) => *s,
+            None => return Err(invalid("the default scene doesn't exist")),
+        };
+
+        let mut draw_list = Vec::new();
+        for node in try!(get_array(scene, "nodes")).into_iter() {
+            let node = match node.as_u64() {
+                Some(n) => n as usize,
+                None => return Err(invalid("a scene contains an invalid node index")),
+            };
+
+            try!(self.visit_node(node, IDENTITY, meshes_count, 0, &mut draw_list));
+        }
+
+        Ok(draw_list)
+    }
+
+    fn visit_node(&self, index: usize, parent: [[f32; 4]; 4], meshes_count: usize,
+                  depth: usize, draw_list: &mut Vec<DrawItem>) -> Result<(), GltfError>
+    {
+        let nodes_count = try!(get_array(self.json, "nodes")).len();
+        if depth > nodes_count {
+            return Err(invalid("the hierarchy of nodes contains a cycle"));
+        }
+
+        let node = try!(get_element(self.json, "nodes", index));
+        let transform = multiply(&parent, &try!(get_node_transform(node)));
+
+        if let Some(mesh) = try!(get_usize(node, "mesh")) {
+            if mesh >= meshes_count {
+                return Err(invalid("a node references a mesh that doesn't exist"));
+            }
+
+            draw_list.push(DrawItem {
+                mesh: mesh,
+                node_name: try!(get_string(node, "name")).map(|s| s.to_string()),
+                transform: transform,
+            });
+        }
+
+        for child in try!(get_array(node, "children")).into_iter() {
+            let child = match child.as_u64() {
+                Some(c) => c as usize,
+                None => return Err(invalid("a node contains an invalid child index")),
+            };
+
+            try!(self.visit_node(child, transform, meshes_count, depth + 1, draw_list));
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts the data of an accessor into a vertex attribute.
+///
+/// Returns the type of the attribute, the converted data and the size of each element, which
+/// is always a multiple of four bytes.
+fn convert_attribute(accessor: &Accessor) -> Result<(AttributeType, Vec<u8>, usize), GltfError> {
+    // the columns of integer matrices are padded, which isn't handled
+    if accessor.ty.starts_with("MAT") && accessor.component_type != 5126 {
+        return Err(GltfError::UnsupportedFeature("integer matrices".to_string()));
+    }
+
+    // floating-point and normalized values are turned into floats
+    if accessor.component_type == 5126 || accessor.normalized {
+        let ty = match (&accessor.ty[..], accessor.components) {
+            ("MAT2", _) => AttributeType::F32x2x2,
+            ("MAT3", _) => AttributeType::F32x3x3,
+            ("MAT4", _) => AttributeType::F32x4x4,
+            (_, 1) => AttributeType::F32,
+            (_, 2) => AttributeType::F32F32,
+            (_, 3) => AttributeType::F32F32F32,
+            (_, 4) => AttributeType::F32F32F32F32,
+            _ => unreachable!()
+        };
+
+        let data = if accessor.component_type == 5126 {
+            accessor.data.clone()
+        } else {
+            let size = match accessor.component_type { 5120 | 5121 => 1, 5122 | 5123 => 2, _ => 4 };
+            let mut data = Vec::with_capacity(accessor.data.len() / size * 4);
+
+            for value in accessor.data.chunks(size) {
+                let value = match accessor.component_type {
+                    5120 => (value[0] as i8 as f32 / 127.0).max(-1.0),
+                    5121 => value[0] as f32 / 255.0,
+                    5122 => ((value[0] as u16 | (value[1] as u16) << 8) as i16 as f32 / 32767.0)
+                                .max(-1.0),
+                    5123 => (value[0] as u16 | (value[1] as u16) << 8) as f32 / 65535.0,
+                    _ => read_u32(value, 0) as f32 / 4294967295.0,
+                };
+
+                let bits: u32 = unsafe { mem::transmute(value) };
+                data.extend([bits as u8, (bits >> 8) as u8, (bits >> 16) as u8,
+                             (bits >> 24) as u8].iter().cloned());
+            }
+
+            data
+        };
+
+        let size = accessor.components * 4;
+        return Ok((ty, data, size));
+    }
+
+    let ty = match (accessor.component_type, accessor.components) {
+        (5120, 1) => AttributeType::I8,
+        (5120, 2) => AttributeType::I8I8,
+        (5120, 3) => AttributeType::I8I8I8,
+        (5120, 4) => AttributeType::I8I8I8I8,
+        (5121, 1) => AttributeType::U8,
+        (5121, 2) => AttributeType::U8U8,
+        (5121, 3) => AttributeType::U8U8U8,
+        (5121, 4) => AttributeType::U8U8U8U8,
+        (5122, 1) => AttributeType::I16,
+        (5122, 2) => AttributeType::I16I16,
+        (5122, 3) => AttributeType::I16I16I16,
+        (5122, 4) => AttributeType::I16I16I16I16,
+        (5123, 1) => AttributeType::U16,
+        (5123, 2) => AttributeType::U16U16,
+        (5123, 3) => AttributeType::U16U16U16,
+        (5123, 4) => AttributeType::U16U16U16U16,
+        (5125, 1) => AttributeType::U32,
+        (5125, 2) => AttributeType::U32U32,
+        (5125, 3) => AttributeType::U32U32U32,
+        (5125, 4) => AttributeType::U32U32U32U32,
+        _ => unreachable!()
+    };
+
+    // each element is padded to a multiple of four bytes
+    let element_size = accessor.data.len() / ::std::cmp::max(accessor.count, 1);
+    let size = (element_size + 3) / 4 * 4;
+
+    let mut data = Vec::with_capacity(accessor.count * size);
+    for element in accessor.data.chunks(::std::cmp::max(element_size, 1)) {
+        data.extend(element.iter().cloned());
+        data.extend((element_size .. size).map(|_| 0));
+    }
+
+    Ok((ty, data, size))
+}
+
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// Multiplies two column-major matrices.
+fn multiply(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut result = [[0.0; 4]; 4];
+
+    for col in 0 .. 4 {
+        for row in 0 .. 4 {
+            for k in 0 .. 4 {
+                result[col][row] += a[k][row] * b[col][k];
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the local transformation of a node, from either its `matrix` or its translation,
+/// rotation and scale.
+fn get_node_transform(node: &Json) -> Result<[[f32; 4]; 4], GltfError> {
+    if let Some(m) = try!(get_floats(node, "matrix", 16)) {
+        return Ok([
+            [m[0], m[1], m[2], m[3]],
+            [m[4], m[5], m[6], m[7]],
+            [m[8], m[9], m[10], m[11]],
+            [m[12], m[13], m[14], m[15]],
+        ]);
+    }
+
+    let t = try!(get_floats(node, "translation", 3)).unwrap_or(vec![0.0, 0.0, 0.0]);
+    let r = try!(get_floats(node, "rotation", 4)).unwrap_or(vec![0.0, 0.0, 0.0, 1.0]);
+    let s = try!(get_floats(node, "scale", 3)).unwrap_or(vec![1.0, 1.0, 1.0]);
+
+    let (x, y, z, w) = (r[0], r[1], r[2], r[3]);
+
+    Ok([
+        [(1.0 - 2.0 * (y * y + z * z)) * s[0], 2.0 * (x * y + z * w) * s[0],
+         2.0 * (x * z - y * w) * s[0], 0.0],
+        [2.0 * (x * y - z * w) * s[1], (1.0 - 2.0 * (x * x + z * z)) * s[1],
+         2.0 * (y * z + x * w) * s[1], 0.0],
+        [2.0 * (x * z + y * w) * s[2], 2.0 * (y * z - x * w) * s[2],
+         (1.0 - 2.0 * (x * x + y * y)) * s[2], 0.0],
+        [t[0], t[1], t[2], 1.0],
+    ])
+}
+
+/// Builds a `SamplerBehavior` from a glTF sampler.
+fn get_sampler(sampler: &Json) -> Result<SamplerBehavior, GltfError> {
+    fn wrap(value: Option<usize>) -> Result<SamplerWrapFunction, GltfError> {
+        match value {
+            None | Some(10497) => Ok(SamplerWrapFunction::Repeat),
+            Some(33071) => Ok(SamplerWrapFunction::Clamp),
+            Some(33648) => Ok(SamplerWrapFunction::Mirror),
+            Some(v) => Err(invalid(&format!("unknown wrap mode {}", v))),
+        }
+    }
+
+    let magnify_filter = match try!(get_usize(sampler, "magFilter")) {
+        Some(9728) => MagnifySamplerFilter::Nearest,
+        None | Some(9729) => MagnifySamplerFilter::Linear,
+        Some(v) => return Err(invalid(&format!("unknown magnification filter {}", v))),
+    };
+
+    let minify_filter = match try!(get_usize(sampler, "minFilter")) {
+        Some(9728) => MinifySamplerFilter::Nearest,
+        Some(9729) => MinifySamplerFilter::Linear,
+        Some(9984) => MinifySamplerFilter::NearestMipmapNearest,
+        Some(9985) => MinifySamplerFilter::LinearMipmapNearest,
+        Some(9986) => MinifySamplerFilter::NearestMipmapLinear,
+        None | Some(9987) => MinifySamplerFilter::LinearMipmapLinear,
+        Some(v) => return Err(invalid(&format!("unknown minification filter {}", v))),
+    };
+
+    Ok(SamplerBehavior {
+        wrap_function: (try!(wrap(try!(get_usize(sampler, "wrapS")))),
+                        try!(wrap(try!(get_usize(sampler, "wrapT")))),
+                        SamplerWrapFunction::Repeat),
+        minify_filter: minify_filter,
+        magnify_filter: magnify_filter,
+        .. Default::default()
+    })
+}
+
+fn invalid(message: &str) -> GltfError {
+    GltfError::InvalidGltf(message.to_string())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    data[offset] as u32 | (data[offset + 1] as u32) << 8 | (data[offset + 2] as u32) << 16 |
+    (data[offset + 3] as u32) << 24
+}
+
+/// Returns the elements of an array property, or an empty list if the property is missing.
+fn get_array<'a>(json: &'a Json, key: &str) -> Result<Vec<&'a Json>, GltfError> {
+    match json.find(key) {
+        None => Ok(Vec::new()),
+        Some(&Json::Array(ref array)) => Ok(array.iter().collect()),
+        Some(_) => Err(invalid(&format!("`{}` is not an array", key))),
+    }
+}
+
+/// Returns the element of a top-level array of the document.
+fn get_element<'a>(json: &'a Json, key: &str, index: usize) -> Result<&'a Json, GltfError> {
+    match try!(get_array(json, key)).get(index) {
+        Some(e) => Ok(*e),
+        None => Err(invalid(&format!("`{}` doesn't have an element {}", key, index))),
+    }
+}
+
+fn get_object<'a>(json: &'a Json, key: &str) -> Result<&'a Json, GltfError> {
+    match json.find(key) {
+        Some(o) if o.is_object() => Ok(o),
+        _ => Err(invalid(&format!("missing `{}` object", key))),
+    }
+}
+
+fn get_usize(json: &Json, key: &str) -> Result<Option<usize>, GltfError> {
+    match json.find(key) {
+        None => Ok(None),
+        Some(v) => match v.as_u64() {
+            Some(v) => Ok(Some(v as usize)),
+            None => Err(invalid(&format!("`{}` is not a positive integer", key))),
+        },
+    }
+}
+
+fn require_usize(json: &Json, key: &str) -> Result<usize, GltfError> {
+    match try!(get_usize(json, key)) {
+        Some(v) => Ok(v),
+        None => Err(invalid(&format!("missing `{}`", key))),
+    }
+}
+
+fn get_f32(json: &Json, key: &str) -> Result<Option<f32>, GltfError> {
+    match json.find(key) {
+        None => Ok(None),
+        Some(v) => match v.as_f64() {
+            Some(v) => Ok(Some(v as f32)),
+            None => Err(invalid(&format!("`{}` is not a number", key))),
+        },
+    }
+}
+
+fn get_floats(json: &Json, key: &str, len: usize) -> Result<Option<Vec<f32>>, GltfError> {
+    if json.find(key).is_none() {
+        return Ok(None);
+    }
+
+    let values = try!(get_array(json, key)).into_iter().filter_map(|v| v.as_f64())
+                                           .map(|v| v as f32).collect::<Vec<_>>();
+
+    if values.len() != len {
+        return Err(invalid(&format!("`{}` must contain {} numbers", key, len)));
+    }
+
+    Ok(Some(values))
+}
+
+fn get_string<'a>(json: &'a Json, key: &str) -> Result<Option<&'a str>, GltfError> {
+    match json.find(key) {
+        None => Ok(None),
+        Some(v) => match v.as_string() {
+            Some(v) => Ok(Some(v)),
+            None => Err(invalid(&format!("`{}` is not a string", key))),
+        },
+    }
+}
+
+fn get_bool(json: &Json, key: &str) -> Result<Option<bool>, GltfError> {
+    match json.find(key) {
+        None => Ok(None),
+        Some(v) => match v.as_boolean() {
+            Some(v) => Ok(Some(v)),
+            None => Err(invalid(&format!("`{}` is not a boolean", key))),
+        },
+    }
+}
@@ -420,6 +420,50 @@ pub enum CompressedFormat {
     S3tcDxt3Alpha,
     /// S3TC DXT5, see https://www.opengl.org/wiki/S3_Texture_Compression.
     S3tcDxt5Alpha,
+
+    /// ETC2 format with three components (no alpha).
+    Etc2Rgb8,
+    /// ETC2 format with three components and a 1-bit alpha (also called "punchthrough alpha").
+    Etc2Rgb8A1,
+    /// ETC2 format with four components, the alpha being compressed with EAC.
+    Etc2Rgba8,
+    /// EAC format with one unsigned component.
+    EacR11Unsigned,
+    /// EAC format with one signed component.
+    EacR11Signed,
+    /// EAC format with two unsigned components.
+    EacRg11Unsigned,
+    /// EAC format with two signed components.
+    EacRg11Signed,
+
+    /// ASTC LDR format with four components and blocks of 4x4 pixels.
+    Astc4x4,
+    /// ASTC LDR format with four components and blocks of 5x4 pixels.
+    Astc5x4,
+    /// ASTC LDR format with four components and blocks of 5x5 pixels.
+    Astc5x5,
+    /// ASTC LDR format with four components and blocks of 6x5 pixels.
+    Astc6x5,
+    /// ASTC LDR format with four components and blocks of 6x6 pixels.
+    Astc6x6,
+    /// ASTC LDR format with four components and blocks of 8x5 pixels.
+    Astc8x5,
+    /// ASTC LDR format with four components and blocks of 8x6 pixels.
+    Astc8x6,
+    /// ASTC LDR format with four components and blocks of 8x8 pixels.
+    Astc8x8,
+    /// ASTC LDR format with four components and blocks of 10x5 pixels.
+    Astc10x5,
+    /// ASTC LDR format with four components and blocks of 10x6 pixels.
+    Astc10x6,
+    /// ASTC LDR format with four components and blocks of 10x8 pixels.
+    Astc10x8,
+    /// ASTC LDR format with four components and blocks of 10x10 pixels.
+    Astc10x10,
+    /// ASTC LDR format with four components and blocks of 12x10 pixels.
+    Astc12x10,
+    /// ASTC LDR format with four components and blocks of 12x12 pixels.
+    Astc12x12,
 }
 
 impl CompressedFormat {
@@ -439,6 +483,26 @@ pub enum CompressedSrgbFormat {
     S3tcDxt1Alpha,
     S3tcDxt3Alpha,
     S3tcDxt5Alpha,
+    /// ETC2 format with three components (no alpha).
+    Etc2Rgb8,
+    /// ETC2 format with three components and a 1-bit alpha.
+    Etc2Rgb8A1,
+    /// ETC2 format with four components, the alpha being compressed with EAC.
+    Etc2Rgba8,
+    Astc4x4,
+    Astc5x4,
+    Astc5x5,
+    Astc6x5,
+    Astc6x6,
+    Astc8x5,
+    Astc8x6,
+    Astc8x8,
+    Astc10x5,
+    Astc10x6,
+    Astc10x8,
+    Astc10x10,
+    Astc12x10,
+    Astc12x12,
 }
 
 impl CompressedSrgbFormat {
@@ -522,6 +586,252 @@ pub enum TextureFormat {
     DepthStencilFormat(DepthStencilFormat),
 }
 
+/// List of the ASTC formats, with the width and height of their blocks and their `GLenum`s.
+pub static ASTC_FORMATS: [(CompressedFormat, CompressedSrgbFormat, u32, u32,
+                           gl::types::GLenum, gl::types::GLenum); 14] = [
+    (CompressedFormat::Astc4x4, CompressedSrgbFormat::Astc4x4, 4, 4,
+     gl::COMPRESSED_RGBA_ASTC_4x4_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR),
+    (CompressedFormat::Astc5x4, CompressedSrgbFormat::Astc5x4, 5, 4,
+     gl::COMPRESSED_RGBA_ASTC_5x4_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4_KHR),
+    (CompressedFormat::Astc5x5, CompressedSrgbFormat::Astc5x5, 5, 5,
+     gl::COMPRESSED_RGBA_ASTC_5x5_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5_KHR),
+    (CompressedFormat::Astc6x5, CompressedSrgbFormat::Astc6x5, 6, 5,
+     gl::COMPRESSED_RGBA_ASTC_6x5_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5_KHR),
+    (CompressedFormat::Astc6x6, CompressedSrgbFormat::Astc6x6, 6, 6,
+     gl::COMPRESSED_RGBA_ASTC_6x6_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6_KHR),
+    (CompressedFormat::Astc8x5, CompressedSrgbFormat::Astc8x5, 8, 5,
+     gl::COMPRESSED_RGBA_ASTC_8x5_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5_KHR),
+    (CompressedFormat::Astc8x6, CompressedSrgbFormat::Astc8x6, 8, 6,
+     gl::COMPRESSED_RGBA_ASTC_8x6_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6_KHR),
+    (CompressedFormat::Astc8x8, CompressedSrgbFormat::Astc8x8, 8, 8,
+     gl::COMPRESSED_RGBA_ASTC_8x8_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8_KHR),
+    (CompressedFormat::Astc10x5, CompressedSrgbFormat::Astc10x5, 10, 5,
+     gl::COMPRESSED_RGBA_ASTC_10x5_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5_KHR),
+    (CompressedFormat::Astc10x6, CompressedSrgbFormat::Astc10x6, 10, 6,
+     gl::COMPRESSED_RGBA_ASTC_10x6_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6_KHR),
+    (CompressedFormat::Astc10x8, CompressedSrgbFormat::Astc10x8, 10, 8,
+     gl::COMPRESSED_RGBA_ASTC_10x8_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8_KHR),
+    (CompressedFormat::Astc10x10, CompressedSrgbFormat::Astc10x10, 10, 10,
+     gl::COMPRESSED_RGBA_ASTC_10x10_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10_KHR),
+    (CompressedFormat::Astc12x10, CompressedSrgbFormat::Astc12x10, 12, 10,
+     gl::COMPRESSED_RGBA_ASTC_12x10_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10_KHR),
+    (CompressedFormat::Astc12x12, CompressedSrgbFormat::Astc12x12, 12, 12,
+     gl::COMPRESSED_RGBA_ASTC_12x12_KHR, gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12_KHR),
+];
+
+/// List of the ETC2 and EAC formats, with their `GLenum`s.
+static ETC2_FORMATS: [(TextureFormat, gl::types::GLenum); 10] = [
+    (TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8),
+     gl::COMPRESSED_RGB8_ETC2),
+    (TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8A1),
+     gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2),
+    (TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgba8),
+     gl::COMPRESSED_RGBA8_ETC2_EAC),
+    (TextureFormat::CompressedFormat(CompressedFormat::EacR11Unsigned),
+     gl::COMPRESSED_R11_EAC),
+    (TextureFormat::CompressedFormat(CompressedFormat::EacR11Signed),
+     gl::COMPRESSED_SIGNED_R11_EAC),
+    (TextureFormat::CompressedFormat(CompressedFormat::EacRg11Unsigned),
+     gl::COMPRESSED_RG11_EAC),
+    (TextureFormat::CompressedFormat(CompressedFormat::EacRg11Signed),
+     gl::COMPRESSED_SIGNED_RG11_EAC),
+    (TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8),
+     gl::COMPRESSED_SRGB8_ETC2),
+    (TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1),
+     gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2),
+    (TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgba8),
+     gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC),
+];
+
+/// Returns the `GLenum` of an ETC2 or EAC format, or `None` if the format is not one of them.
+fn get_etc2_glenum(format: TextureFormat) -> Option<gl::types::GLenum> {
+    ETC2_FORMATS.iter().find(|&&(f, _)| f == format).map(|&(_, glenum)| glenum)
+}
+
+/// Returns the ASTC block width and height and the `GLenum` of a format, or `None` if the format
+/// is not an ASTC format.
+fn get_astc_format(format: TextureFormat) -> Option<(u32, u32, gl::types::GLenum)> {
+    for &(linear, srgb, width, height, linear_enum, srgb_enum) in ASTC_FORMATS.iter() {
+        if format == TextureFormat::CompressedFormat(linear) {
+            return Some((width, height, linear_enum));
+        }
+
+        if format == TextureFormat::CompressedSrgbFormat(srgb) {
+            return Some((width, height, srgb_enum));
+        }
+    }
+
+    None
+}
+
+/// Returns the compressed format corresponding to an internal format `GLenum`, or `None` if the
+/// `GLenum` is not a compressed format known by glium.
+pub fn compressed_glenum_to_format(format: gl::types::GLenum) -> Option<TextureFormat> {
+    Some(match format {
+        gl::COMPRESSED_RGB_S3TC_DXT1_EXT => {
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha)
+        },
+        gl::COMPRESSED_RGBA_S3TC_DXT1_EXT => {
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha)
+        },
+        gl::COMPRESSED_RGBA_S3TC_DXT3_EXT => {
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha)
+        },
+        gl::COMPRESSED_RGBA_S3TC_DXT5_EXT => {
+            TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha)
+        },
+        gl::COMPRESSED_SRGB_S3TC_DXT1_EXT => {
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha)
+        },
+        gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => {
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha)
+        },
+        gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT => {
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha)
+        },
+        gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => {
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha)
+        },
+        gl::COMPRESSED_RED_RGTC1 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU),
+        gl::COMPRESSED_SIGNED_RED_RGTC1 => {
+            TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatI)
+        },
+        gl::COMPRESSED_RG_RGTC2 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatUU),
+        gl::COMPRESSED_SIGNED_RG_RGTC2 => {
+            TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatII)
+        },
+        gl::COMPRESSED_RGBA_BPTC_UNORM => {
+            TextureFormat::CompressedFormat(CompressedFormat::BptcUnorm4)
+        },
+        gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT => {
+            TextureFormat::CompressedFormat(CompressedFormat::BptcSignedFloat3)
+        },
+        gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => {
+            TextureFormat::CompressedFormat(CompressedFormat::BptcUnsignedFloat3)
+        },
+        gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => {
+            TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc)
+        },
+        format => {
+            for &(etc2, glenum) in ETC2_FORMATS.iter() {
+                if format == glenum {
+                    return Some(etc2);
+                }
+            }
+
+            for &(linear, srgb, _, _, linear_enum, srgb_enum) in ASTC_FORMATS.iter() {
+                if format == linear_enum {
+                    return Some(TextureFormat::CompressedFormat(linear));
+                }
+
+                if format == srgb_enum {
+                    return Some(TextureFormat::CompressedSrgbFormat(srgb));
+                }
+            }
+
+            return None;
+        },
+    })
+}
+
+/// Returns the width and height in pixels of a block of a compressed format, and the number of
+/// bytes of each block.
+///
+/// Returns `None` if the format is not compressed.
+pub fn get_compressed_block_size(format: TextureFormat) -> Option<(u32, u32, usize)> {
+    match format {
+        TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU) |
+        TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatI) |
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha) |
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha) |
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha) |
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha) |
+        TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8) |
+        TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8A1) |
+        TextureFormat::CompressedFormat(CompressedFormat::EacR11Unsigned) |
+        TextureFormat::CompressedFormat(CompressedFormat::EacR11Signed) |
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8) |
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1) => {
+            Some((4, 4, 8))
+        },
+
+        TextureFormat::CompressedFormat(_) | TextureFormat::CompressedSrgbFormat(_) => {
+            match get_astc_format(format) {
+                Some((width, height, _)) => Some((width, height, 16)),
+                None => Some((4, 4, 16)),
+            }
+        },
+
+        _ => None
+    }
+}
+
+/// Compatibility class of an internal format, as defined by the `glTextureView` specification.
+///
+/// Two different internal formats can be used for the same storage only if they belong to
+/// the same class.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ViewClass {
+    Bits128,
+    Bits96,
+    Bits64,
+    Bits48,
+    Bits32,
+    Bits24,
+    Bits16,
+    Bits8,
+    Rgtc1Red,
+    Rgtc2Rg,
+    BptcUnorm,
+    BptcFloat,
+}
+
+/// Returns the view class of a sized internal format.
+///
+/// Returns `None` if the format doesn't belong to any class, in which case it can only be
+/// viewed with the exact same format.
+pub fn get_view_class(format: gl::types::GLenum) -> Option<ViewClass> {
+    match format {
+        gl::RGBA32F | gl::RGBA32UI | gl::RGBA32I => Some(ViewClass::Bits128),
+
+        gl::RGB32F | gl::RGB32UI | gl::RGB32I => Some(ViewClass::Bits96),
+
+        gl::RGBA16F | gl::RG32F | gl::RGBA16UI | gl::RG32UI | gl::RGBA16I | gl::RG32I |
+        gl::RGBA16 | gl::RGBA16_SNORM => Some(ViewClass::Bits64),
+
+        gl::RGB16 | gl::RGB16_SNORM | gl::RGB16F | gl::RGB16UI | gl::RGB16I => {
+            Some(ViewClass::Bits48)
+        },
+
+        gl::RG16F | gl::R11F_G11F_B10F | gl::R32F | gl::RGB10_A2UI | gl::RGBA8UI | gl::RG16UI |
+        gl::R32UI | gl::RGBA8I | gl::RG16I | gl::R32I | gl::RGB10_A2 | gl::RGBA8 | gl::RG16 |
+        gl::RGBA8_SNORM | gl::RG16_SNORM | gl::SRGB8_ALPHA8 | gl::RGB9_E5 => {
+            Some(ViewClass::Bits32)
+        },
+
+        gl::RGB8 | gl::RGB8_SNORM | gl::SRGB8 | gl::RGB8UI | gl::RGB8I => Some(ViewClass::Bits24),
+
+        gl::R16F | gl::RG8UI | gl::R16UI | gl::RG8I | gl::R16I | gl::RG8 | gl::R16 |
+        gl::RG8_SNORM | gl::R16_SNORM => Some(ViewClass::Bits16),
+
+        gl::R8UI | gl::R8I | gl::R8 | gl::R8_SNORM => Some(ViewClass::Bits8),
+
+        gl::COMPRESSED_RED_RGTC1 | gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(ViewClass::Rgtc1Red),
+
+        gl::COMPRESSED_RG_RGTC2 | gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(ViewClass::Rgtc2Rg),
+
+        gl::COMPRESSED_RGBA_BPTC_UNORM | gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => {
+            Some(ViewClass::BptcUnorm)
+        },
+
+        gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT | gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => {
+            Some(ViewClass::BptcFloat)
+        },
+
+        _ => None
+    }
+}
+
 /// Checks that the texture format is supported and compatible with the client format.
 ///
 /// Returns two `GLenum`s. The first one can be unsized and is suitable for the internal format
@@ -575,6 +885,8 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
 
             } else if version >= &Version(Api::GlEs, 2, 0) {
                 match size {
+                    Some(1) => (gl::LUMINANCE, None),
+                    Some(2) => (gl::LUMINANCE_ALPHA, None),
                     Some(3) => {
                         if extensions.gl_oes_rgb8_rgba8 {
                             (gl::RGB, Some(gl::RGB8_OES))
@@ -994,6 +1306,36 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
             }
         },
 
+        // ETC2 and EAC formats are core in OpenGL ES 3.0 and OpenGL 4.3
+        TextureFormatRequest::Specific(f) if get_etc2_glenum(f).is_some() => {
+            if client.is_none() || version >= &Version(Api::Gl, 1, 0) {
+                if version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+                   extensions.gl_arb_es3_compatibility
+                {
+                    let glenum = get_etc2_glenum(f).unwrap();
+                    (glenum, Some(glenum))
+                } else {
+                    return Err(FormatNotSupportedError);
+                }
+            } else {
+                // OpenGL ES can't compress data on the fly
+                return Err(FormatNotSupportedError);
+            }
+        },
+
+        // the remaining formats are ASTC formats, which can only be filled with data that is
+        // already compressed
+        TextureFormatRequest::Specific(f @ TextureFormat::CompressedFormat(_)) => {
+            if client.is_none() && (version >= &Version(Api::GlEs, 3, 2) ||
+                                    extensions.gl_khr_texture_compression_astc_ldr)
+            {
+                let (_, _, glenum) = get_astc_format(f).unwrap();
+                (glenum, Some(glenum))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
         /*******************************************************************/
         /*                             SRGB                                */
         /*******************************************************************/
@@ -1097,6 +1439,19 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
             }
         },
 
+        // the remaining formats are ASTC formats, which can only be filled with data that is
+        // already compressed
+        TextureFormatRequest::Specific(f @ TextureFormat::CompressedSrgbFormat(_)) => {
+            if client.is_none() && (version >= &Version(Api::GlEs, 3, 2) ||
+                                    extensions.gl_khr_texture_compression_astc_ldr)
+            {
+                let (_, _, glenum) = get_astc_format(f).unwrap();
+                (glenum, Some(glenum))
+            } else {
+                return Err(FormatNotSupportedError);
+            }
+        },
+
         /*******************************************************************/
         /*                          INTEGRAL                               */
         /*******************************************************************/
@@ -1506,9 +1861,15 @@ pub fn format_request_to_glenum(context: &Context, client: Option<ClientFormat>,
 /// Checks that the client texture format is supported.
 ///
 /// Returns two GLenums suitable for `glTexImage#D` and `glTexSubImage#D`.
-pub fn client_format_to_glenum(_context: &Context, client: ClientFormat, format: TextureFormatRequest)
+pub fn client_format_to_glenum(context: &Context, client: ClientFormat, format: TextureFormatRequest)
                                -> (gl::types::GLenum, gl::types::GLenum)
 {
+    // OpenGL ES 2 has no `RED` and `RG` formats
+    let luminance = {
+        let version = context.get_version();
+        !(version >= &Version(Api::Gl, 1, 0)) && !(version >= &Version(Api::GlEs, 3, 0))
+    };
+
     match format {
         TextureFormatRequest::AnyFloatingPoint | TextureFormatRequest::AnyCompressed |
         TextureFormatRequest::AnySrgb | TextureFormatRequest::AnyCompressedSrgb |
@@ -1518,6 +1879,8 @@ pub fn client_format_to_glenum(_context: &Context, client: ClientFormat, format:
         TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)) =>
         {
             match client {
+                ClientFormat::U8 if luminance => (gl::LUMINANCE, gl::UNSIGNED_BYTE),
+                ClientFormat::U8U8 if luminance => (gl::LUMINANCE_ALPHA, gl::UNSIGNED_BYTE),
                 ClientFormat::U8 => (gl::RED, gl::UNSIGNED_BYTE),
                 ClientFormat::U8U8 => (gl::RG, gl::UNSIGNED_BYTE),
                 ClientFormat::U8U8U8 => (gl::RGB, gl::UNSIGNED_BYTE),
@@ -1636,3 +1999,130 @@ pub fn client_format_to_glenum(_context: &Context, client: ClientFormat, format:
         },
     }
 }
+
+/// Returns true if the backend can upload data of the given client format to a texture of the
+/// given format.
+///
+/// Only floating-point, sRGB and compressed formats are checked. If this function returns
+/// false, the data must be converted to another client format on the CPU.
+pub fn is_client_format_supported(context: &Context, client: ClientFormat,
+                                  format: TextureFormatRequest) -> bool
+{
+    match format {
+        TextureFormatRequest::AnyFloatingPoint | TextureFormatRequest::AnyCompressed |
+        TextureFormatRequest::AnySrgb | TextureFormatRequest::AnyCompressedSrgb |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) |
+        TextureFormatRequest::Specific(TextureFormat::Srgb(_)) |
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
+        TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)) => (),
+        _ => return true,
+    };
+
+    let version = context.get_version();
+
+    if version >= &Version(Api::Gl, 1, 0) {
+        true
+
+    } else if version >= &Version(Api::GlEs, 3, 0) {
+        // OpenGL ES 3 requires the type of the data to match the internal format
+        match (client, get_es3_upload_type(format)) {
+            (ClientFormat::U8, UploadType::UnsignedNormalized) |
+            (ClientFormat::U8U8, UploadType::UnsignedNormalized) |
+            (ClientFormat::U8U8U8, UploadType::UnsignedNormalized) |
+            (ClientFormat::U8U8U8U8, UploadType::UnsignedNormalized) |
+            (ClientFormat::U5U6U5, UploadType::UnsignedNormalized) |
+            (ClientFormat::U4U4U4U4, UploadType::UnsignedNormalized) |
+            (ClientFormat::U5U5U5U1, UploadType::UnsignedNormalized) => true,
+
+            (ClientFormat::I8, UploadType::SignedNormalized) |
+            (ClientFormat::I8I8, UploadType::SignedNormalized) |
+            (ClientFormat::I8I8I8, UploadType::SignedNormalized) |
+            (ClientFormat::I8I8I8I8, UploadType::SignedNormalized) => true,
+
+            (ClientFormat::F16, UploadType::Float) | (ClientFormat::F16F16, UploadType::Float) |
+            (ClientFormat::F16F16F16, UploadType::Float) |
+            (ClientFormat::F16F16F16F16, UploadType::Float) |
+            (ClientFormat::F32, UploadType::Float) | (ClientFormat::F32F32, UploadType::Float) |
+            (ClientFormat::F32F32F32, UploadType::Float) |
+            (ClientFormat::F32F32F32F32, UploadType::Float) => true,
+
+            _ => false,
+        }
+
+    } else {
+        match client {
+            ClientFormat::U8 | ClientFormat::U8U8 | ClientFormat::U8U8U8 |
+            ClientFormat::U8U8U8U8 | ClientFormat::U5U6U5 | ClientFormat::U4U4U4U4 |
+            ClientFormat::U5U5U5U1 => true,
+            _ => false,
+        }
+    }
+}
+
+/// Returns the client format that data of the given client format must be converted to before
+/// being uploaded to a texture of the given format.
+///
+/// Returns `client` if it is supported. Otherwise the returned format has the same number of
+/// components. On OpenGL ES 3, its type is chosen from the internal format of the texture: floats
+/// for floating-point formats, signed bytes for signed normalized formats and unsigned bytes for
+/// the others. OpenGL ES 2 only supports unsigned bytes, so signed values are clamped to zero.
+pub fn get_nearest_supported_client_format(context: &Context, client: ClientFormat,
+                                           format: TextureFormatRequest) -> ClientFormat
+{
+    if is_client_format_supported(context, client, format) {
+        return client;
+    }
+
+    let ty = if context.get_version() >= &Version(Api::GlEs, 3, 0) {
+        get_es3_upload_type(format)
+    } else {
+        UploadType::UnsignedNormalized
+    };
+
+    match (ty, client.get_num_components()) {
+        (UploadType::UnsignedNormalized, 1) => ClientFormat::U8,
+        (UploadType::UnsignedNormalized, 2) => ClientFormat::U8U8,
+        (UploadType::UnsignedNormalized, 3) => ClientFormat::U8U8U8,
+        (UploadType::UnsignedNormalized, _) => ClientFormat::U8U8U8U8,
+        (UploadType::SignedNormalized, 1) => ClientFormat::I8,
+        (UploadType::SignedNormalized, 2) => ClientFormat::I8I8,
+        (UploadType::SignedNormalized, 3) => ClientFormat::I8I8I8,
+        (UploadType::SignedNormalized, _) => ClientFormat::I8I8I8I8,
+        (UploadType::Float, 1) => ClientFormat::F32,
+        (UploadType::Float, 2) => ClientFormat::F32F32,
+        (UploadType::Float, 3) => ClientFormat::F32F32F32,
+        (UploadType::Float, _) => ClientFormat::F32F32F32F32,
+    }
+}
+
+/// Type of the data that OpenGL ES 3 accepts when uploading to a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UploadType {
+    UnsignedNormalized,
+    SignedNormalized,
+    Float,
+}
+
+/// Returns the type of the data that OpenGL ES 3 accepts when uploading to a texture of the
+/// given format. Requests that are not specific are given an unsigned normalized format.
+fn get_es3_upload_type(format: TextureFormatRequest) -> UploadType {
+    match format {
+        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(format)) => {
+            match format {
+                UncompressedFloatFormat::I8 | UncompressedFloatFormat::I8I8 |
+                UncompressedFloatFormat::I8I8I8 |
+                UncompressedFloatFormat::I8I8I8I8 => UploadType::SignedNormalized,
+
+                UncompressedFloatFormat::F16 | UncompressedFloatFormat::F16F16 |
+                UncompressedFloatFormat::F16F16F16 | UncompressedFloatFormat::F16F16F16F16 |
+                UncompressedFloatFormat::F32 | UncompressedFloatFormat::F32F32 |
+                UncompressedFloatFormat::F32F32F32 | UncompressedFloatFormat::F32F32F32F32 |
+                UncompressedFloatFormat::F11F11F10 |
+                UncompressedFloatFormat::F9F9F9 => UploadType::Float,
+
+                _ => UploadType::UnsignedNormalized,
+            }
+        },
+        _ => UploadType::UnsignedNormalized,
+    }
+}
@@ -31,6 +31,14 @@ When you draw something, a draw command is sent to the GPU and the execution con
 after. But if you pass indices in RAM, the execution has to block until the GPU has finished
 drawing in order to make sure that the indices are not free'd.
 
+# Post-processing
+
+Before being uploaded, a list of indices can be processed in RAM with the functions of this
+module: `optimize_vertex_cache` reorders triangles so that the vertices are more often found in
+the post-transform cache, `to_triangles_list` converts strips and fans to lists,
+`generate_adjacency` produces the indices needed by `TrianglesListAdjacency` and `shrink`
+converts `u32` indices to a smaller type when possible.
+
 */
 use gl;
 use ToGlEnum;
@@ -41,9 +49,13 @@ use buffer::BufferViewAnySlice;
 
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::multidraw::{DrawCommandsNoIndicesBuffer, DrawCommandNoIndices};
+pub use self::multidraw::{DrawCommandsIndicesBuffer, DrawCommandIndices};
+pub use self::utils::{optimize_vertex_cache, to_triangles_list, generate_adjacency};
+pub use self::utils::{shrink, ShrunkIndices};
 
 mod buffer;
 mod multidraw;
+mod utils;
 
 /// Describes a source of indices used for drawing.
 #[derive(Clone)]
@@ -62,6 +74,24 @@ pub enum IndicesSource<'a> {
     MultidrawArray {
         /// The buffer.
         buffer: BufferViewAnySlice<'a>,
+        /// If `Some`, the number of commands to execute is read from the first element of this
+        /// buffer, and is capped by the given maximum.
+        draw_count: Option<(BufferViewAnySlice<'a>, usize)>,
+        /// Type of primitives contained in the vertex source.
+        primitives: PrimitiveType,
+    },
+
+    /// Use a multidraw indirect buffer with indices.
+    MultidrawElement {
+        /// The buffer of draw commands.
+        commands: BufferViewAnySlice<'a>,
+        /// The buffer of indices.
+        indices: BufferViewAnySlice<'a>,
+        /// If `Some`, the number of commands to execute is read from the first element of this
+        /// buffer, and is capped by the given maximum.
+        draw_count: Option<(BufferViewAnySlice<'a>, usize)>,
+        /// Type of indices in the buffer.
+        data_type: IndexType,
         /// Type of primitives contained in the vertex source.
         primitives: PrimitiveType,
     },
@@ -80,6 +110,7 @@ impl<'a> IndicesSource<'a> {
         match self {
             &IndicesSource::IndexBuffer { primitives, .. } => primitives,
             &IndicesSource::MultidrawArray { primitives, .. } => primitives,
+            &IndicesSource::MultidrawElement { primitives, .. } => primitives,
             &IndicesSource::NoIndices { primitives } => primitives,
         }
     }
@@ -115,6 +146,21 @@ pub enum PrimitiveType {
     },
 }
 
+impl PrimitiveType {
+    /// Returns true if primitives of this type can be restarted with the
+    /// `primitive_restart_index` draw parameter, in other words if this is a strip or a fan.
+    pub fn supports_primitive_restart(&self) -> bool {
+        match self {
+            &PrimitiveType::LineStrip => true,
+            &PrimitiveType::LineStripAdjacency => true,
+            &PrimitiveType::TriangleStrip => true,
+            &PrimitiveType::TriangleStripAdjacency => true,
+            &PrimitiveType::TriangleFan => true,
+            _ => false,
+        }
+    }
+}
+
 impl ToGlEnum for PrimitiveType {
     fn to_glenum(&self) -> gl::types::GLenum {
         match self {
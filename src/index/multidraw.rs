@@ -6,8 +6,11 @@ use std::ops::Deref;
 use std::ops::DerefMut;
 
 use backend::Facade;
-use buffer::{BufferCreationError, BufferType, BufferView};
+use buffer::{BufferCreationError, BufferType, BufferView, BufferViewSlice};
 use index::{IndicesSource, PrimitiveType};
+use version::{Api, Version};
+
+use ContextExt;
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -23,14 +26,19 @@ pub struct DrawCommandNoIndices {
     pub base_instance: libc::c_uint,
 }
 
+/// Represents a draw command with indices.
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-#[allow(missing_docs)]      // TODO: remove this
 pub struct DrawCommandIndices {
+    /// Number of indices to use in the index buffer.
     pub count: libc::c_uint,
+    /// Number of instances to draw. If it's `0`, nothing will be drawn.
     pub instance_count: libc::c_uint,
+    /// First index to use in the index buffer.
     pub first_index: libc::c_uint,
-    pub base_vertex: libc::c_uint,
+    /// Value to add to each index before fetching the vertex.
+    pub base_vertex: libc::c_int,
+    /// Numero of the first instance to draw.
     pub base_instance: libc::c_uint,
 }
 
@@ -77,9 +85,41 @@ impl DrawCommandsNoIndicesBuffer {
     pub fn with_primitive_type(&self, primitives: PrimitiveType) -> IndicesSource {
         IndicesSource::MultidrawArray {
             buffer: self.buffer.as_slice_any(),
+            draw_count: None,
             primitives: primitives,
         }
     }
+
+    /// Builds an indices source from this buffer and a primitives type. Instead of executing
+    /// all the commands of this buffer, the number of commands is read by the GPU from the
+    /// first element of `count` and can't exceed `max_count`.
+    ///
+    /// Returns `None` if the backend doesn't support reading the number of commands from
+    /// a buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `max_count` is superior to the number of commands in this buffer, or if
+    /// `count` is empty.
+    pub fn with_primitive_type_and_draw_count_if_supported<'a>(&'a self,
+                                                               primitives: PrimitiveType,
+                                                               count: BufferViewSlice<'a, u32>,
+                                                               max_count: usize)
+                                                               -> Option<IndicesSource<'a>>
+    {
+        if !is_draw_count_supported(&self.buffer) {
+            return None;
+        }
+
+        assert!(max_count <= self.buffer.len());
+        assert!(count.len() >= 1);
+
+        Some(IndicesSource::MultidrawArray {
+            buffer: self.buffer.as_slice_any(),
+            draw_count: Some((count.as_slice_any(), max_count)),
+            primitives: primitives,
+        })
+    }
 }
 
 impl Deref for DrawCommandsNoIndicesBuffer {
@@ -95,3 +135,138 @@ impl DerefMut for DrawCommandsNoIndicesBuffer {
         &mut self.buffer
     }
 }
+
+/// A buffer containing a list of draw commands with indices.
+///
+/// Contrary to `DrawCommandsNoIndicesBuffer`, this buffer can always be created. If the backend
+/// doesn't support multidraw indirect, the commands are read back when drawing and executed
+/// one by one.
+pub struct DrawCommandsIndicesBuffer {
+    buffer: BufferView<DrawCommandIndices>,
+}
+
+impl DrawCommandsIndicesBuffer {
+    /// Builds an empty buffer.
+    ///
+    /// The parameter indicates the number of elements.
+    pub fn empty<F>(facade: &F, elements: usize) -> DrawCommandsIndicesBuffer where F: Facade {
+        DrawCommandsIndicesBuffer {
+            buffer: BufferView::empty(facade, get_buffer_type(facade), elements, false).unwrap(),
+        }
+    }
+
+    /// Builds an empty buffer.
+    ///
+    /// The parameter indicates the number of elements.
+    pub fn empty_dynamic<F>(facade: &F, elements: usize) -> DrawCommandsIndicesBuffer
+                            where F: Facade
+    {
+        DrawCommandsIndicesBuffer {
+            buffer: BufferView::empty(facade, get_buffer_type(facade), elements, true).unwrap(),
+        }
+    }
+
+    /// Builds an indices source from this buffer and a source of indices. This indices source
+    /// can be passed to the `draw()` function.
+    ///
+    /// The `first_index` and `count` members of the commands are relative to the start of
+    /// the index buffer or of the slice. The type of primitives is the one of the index buffer.
+    ///
+    /// If the slice doesn't start at the beginning of its buffer, the commands are read back
+    /// when drawing and executed one by one.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the indices source is not an index buffer or a slice of an index buffer.
+    pub fn with_index_buffer<'a, I>(&'a self, indices: I) -> IndicesSource<'a>
+                                    where I: Into<IndicesSource<'a>>
+    {
+        match indices.into() {
+            IndicesSource::IndexBuffer { buffer, data_type, primitives } => {
+                IndicesSource::MultidrawElement {
+                    commands: self.buffer.as_slice_any(),
+                    indices: buffer,
+                    draw_count: None,
+                    data_type: data_type,
+                    primitives: primitives,
+                }
+            },
+            _ => panic!("The indices source of a multidraw command must be an index buffer")
+        }
+    }
+
+    /// Builds an indices source from this buffer and a source of indices. Instead of executing
+    /// all the commands of this buffer, the number of commands is read by the GPU from the
+    /// first element of `count` and can't exceed `max_count`.
+    ///
+    /// Returns `None` if the backend doesn't support reading the number of commands from
+    /// a buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the indices source is not an index buffer or a slice of an index buffer, if
+    /// `max_count` is superior to the number of commands in this buffer, or if `count`
+    /// is empty.
+    pub fn with_index_buffer_and_draw_count_if_supported<'a, I>(&'a self, indices: I,
+                                                                count: BufferViewSlice<'a, u32>,
+                                                                max_count: usize)
+                                                                -> Option<IndicesSource<'a>>
+                                                                where I: Into<IndicesSource<'a>>
+    {
+        if !is_draw_count_supported(&self.buffer) {
+            return None;
+        }
+
+        assert!(max_count <= self.buffer.len());
+        assert!(count.len() >= 1);
+
+        match self.with_index_buffer(indices) {
+            IndicesSource::MultidrawElement { commands, indices, data_type, primitives, .. } => {
+                Some(IndicesSource::MultidrawElement {
+                    commands: commands,
+                    indices: indices,
+                    draw_count: Some((count.as_slice_any(), max_count)),
+                    data_type: data_type,
+                    primitives: primitives,
+                })
+            },
+            _ => unreachable!()
+        }
+    }
+}
+
+impl Deref for DrawCommandsIndicesBuffer {
+    type Target = BufferView<DrawCommandIndices>;
+
+    fn deref(&self) -> &BufferView<DrawCommandIndices> {
+        &self.buffer
+    }
+}
+
+impl DerefMut for DrawCommandsIndicesBuffer {
+    fn deref_mut(&mut self) -> &mut BufferView<DrawCommandIndices> {
+        &mut self.buffer
+    }
+}
+
+/// Returns the type of buffer to use to store draw commands.
+///
+/// If multidraw indirect is not supported, the commands are stored in a regular buffer
+/// and read back when drawing.
+fn get_buffer_type<F>(facade: &F) -> BufferType where F: Facade {
+    let context = facade.get_context();
+
+    if context.get_version() >= &Version(Api::Gl, 4, 3) ||
+       context.get_extensions().gl_arb_multi_draw_indirect ||
+       context.get_extensions().gl_ext_multi_draw_indirect
+    {
+        BufferType::DrawIndirectBuffer
+    } else {
+        BufferType::ArrayBuffer
+    }
+}
+
+/// Returns true if the backend supports reading the number of draw commands from a buffer.
+fn is_draw_count_supported<T>(buffer: &BufferView<T>) -> bool where T: Copy + Send + 'static {
+    buffer.get_context().get_extensions().gl_arb_indirect_parameters
+}
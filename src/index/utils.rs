@@ -0,0 +1,316 @@
+//! Post-processing of indices in RAM before they are uploaded.
+use std::collections::HashMap;
+use std::{u8, u16, u32};
+
+use backend::Facade;
+use index::{IndexBuffer, IndexBufferAny, IndexType, PrimitiveType};
+
+/// Maximum number of vertices in the simulated post-transform cache.
+const CACHE_SIZE: usize = 32;
+
+/// Reorders a list of triangles so that vertices are more likely to be found in the
+/// post-transform cache of the GPU.
+///
+/// This uses Tom Forsyth's linear-speed vertex cache optimisation algorithm. The triangles
+/// are only reordered, and the winding of each triangle is kept. Doing this once when loading
+/// a mesh can reduce the number of times the vertex shader is executed.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of three.
+pub fn optimize_vertex_cache(indices: &mut [u32]) {
+    assert!(indices.len() % 3 == 0);
+
+    let triangles_count = indices.len() / 3;
+    let vertices_count = match indices.iter().max() {
+        Some(&max) => max as usize + 1,
+        None => return
+    };
+
+    // list of the triangles that use each vertex and that haven't been added yet
+    let mut vertex_triangles = vec![Vec::new(); vertices_count];
+    for (triangle, vertices) in indices.chunks(3).enumerate() {
+        for &vertex in vertices.iter() {
+            vertex_triangles[vertex as usize].push(triangle);
+        }
+    }
+
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertices_count];
+    let mut vertex_score = vertex_triangles.iter().map(|t| vertex_cache_score(None, t.len()))
+                                           .collect::<Vec<_>>();
+    let mut triangle_score = indices.chunks(3).map(|t| {
+        t.iter().map(|&v| vertex_score[v as usize]).fold(0.0, |a, b| a + b)
+    }).collect::<Vec<f32>>();
+    let mut triangle_added = vec![false; triangles_count];
+
+    let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut output = Vec::with_capacity(indices.len());
+    let mut best_triangle = None;
+
+    for _ in 0 .. triangles_count {
+        // if no triangle of the cache is available, looking for the best one among all of them
+        let triangle = match best_triangle {
+            Some(t) => t,
+            None => {
+                let mut best = None;
+                for t in 0 .. triangles_count {
+                    if triangle_added[t] {
+                        continue;
+                    }
+                    match best {
+                        Some(b) if triangle_score[b] >= triangle_score[t] => (),
+                        _ => best = Some(t)
+                    }
+                }
+                best.unwrap()
+            }
+        };
+
+        triangle_added[triangle] = true;
+        let vertices = [indices[triangle * 3], indices[triangle * 3 + 1],
+                        indices[triangle * 3 + 2]];
+        output.extend(vertices.iter().cloned());
+
+        // moving the vertices of the triangle at the front of the cache
+        for &vertex in vertices.iter().rev() {
+            let list = &mut vertex_triangles[vertex as usize];
+            if let Some(pos) = list.iter().position(|&t| t == triangle) {
+                list.swap_remove(pos);
+            }
+
+            if let Some(pos) = cache.iter().position(|&v| v == vertex) {
+                cache.remove(pos);
+            }
+            cache.insert(0, vertex);
+        }
+
+        // the vertices that are pushed out of the cache and the ones that remain in it
+        let mut updated = Vec::with_capacity(cache.len());
+        while cache.len() > CACHE_SIZE {
+            let vertex = cache.pop().unwrap();
+            cache_position[vertex as usize] = None;
+            updated.push(vertex);
+        }
+        for (position, &vertex) in cache.iter().enumerate() {
+            cache_position[vertex as usize] = Some(position);
+            updated.push(vertex);
+        }
+
+        for &vertex in updated.iter() {
+            let vertex = vertex as usize;
+            vertex_score[vertex] = vertex_cache_score(cache_position[vertex],
+                                                      vertex_triangles[vertex].len());
+        }
+
+        // updating the score of the triangles that are affected and choosing the next one
+        best_triangle = None;
+        for &vertex in updated.iter() {
+            for &t in vertex_triangles[vertex as usize].iter() {
+                triangle_score[t] = indices[t * 3 .. t * 3 + 3].iter()
+                                                               .map(|&v| vertex_score[v as usize])
+                                                               .fold(0.0, |a, b| a + b);
+
+                match best_triangle {
+                    Some(b) if triangle_score[b] >= triangle_score[t] => (),
+                    _ => best_triangle = Some(t)
+                }
+            }
+        }
+    }
+
+    for (dest, src) in indices.iter_mut().zip(output.into_iter()) {
+        *dest = src;
+    }
+}
+
+/// Computes the score of a vertex given its position in the cache and the number of triangles
+/// that still use it.
+fn vertex_cache_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+    if remaining_triangles == 0 {
+        return -1.0;
+    }
+
+    let mut score = match cache_position {
+        // the vertices of the last triangle get a fixed score, so that the next triangle
+        // doesn't simply reuse the same edge
+        Some(p) if p < 3 => 0.75,
+        Some(p) => (1.0 - (p - 3) as f32 / (CACHE_SIZE - 3) as f32).powf(1.5),
+        None => 0.0,
+    };
+
+    // boosting the vertices that are used by few triangles, to get rid of them quickly
+    score += 2.0 * (remaining_triangles as f32).powf(-0.5);
+    score
+}
+
+/// Converts indices of triangles strips or triangle fans into a list of triangles.
+///
+/// If `restart_index` is `Some`, each occurrence of this value starts a new strip or fan, like
+/// when drawing with primitive restart. Degenerate triangles of strips are removed, and the
+/// winding of each triangle is the same as when drawing the original primitives.
+///
+/// `TrianglesList` indices are returned as they are, minus any restart index.
+///
+/// # Panic
+///
+/// Panics if `primitives` is not `TrianglesList`, `TriangleStrip` or `TriangleFan`.
+pub fn to_triangles_list(primitives: PrimitiveType, indices: &[u32], restart_index: Option<u32>)
+                         -> Vec<u32>
+{
+    let mut output = Vec::with_capacity(indices.len() * 3);
+
+    let sequences = indices.split(|&i| Some(i) == restart_index).filter(|s| !s.is_empty());
+
+    for sequence in sequences {
+        match primitives {
+            PrimitiveType::TrianglesList => {
+                let len = sequence.len() / 3 * 3;
+                output.extend(sequence[.. len].iter().cloned());
+            },
+
+            PrimitiveType::TriangleStrip => {
+                for i in 2 .. sequence.len() {
+                    let (a, b, c) = if i % 2 == 0 {
+                        (sequence[i - 2], sequence[i - 1], sequence[i])
+                    } else {
+                        (sequence[i - 1], sequence[i - 2], sequence[i])
+                    };
+
+                    if a == b || b == c || a == c {
+                        continue;
+                    }
+
+                    output.push(a);
+                    output.push(b);
+                    output.push(c);
+                }
+            },
+
+            PrimitiveType::TriangleFan => {
+                for i in 2 .. sequence.len() {
+                    output.push(sequence[0]);
+                    output.push(sequence[i - 1]);
+                    output.push(sequence[i]);
+                }
+            },
+
+            p => panic!("Can't convert {:?} to a list of triangles", p)
+        }
+    }
+
+    output
+}
+
+/// Builds indices of `TrianglesListAdjacency` from a list of triangles.
+///
+/// For each triangle `(a, b, c)`, the output contains `a`, the vertex opposite to the edge
+/// `a-b` in the neighbouring triangle, `b`, the vertex opposite to `b-c`, `c` and the vertex
+/// opposite to `c-a`. When an edge doesn't have any neighbour, the vertex of the triangle
+/// itself that is opposite to the edge is used, so that a geometry shader can detect the
+/// border by comparing the adjacent vertex with the triangle.
+///
+/// Two triangles are neighbours if they share an edge in opposite directions, in other words
+/// if they have the same winding.
+///
+/// # Panic
+///
+/// Panics if the number of indices is not a multiple of three.
+pub fn generate_adjacency(indices: &[u32]) -> Vec<u32> {
+    assert!(indices.len() % 3 == 0);
+
+    // for each directed edge, the vertex opposite to it in its triangle
+    let mut edges = HashMap::with_capacity(indices.len());
+    for triangle in indices.chunks(3) {
+        for i in 0 .. 3 {
+            edges.insert((triangle[i], triangle[(i + 1) % 3]), triangle[(i + 2) % 3]);
+        }
+    }
+
+    let mut output = Vec::with_capacity(indices.len() * 2);
+    for triangle in indices.chunks(3) {
+        for i in 0 .. 3 {
+            let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+            output.push(a);
+            output.push(match edges.get(&(b, a)) {
+                Some(&opposite) => opposite,
+                None => triangle[(i + 2) % 3],
+            });
+        }
+    }
+
+    output
+}
+
+/// Indices stored with the smallest possible type.
+///
+/// Returned by `shrink`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShrunkIndices {
+    /// The indices fit in `u8`s.
+    U8(Vec<u8>),
+    /// The indices fit in `u16`s.
+    U16(Vec<u16>),
+    /// The indices need `u32`s.
+    U32(Vec<u32>),
+}
+
+impl ShrunkIndices {
+    /// Returns the type of the indices.
+    pub fn get_type(&self) -> IndexType {
+        match self {
+            &ShrunkIndices::U8(_) => IndexType::U8,
+            &ShrunkIndices::U16(_) => IndexType::U16,
+            &ShrunkIndices::U32(_) => IndexType::U32,
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        match self {
+            &ShrunkIndices::U8(ref data) => data.len(),
+            &ShrunkIndices::U16(ref data) => data.len(),
+            &ShrunkIndices::U32(ref data) => data.len(),
+        }
+    }
+
+    /// Uploads the indices in an index buffer.
+    pub fn into_index_buffer<F>(self, facade: &F, primitives: PrimitiveType) -> IndexBufferAny
+                                where F: Facade
+    {
+        match self {
+            ShrunkIndices::U8(data) => IndexBuffer::new(facade, primitives, data).into(),
+            ShrunkIndices::U16(data) => IndexBuffer::new(facade, primitives, data).into(),
+            ShrunkIndices::U32(data) => IndexBuffer::new(facade, primitives, data).into(),
+        }
+    }
+}
+
+/// Converts `u32` indices to `u16`s or `u8`s if all the values fit.
+///
+/// If `primitive_restart` is true, the value `u32::MAX` is considered as the restart index and
+/// is converted to the maximum value of the new type, so that the result can still be drawn
+/// with the `primitive_restart_index` draw parameter. The other values must then be strictly
+/// smaller than this maximum.
+///
+/// Using smaller indices reduces the amount of memory used and the bandwidth needed to draw.
+pub fn shrink(indices: &[u32], primitive_restart: bool) -> ShrunkIndices {
+    let max = indices.iter().cloned().filter(|&i| !primitive_restart || i != u32::MAX)
+                     .max().unwrap_or(0);
+
+    // when primitive restart is used, the maximum value of each type is reserved
+    let reserved = if primitive_restart { 1 } else { 0 };
+
+    if max as u64 + reserved <= u8::MAX as u64 {
+        ShrunkIndices::U8(indices.iter().map(|&i| {
+            if primitive_restart && i == u32::MAX { u8::MAX } else { i as u8 }
+        }).collect())
+
+    } else if max as u64 + reserved <= u16::MAX as u64 {
+        ShrunkIndices::U16(indices.iter().map(|&i| {
+            if primitive_restart && i == u32::MAX { u16::MAX } else { i as u16 }
+        }).collect())
+
+    } else {
+        ShrunkIndices::U32(indices.to_vec())
+    }
+}
@@ -98,6 +98,8 @@ extern crate image;
 extern crate libc;
 #[cfg(feature = "nalgebra")]
 extern crate nalgebra;
+#[cfg(feature = "gltf")]
+extern crate rustc_serialize;
 
 #[cfg(feature = "glutin")]
 pub use backend::glutin_backend::glutin;
@@ -123,7 +125,11 @@ pub mod buffer;
 pub mod debug;
 pub mod draw_parameters;
 pub mod framebuffer;
+#[cfg(feature = "gltf")]
+pub mod gltf;
 pub mod index;
+#[cfg(feature = "mesh")]
+pub mod mesh;
 pub mod pixel_buffer;
 pub mod program;
 pub mod render_buffer;
@@ -220,6 +226,10 @@ trait ContextExt {
     fn get_samplers(&self) -> &RefCell<HashMap<uniforms::SamplerBehavior,
                                                sampler_object::SamplerObject>>;
 
+    /// Returns the programs that copy one sample of a multisample texture, indexed by the type
+    /// of sampler that they use.
+    fn get_copy_sample_programs(&self) -> &RefCell<HashMap<gl::types::GLenum, Program>>;
+
     /// Returns the capabilities of the backend.
     fn capabilities(&self) -> &context::Capabilities;
 
@@ -239,6 +249,10 @@ trait ProgramExt {
     /// Changes the block binding of the program.
     fn set_block(&self, ctxt: &mut context::CommandContext, block_location: gl::types::GLuint,
                  value: gl::types::GLuint);
+
+    /// Stops keeping the context of the program alive. The program must then be stored inside
+    /// its context, and the context must destroy it before being destroyed itself.
+    unsafe fn release_context(&mut self);
 }
 
 /// Internal trait for queries.
@@ -709,6 +723,53 @@ pub enum DrawError {
 
     /// See the documentation of the `draw_parameters` module for infos.
     WrongQueryOperation,
+
+    /// A negative base vertex has been requested, but the backend doesn't support
+    /// `glDrawElementsBaseVertex`.
+    BaseVertexNotSupported,
+
+    /// The base vertex is outside of the vertices sources.
+    BaseVertexOutOfRange,
+
+    /// A base instance has been requested in a draw command, but the backend doesn't support
+    /// it.
+    BaseInstanceNotSupported,
+
+    /// Instancing has been requested, with `instances_count` or in a draw command, but the
+    /// backend doesn't support it.
+    InstancingNotSupported,
+
+    /// The backend doesn't support multidraw indirect and the draw commands couldn't be read
+    /// back in order to be executed one by one.
+    MultidrawIndirectNotSupported,
+
+    /// The indices of a draw command are outside of the indices source.
+    DrawCommandIndicesOutOfRange,
+
+    /// An image unit has been bound to a uniform, but the backend doesn't support image load
+    /// and store.
+    ImageUnitsNotSupported,
+
+    /// The program uses more images than there are image units available.
+    TooManyImageUnits,
+
+    /// The layer of an image unit is outside of the layers of its texture.
+    ImageUnitLayerOutOfRange,
+
+    /// The number of work groups of a compute dispatch exceeds the maximum supported by the
+    /// backend.
+    TooManyWorkGroups,
+
+    /// Restarting primitives with the maximum index value has been requested, but the backend
+    /// doesn't support it.
+    FixedIndexRestartingNotSupported,
+
+    /// Restarting primitives has been requested with primitives that are not strips or fans.
+    PrimitiveRestartWithWrongPrimitives,
+
+    /// A sampler uses `SamplerWrapFunction::BorderClamp`, but this is not supported by the
+    /// backend.
+    BorderClampNotSupported,
 }
 
 impl std::fmt::Display for DrawError {
@@ -760,6 +821,47 @@ impl std::fmt::Display for DrawError {
                                                                       primitves, but this is not \
                                                                       supported by the backend."),
             &DrawError::WrongQueryOperation => write!(fmt, "Wrong query operation."),
+            &DrawError::BaseVertexNotSupported => write!(fmt, "A negative base vertex has been \
+                                                               requested, but this is not \
+                                                               supported by the backend."),
+            &DrawError::BaseVertexOutOfRange => write!(fmt, "The base vertex is outside of the \
+                                                             vertices sources."),
+            &DrawError::BaseInstanceNotSupported => write!(fmt, "A base instance has been \
+                                                                 requested in a draw command, \
+                                                                 but this is not supported by \
+                                                                 the backend."),
+            &DrawError::InstancingNotSupported => write!(fmt, "Instancing has been requested, \
+                                                               but it is not supported by the \
+                                                               backend."),
+            &DrawError::MultidrawIndirectNotSupported => write!(fmt, "Multidraw indirect is not \
+                                                                      supported by the backend \
+                                                                      and the draw commands \
+                                                                      couldn't be read back."),
+            &DrawError::DrawCommandIndicesOutOfRange => write!(fmt, "The indices of a draw \
+                                                                     command are outside of \
+                                                                     the indices source."),
+            &DrawError::ImageUnitsNotSupported => write!(fmt, "An image unit has been bound \
+                                                               to a uniform, but image load \
+                                                               and store is not supported by \
+                                                               the backend."),
+            &DrawError::TooManyImageUnits => write!(fmt, "The program uses more images than \
+                                                          there are image units available."),
+            &DrawError::ImageUnitLayerOutOfRange => write!(fmt, "The layer of an image unit is \
+                                                                 outside of the layers of its \
+                                                                 texture."),
+            &DrawError::TooManyWorkGroups => write!(fmt, "The number of work groups exceeds the \
+                                                          maximum supported by the backend."),
+            &DrawError::FixedIndexRestartingNotSupported => write!(fmt, "Restarting primitives \
+                                                                         with the maximum index \
+                                                                         value is not supported \
+                                                                         by the backend."),
+            &DrawError::PrimitiveRestartWithWrongPrimitives => write!(fmt, "Primitive restart \
+                                                                            can only be used \
+                                                                            with strips and \
+                                                                            fans."),
+            &DrawError::BorderClampNotSupported => write!(fmt, "Clamping to the border color \
+                                                                is not supported by the \
+                                                                backend."),
         }
     }
 }
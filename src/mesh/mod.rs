@@ -0,0 +1,366 @@
+/*!
+Loading meshes from files.
+
+This module is only available if the `mesh` feature is enabled.
+
+A `Mesh` can be loaded from a Wavefront OBJ file or from a PLY file (ASCII or binary). The
+vertices are deduplicated, missing normals are generated, and polygons are triangulated.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+use std::fs::File;
+use std::io::BufReader;
+
+let file = BufReader::new(File::open("teapot.obj").unwrap());
+let mesh = glium::mesh::Mesh::from_obj(file).unwrap();
+
+let vertex_buffer = mesh.to_vertex_buffer(&display);
+let index_buffer = mesh.to_index_buffer(&display);
+```
+
+# Vertex layout
+
+The vertex buffer produced by `to_vertex_buffer` has a layout that depends on the content of
+the mesh. The attributes are named:
+
+ - `position`, a `vec3`. Always present.
+ - `normal`, a `vec3`. Present if `normals` is `Some`.
+ - `texcoord`, a `vec2`. Present if `texcoords` is `Some`.
+ - `color`, a `vec4`. Present if `colors` is `Some`.
+
+The index buffer always contains a list of triangles.
+
+*/
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::error::Error;
+use std::io::{self, BufRead, Read};
+use std::ops::Range;
+use std::{fmt, mem};
+
+use backend::Facade;
+use index::{IndexBuffer, IndexBufferAny, PrimitiveType};
+use vertex::{AttributeType, VertexBuffer, VertexBufferAny, VertexFormat};
+
+mod obj;
+mod ply;
+
+/// Error that can happen while loading a mesh.
+#[derive(Debug)]
+pub enum MeshError {
+    /// Error while reading the data.
+    IoError(io::Error),
+
+    /// The data is not a valid mesh.
+    ParseError {
+        /// Line where the problem was found, starting at 1. `None` for binary data.
+        line: Option<usize>,
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &MeshError::IoError(ref err) =>
+                formatter.write_fmt(format_args!("Error while reading the mesh: {}", err)),
+            &MeshError::ParseError { line: Some(line), ref message } =>
+                formatter.write_fmt(format_args!("Invalid mesh at line {}: {}", line, message)),
+            &MeshError::ParseError { line: None, ref message } =>
+                formatter.write_fmt(format_args!("Invalid mesh: {}", message)),
+        }
+    }
+}
+
+impl Error for MeshError {
+    fn description(&self) -> &str {
+        match self {
+            &MeshError::IoError(_) => "Error while reading the mesh",
+            &MeshError::ParseError { .. } => "The data is not a valid mesh",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &MeshError::IoError(ref err) => Some(err as &Error),
+            &MeshError::ParseError { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for MeshError {
+    fn from(err: io::Error) -> MeshError {
+        MeshError::IoError(err)
+    }
+}
+
+/// A part of a mesh that has a name and a material.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// Name of the group.
+    pub name: String,
+
+    /// Name of the material used by this group, if any.
+    pub material: Option<String>,
+
+    /// Range of elements of `Mesh::indices` that belong to this group.
+    pub indices: Range<usize>,
+}
+
+/// A list of triangles loaded in RAM.
+///
+/// All the vertex streams that are `Some` have the same length as `positions`.
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    /// Position of each vertex.
+    pub positions: Vec<[f32; 3]>,
+
+    /// Normal of each vertex.
+    pub normals: Option<Vec<[f32; 3]>>,
+
+    /// Texture coordinates of each vertex.
+    pub texcoords: Option<Vec<[f32; 2]>>,
+
+    /// Color of each vertex, as RGBA values between `0.0` and `1.0`.
+    pub colors: Option<Vec<[f32; 4]>>,
+
+    /// List of triangles. Each group of three indices is a triangle.
+    pub indices: Vec<u32>,
+
+    /// Groups of triangles of the mesh. Empty if the format doesn't support groups.
+    pub groups: Vec<Group>,
+
+    /// Material libraries referenced by the file. Empty if the format doesn't support materials.
+    pub material_libraries: Vec<String>,
+}
+
+impl Mesh {
+    /// Loads a mesh from a Wavefront OBJ file.
+    ///
+    /// Faces are triangulated, and normals are generated for the vertices that don't have one.
+    /// Lines, points, curves and surfaces are ignored.
+    pub fn from_obj<R>(reader: R) -> Result<Mesh, MeshError> where R: BufRead {
+        obj::load(reader)
+    }
+
+    /// Loads a mesh from a PLY file, in either ASCII or binary format.
+    ///
+    /// The `vertex` element must have `x`, `y` and `z` properties. Normals are read from `nx`,
+    /// `ny` and `nz`, texture coordinates from `u` and `v` (or `s` and `t`) and colors from
+    /// `red`, `green`, `blue` and `alpha`. Faces are read from the `vertex_indices` list of
+    /// the `face` element. Other elements and properties are ignored.
+    pub fn from_ply<R>(reader: R) -> Result<Mesh, MeshError> where R: Read {
+        ply::load(reader)
+    }
+
+    /// Returns the number of vertices in the mesh.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Replaces the normals of the mesh with smooth normals computed from the triangles.
+    pub fn generate_normals(&mut self) {
+        self.normals = Some(compute_normals(&self.positions, &self.indices));
+    }
+
+    /// Merges the vertices that have exactly the same attributes, and updates the indices.
+    pub fn deduplicate(&mut self) {
+        let mut vertices: HashMap<Vec<u32>, u32> = HashMap::new();
+        // for each old vertex, its new index
+        let mut remap = Vec::with_capacity(self.len());
+        // for each new vertex, its old index
+        let mut kept = Vec::new();
+
+        for vertex in 0 .. self.len() {
+            let mut key = Vec::with_capacity(12);
+            key.extend(self.positions[vertex].iter().map(|v| to_bits(*v)));
+            if let Some(ref normals) = self.normals {
+                key.extend(normals[vertex].iter().map(|v| to_bits(*v)));
+            }
+            if let Some(ref texcoords) = self.texcoords {
+                key.extend(texcoords[vertex].iter().map(|v| to_bits(*v)));
+            }
+            if let Some(ref colors) = self.colors {
+                key.extend(colors[vertex].iter().map(|v| to_bits(*v)));
+            }
+
+            let index = match vertices.entry(key) {
+                Entry::Occupied(e) => *e.get(),
+                Entry::Vacant(e) => {
+                    kept.push(vertex);
+                    *e.insert(kept.len() as u32 - 1)
+                },
+            };
+
+            remap.push(index);
+        }
+
+        if kept.len() == self.len() {
+            return;
+        }
+
+        self.positions = kept.iter().map(|&v| self.positions[v]).collect();
+        self.normals = self.normals.as_ref().map(|n| kept.iter().map(|&v| n[v]).collect());
+        self.texcoords = self.texcoords.as_ref().map(|t| kept.iter().map(|&v| t[v]).collect());
+        self.colors = self.colors.as_ref().map(|c| kept.iter().map(|&v| c[v]).collect());
+
+        for index in self.indices.iter_mut() {
+            *index = remap[*index as usize];
+        }
+    }
+
+    /// Returns the format of the vertices produced by `to_vertex_buffer`.
+    pub fn get_vertex_format(&self) -> VertexFormat {
+        let mut format = Vec::with_capacity(4);
+        let mut offset = 0;
+
+        format.push((Cow::Borrowed("position"), offset, AttributeType::F32F32F32));
+        offset += 3 * mem::size_of::<f32>();
+
+        if self.normals.is_some() {
+            format.push((Cow::Borrowed("normal"), offset, AttributeType::F32F32F32));
+            offset += 3 * mem::size_of::<f32>();
+        }
+
+        if self.texcoords.is_some() {
+            format.push((Cow::Borrowed("texcoord"), offset, AttributeType::F32F32));
+            offset += 2 * mem::size_of::<f32>();
+        }
+
+        if self.colors.is_some() {
+            format.push((Cow::Borrowed("color"), offset, AttributeType::F32F32F32F32));
+        }
+
+        Cow::Owned(format)
+    }
+
+    /// Uploads the vertices of the mesh in a vertex buffer.
+    ///
+    /// See the documentation of the module for the layout of the vertices.
+    pub fn to_vertex_buffer<F>(&self, facade: &F) -> VertexBufferAny where F: Facade {
+        let mut data = Vec::new();
+
+        for vertex in 0 .. self.len() {
+            data.extend(self.positions[vertex].iter().cloned());
+            if let Some(ref normals) = self.normals {
+                data.extend(normals[vertex].iter().cloned());
+            }
+            if let Some(ref texcoords) = self.texcoords {
+                data.extend(texcoords[vertex].iter().cloned());
+            }
+            if let Some(ref colors) = self.colors {
+                data.extend(colors[vertex].iter().cloned());
+            }
+        }
+
+        let stride = if self.len() == 0 { 3 } else { data.len() / self.len() };
+        let format = self.get_vertex_format();
+
+        // the vertex buffer needs an element type whose size is the size of a vertex
+        macro_rules! upload {
+            ($($n:expr),+) => (
+                match stride {
+                    $(
+                        $n => {
+                            let data = data.chunks($n).map(|chunk| {
+                                let mut vertex = [0.0f32; $n];
+                                for (dest, src) in vertex.iter_mut().zip(chunk.iter()) {
+                                    *dest = *src;
+                                }
+                                vertex
+                            }).collect::<Vec<_>>();
+
+                            let buffer = unsafe {
+                                VertexBuffer::new_raw(facade, data, format,
+                                                      $n * mem::size_of::<f32>())
+                            };
+
+                            buffer.into()
+                        },
+                    )+
+                    _ => unreachable!()
+                }
+            );
+        }
+
+        upload!(3, 5, 6, 7, 8, 9, 10, 12)
+    }
+
+    /// Uploads the indices of the mesh in an index buffer of `TrianglesList`.
+    ///
+    /// The indices are stored as `u16` if possible, and as `u32` otherwise.
+    pub fn to_index_buffer<F>(&self, facade: &F) -> IndexBufferAny where F: Facade {
+        if self.len() <= 65536 {
+            let indices = self.indices.iter().map(|&i| i as u16).collect::<Vec<_>>();
+            IndexBuffer::new(facade, PrimitiveType::TrianglesList, indices).into()
+        } else {
+            IndexBuffer::new(facade, PrimitiveType::TrianglesList, &self.indices[..]).into()
+        }
+    }
+}
+
+/// Builds a `ParseError`.
+fn parse_error(line: Option<usize>, message: String) -> MeshError {
+    MeshError::ParseError {
+        line: line,
+        message: message,
+    }
+}
+
+/// Computes smooth normals by adding the normals of the triangles around each vertex, weighted
+/// by their area.
+fn compute_normals(positions: &[[f32; 3]], indices: &[u32]) -> Vec<[f32; 3]> {
+    let mut normals = vec![[0.0f32; 3]; positions.len()];
+
+    for triangle in indices.chunks(3) {
+        if triangle.len() != 3 {
+            break;
+        }
+
+        let a = positions[triangle[0] as usize];
+        let b = positions[triangle[1] as usize];
+        let c = positions[triangle[2] as usize];
+
+        let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+
+        // the length of the cross product is twice the area of the triangle
+        let normal = [
+            ab[1] * ac[2] - ab[2] * ac[1],
+            ab[2] * ac[0] - ab[0] * ac[2],
+            ab[0] * ac[1] - ab[1] * ac[0],
+        ];
+
+        for &vertex in triangle.iter() {
+            let n = &mut normals[vertex as usize];
+            n[0] += normal[0];
+            n[1] += normal[1];
+            n[2] += normal[2];
+        }
+    }
+
+    for n in normals.iter_mut() {
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        if len > 0.0 {
+            n[0] /= len;
+            n[1] /= len;
+            n[2] /= len;
+        }
+    }
+
+    normals
+}
+
+/// Returns the bits of a float, for hashing.
+fn to_bits(value: f32) -> u32 {
+    // all the NaNs and both zeroes are considered equal
+    if value != value {
+        0x7fc00000
+    } else if value == 0.0 {
+        0
+    } else {
+        unsafe { mem::transmute(value) }
+    }
+}
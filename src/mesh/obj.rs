@@ -0,0 +1,270 @@
+//! Loading Wavefront OBJ files.
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io::BufRead;
+
+use mesh::{compute_normals, parse_error, Group, Mesh, MeshError};
+
+/// Indices of the position, texture coordinates and normal of a vertex of a face.
+type VertexKey = (usize, Option<usize>, Option<usize>);
+
+pub fn load<R>(reader: R) -> Result<Mesh, MeshError> where R: BufRead {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut position_colors: Vec<Option<[f32; 4]>> = Vec::new();
+    let mut texcoords: Vec<[f32; 2]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+
+    // the vertices of the final mesh and the index of each of them
+    let mut vertices: HashMap<VertexKey, u32> = HashMap::new();
+    let mut keys: Vec<VertexKey> = Vec::new();
+
+    let mut indices: Vec<u32> = Vec::new();
+    let mut groups = vec![Group {
+        name: "default".to_string(),
+        material: None,
+        indices: 0 .. 0,
+    }];
+    let mut material_libraries = Vec::new();
+
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = try!(line);
+        let line_num = line_num + 1;
+
+        let line = match line.find('#') {
+            Some(pos) => &line[.. pos],
+            None => &line[..]
+        };
+
+        let mut words = line.split(|c: char| c.is_whitespace()).filter(|w| !w.is_empty());
+        let keyword = match words.next() {
+            Some(k) => k,
+            None => continue
+        };
+        let args = words.collect::<Vec<_>>();
+
+        match keyword {
+            "v" => {
+                let values = try!(parse_floats(&args, line_num));
+                match values.len() {
+                    3 | 4 => {
+                        positions.push([values[0], values[1], values[2]]);
+                        position_colors.push(None);
+                    },
+                    6 => {
+                        positions.push([values[0], values[1], values[2]]);
+                        position_colors.push(Some([values[3], values[4], values[5], 1.0]));
+                    },
+                    n => return Err(parse_error(Some(line_num),
+                                                format!("expected 3, 4 or 6 values for a \
+                                                         vertex position, found {}", n))),
+                }
+            },
+
+            "vt" => {
+                let values = try!(parse_floats(&args, line_num));
+                match values.len() {
+                    1 => texcoords.push([values[0], 0.0]),
+                    2 | 3 => texcoords.push([values[0], values[1]]),
+                    n => return Err(parse_error(Some(line_num),
+                                                format!("expected 1, 2 or 3 values for \
+                                                         texture coordinates, found {}", n))),
+                }
+            },
+
+            "vn" => {
+                let values = try!(parse_floats(&args, line_num));
+                if values.len() != 3 {
+                    return Err(parse_error(Some(line_num),
+                                           format!("expected 3 values for a normal, found {}",
+                                                   values.len())));
+                }
+                normals.push([values[0], values[1], values[2]]);
+            },
+
+            "f" => {
+                if args.len() < 3 {
+                    return Err(parse_error(Some(line_num),
+                                           format!("a face must have at least 3 vertices, \
+                                                    found {}", args.len())));
+                }
+
+                let mut face = Vec::with_capacity(args.len());
+                for arg in args.iter() {
+                    let key = try!(parse_face_vertex(arg, positions.len(), texcoords.len(),
+                                                     normals.len(), line_num));
+
+                    let index = match vertices.entry(key) {
+                        Entry::Occupied(e) => *e.get(),
+                        Entry::Vacant(e) => {
+                            keys.push(key);
+                            *e.insert(keys.len() as u32 - 1)
+                        },
+                    };
+
+                    face.push(index);
+                }
+
+                // triangulating the polygon as a fan
+                for i in 1 .. face.len() - 1 {
+                    indices.push(face[0]);
+                    indices.push(face[i]);
+                    indices.push(face[i + 1]);
+                }
+            },
+
+            "g" | "o" => {
+                let name = if args.is_empty() { "default".to_string() } else { args.connect(" ") };
+                let material = groups.last().unwrap().material.clone();
+                start_group(&mut groups, indices.len(), name, material);
+            },
+
+            "usemtl" => {
+                if args.is_empty() {
+                    return Err(parse_error(Some(line_num),
+                                           "missing material name after `usemtl`".to_string()));
+                }
+
+                let name = groups.last().unwrap().name.clone();
+                start_group(&mut groups, indices.len(), name, Some(args.connect(" ")));
+            },
+
+            "mtllib" => {
+                material_libraries.extend(args.iter().map(|s| s.to_string()));
+            },
+
+            // lines, points, smoothing groups, curves, etc. are not supported
+            _ => ()
+        }
+    }
+
+    // finishing the groups and removing the empty ones
+    let total_indices = indices.len();
+    let mut next_start = total_indices;
+    for group in groups.iter_mut().rev() {
+        group.indices.end = next_start;
+        next_start = group.indices.start;
+    }
+    let groups = groups.into_iter().filter(|g| g.indices.start != g.indices.end).collect();
+
+    let has_texcoords = keys.iter().any(|k| k.1.is_some());
+    let has_colors = keys.iter().any(|k| position_colors[k.0].is_some());
+
+    let mesh_positions = keys.iter().map(|k| positions[k.0]).collect::<Vec<_>>();
+
+    let mesh_texcoords = if has_texcoords {
+        Some(keys.iter().map(|k| k.1.map(|i| texcoords[i]).unwrap_or([0.0, 0.0])).collect())
+    } else {
+        None
+    };
+
+    let mesh_colors = if has_colors {
+        Some(keys.iter().map(|k| position_colors[k.0].unwrap_or([1.0, 1.0, 1.0, 1.0]))
+                 .collect())
+    } else {
+        None
+    };
+
+    // generating the normals of the vertices that don't have one
+    let mesh_normals = if keys.iter().all(|k| k.2.is_some()) {
+        keys.iter().map(|k| normals[k.2.unwrap()]).collect()
+    } else {
+        let generated = compute_normals(&mesh_positions, &indices);
+        keys.iter().zip(generated.into_iter())
+                   .map(|(k, generated)| k.2.map(|i| normals[i]).unwrap_or(generated))
+                   .collect()
+    };
+
+    Ok(Mesh {
+        positions: mesh_positions,
+        normals: Some(mesh_normals),
+        texcoords: mesh_texcoords,
+        colors: mesh_colors,
+        indices: indices,
+        groups: groups,
+        material_libraries: material_libraries,
+    })
+}
+
+/// Starts a new group at the given position in the list of indices.
+fn start_group(groups: &mut Vec<Group>, start: usize, name: String, material: Option<String>) {
+    // a group that doesn't have any face yet is simply replaced
+    if groups.last().unwrap().indices.start == start {
+        let last = groups.last_mut().unwrap();
+        last.name = name;
+        last.material = material;
+        return;
+    }
+
+    groups.push(Group {
+        name: name,
+        material: material,
+        indices: start .. start,
+    });
+}
+
+fn parse_floats(args: &[&str], line_num: usize) -> Result<Vec<f32>, MeshError> {
+    let mut values = Vec::with_capacity(args.len());
+
+    for arg in args.iter() {
+        match arg.parse() {
+            Ok(v) => values.push(v),
+            Err(_) => return Err(parse_error(Some(line_num),
+                                             format!("`{}` is not a valid number", arg))),
+        }
+    }
+
+    Ok(values)
+}
+
+/// Parses a vertex of a face, in the `v`, `v/vt`, `v//vn` or `v/vt/vn` format.
+fn parse_face_vertex(arg: &str, positions: usize, texcoords: usize, normals: usize,
+                     line_num: usize) -> Result<VertexKey, MeshError>
+{
+    let mut parts = arg.split('/');
+
+    let position = match parts.next() {
+        Some(p) if !p.is_empty() => try!(resolve_index(p, positions, line_num)),
+        _ => return Err(parse_error(Some(line_num),
+                                    format!("missing position index in `{}`", arg))),
+    };
+
+    let texcoord = match parts.next() {
+        Some(p) if !p.is_empty() => Some(try!(resolve_index(p, texcoords, line_num))),
+        _ => None
+    };
+
+    let normal = match parts.next() {
+        Some(p) if !p.is_empty() => Some(try!(resolve_index(p, normals, line_num))),
+        _ => None
+    };
+
+    if parts.next().is_some() {
+        return Err(parse_error(Some(line_num), format!("invalid face vertex `{}`", arg)));
+    }
+
+    Ok((position, texcoord, normal))
+}
+
+/// Turns a one-based or negative OBJ index into a zero-based index.
+fn resolve_index(value: &str, count: usize, line_num: usize) -> Result<usize, MeshError> {
+    let index: i64 = match value.parse() {
+        Ok(i) => i,
+        Err(_) => return Err(parse_error(Some(line_num),
+                                         format!("`{}` is not a valid index", value))),
+    };
+
+    // negative indices are relative to the end of the list
+    let resolved = if index > 0 {
+        index - 1
+    } else if index < 0 {
+        count as i64 + index
+    } else {
+        return Err(parse_error(Some(line_num), "indices start at 1, found 0".to_string()));
+    };
+
+    if resolved < 0 || resolved >= count as i64 {
+        return Err(parse_error(Some(line_num), format!("index {} is out of range", index)));
+    }
+
+    Ok(resolved as usize)
+}
@@ -0,0 +1,483 @@
+//! Loading PLY files.
+use std::io::Read;
+use std::{mem, str};
+
+use mesh::{parse_error, Mesh, MeshError};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn from_name(name: &str) -> Option<ScalarType> {
+        match name {
+            "char" | "int8" => Some(ScalarType::I8),
+            "uchar" | "uint8" => Some(ScalarType::U8),
+            "short" | "int16" => Some(ScalarType::I16),
+            "ushort" | "uint16" => Some(ScalarType::U16),
+            "int" | "int32" => Some(ScalarType::I32),
+            "uint" | "uint32" => Some(ScalarType::U32),
+            "float" | "float32" => Some(ScalarType::F32),
+            "double" | "float64" => Some(ScalarType::F64),
+            _ => None
+        }
+    }
+
+    fn get_size(&self) -> usize {
+        match *self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+            ScalarType::F64 => 8,
+        }
+    }
+
+    /// Returns the value that corresponds to `1.0` when this type is used for a color.
+    fn get_color_max(&self) -> f64 {
+        match *self {
+            ScalarType::U8 => 255.0,
+            ScalarType::U16 => 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Property {
+    Scalar(String, ScalarType),
+    /// Name, type of the length and type of the elements.
+    List(String, ScalarType, ScalarType),
+}
+
+impl Property {
+    fn get_name(&self) -> &str {
+        match self {
+            &Property::Scalar(ref name, _) => name,
+            &Property::List(ref name, _, _) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    fn find_property(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|p| p.get_name() == name)
+    }
+
+    fn find_scalar(&self, name: &str) -> Option<(usize, ScalarType)> {
+        self.properties.iter().enumerate().filter_map(|(n, p)| {
+            match p {
+                &Property::Scalar(ref p, ty) if p == name => Some((n, ty)),
+                _ => None
+            }
+        }).next()
+    }
+}
+
+/// Reads the values of the body of the file.
+enum Body<'a> {
+    Ascii {
+        /// Each token and the line where it is.
+        tokens: Vec<(usize, &'a str)>,
+        position: usize,
+    },
+    Binary {
+        data: &'a [u8],
+        position: usize,
+        big_endian: bool,
+    },
+}
+
+impl<'a> Body<'a> {
+    fn read(&mut self, ty: ScalarType) -> Result<f64, MeshError> {
+        match self {
+            &mut Body::Ascii { ref tokens, ref mut position } => {
+                let (line_num, token) = match tokens.get(*position) {
+                    Some(t) => *t,
+                    None => return Err(parse_error(None, "unexpected end of data".to_string())),
+                };
+
+                *position += 1;
+
+                let value: f64 = match token.parse() {
+                    Ok(v) => v,
+                    Err(_) => return Err(parse_error(Some(line_num),
+                                                     format!("`{}` is not a valid number",
+                                                             token))),
+                };
+
+                match ty {
+                    ScalarType::F32 | ScalarType::F64 => (),
+                    _ if value.fract() != 0.0 => {
+                        return Err(parse_error(Some(line_num),
+                                               format!("`{}` is not a valid integer", token)));
+                    },
+                    _ => ()
+                }
+
+                Ok(value)
+            },
+
+            &mut Body::Binary { data, ref mut position, big_endian } => {
+                let size = ty.get_size();
+                if *position + size > data.len() {
+                    return Err(parse_error(None, "unexpected end of data".to_string()));
+                }
+
+                let bytes = &data[*position .. *position + size];
+                *position += size;
+
+                let mut bits: u64 = 0;
+                for i in 0 .. size {
+                    let byte = if big_endian { bytes[i] } else { bytes[size - 1 - i] };
+                    bits = (bits << 8) | byte as u64;
+                }
+
+                Ok(match ty {
+                    ScalarType::I8 => bits as u8 as i8 as f64,
+                    ScalarType::U8 => bits as u8 as f64,
+                    ScalarType::I16 => bits as u16 as i16 as f64,
+                    ScalarType::U16 => bits as u16 as f64,
+                    ScalarType::I32 => bits as u32 as i32 as f64,
+                    ScalarType::U32 => bits as u32 as f64,
+                    ScalarType::F32 => unsafe { mem::transmute::<u32, f32>(bits as u32) as f64 },
+                    ScalarType::F64 => unsafe { mem::transmute::<u64, f64>(bits) },
+                })
+            },
+        }
+    }
+}
+
+pub fn load<R>(mut reader: R) -> Result<Mesh, MeshError> where R: Read {
+    let mut data = Vec::new();
+    try!(reader.read_to_end(&mut data));
+
+    // parsing the header
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    let mut position = 0;
+    let mut line_num = 0;
+
+    loop {
+        let end = match data[position ..].iter().position(|&b| b == b'\n') {
+            Some(end) => position + end,
+            None => return Err(parse_error(None, "missing `end_header`".to_string())),
+        };
+
+        let line = match str::from_utf8(&data[position .. end]) {
+            Ok(l) => l.trim_right_matches('\r'),
+            Err(_) => return Err(parse_error(Some(line_num + 1),
+                                             "the header is not valid text".to_string())),
+        };
+
+        position = end + 1;
+        line_num += 1;
+
+        let words = line.split(|c: char| c.is_whitespace()).filter(|w| !w.is_empty())
+                        .collect::<Vec<_>>();
+
+        if line_num == 1 {
+            if words != ["ply"] {
+                return Err(parse_error(Some(1), "this is not a PLY file".to_string()));
+            }
+            continue;
+        }
+
+        match words.get(0).map(|w| *w) {
+            Some("format") => {
+                if words.len() != 3 || words[2] != "1.0" {
+                    return Err(parse_error(Some(line_num), "unsupported format".to_string()));
+                }
+
+                format = Some(match words[1] {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    f => return Err(parse_error(Some(line_num),
+                                                format!("unsupported format `{}`", f))),
+                });
+            },
+
+            Some("element") => {
+                if words.len() != 3 {
+                    return Err(parse_error(Some(line_num), "invalid element".to_string()));
+                }
+
+                let count = match words[2].parse() {
+                    Ok(c) => c,
+                    Err(_) => return Err(parse_error(Some(line_num),
+                                                     format!("`{}` is not a valid number of \
+                                                              elements", words[2]))),
+                };
+
+                elements.push(Element {
+                    name: words[1].to_string(),
+                    count: count,
+                    properties: Vec::new(),
+                });
+            },
+
+            Some("property") => {
+                let element = match elements.last_mut() {
+                    Some(e) => e,
+                    None => return Err(parse_error(Some(line_num),
+                                                   "property outside of an element".to_string())),
+                };
+
+                let property = if words.len() == 3 {
+                    let ty = try!(parse_type(words[1], line_num));
+                    Property::Scalar(words[2].to_string(), ty)
+                } else if words.len() == 5 && words[1] == "list" {
+                    let len_ty = try!(parse_type(words[2], line_num));
+                    let ty = try!(parse_type(words[3], line_num));
+                    Property::List(words[4].to_string(), len_ty, ty)
+                } else {
+                    return Err(parse_error(Some(line_num), "invalid property".to_string()));
+                };
+
+                element.properties.push(property);
+            },
+
+            Some("end_header") => break,
+            Some("comment") | Some("obj_info") | None => (),
+            Some(w) => return Err(parse_error(Some(line_num),
+                                              format!("unknown header keyword `{}`", w))),
+        }
+    }
+
+    let format = match format {
+        Some(f) => f,
+        None => return Err(parse_error(None, "missing `format` in the header".to_string())),
+    };
+
+    let mut body = match format {
+        Format::Ascii => {
+            let text = match str::from_utf8(&data[position ..]) {
+                Ok(t) => t,
+                Err(_) => return Err(parse_error(None, "the data is not valid text".to_string())),
+            };
+
+            let mut tokens = Vec::new();
+            for (num, line) in text.lines().enumerate() {
+                let words = line.split(|c: char| c.is_whitespace()).filter(|w| !w.is_empty());
+                tokens.extend(words.map(|w| (line_num + num + 1, w)));
+            }
+
+            Body::Ascii { tokens: tokens, position: 0 }
+        },
+        Format::BinaryLittleEndian | Format::BinaryBigEndian => {
+            Body::Binary {
+                data: &data[position ..],
+                position: 0,
+                big_endian: format == Format::BinaryBigEndian,
+            }
+        },
+    };
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut texcoords = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+
+    let mut has_vertices = false;
+    let mut has_normals = false;
+    let mut has_texcoords = false;
+    let mut has_colors = false;
+
+    for element in elements.iter() {
+        match &element.name[..] {
+            "vertex" => {
+                has_vertices = true;
+
+                let x = element.find_scalar("x");
+                let y = element.find_scalar("y");
+                let z = element.find_scalar("z");
+                let (x, y, z) = match (x, y, z) {
+                    (Some(x), Some(y), Some(z)) => (x.0, y.0, z.0),
+                    _ => return Err(parse_error(None, "the vertices must have `x`, `y` and \
+                                                       `z` properties".to_string())),
+                };
+
+                let normal = match (element.find_scalar("nx"), element.find_scalar("ny"),
+                                    element.find_scalar("nz"))
+                {
+                    (Some(x), Some(y), Some(z)) => Some((x.0, y.0, z.0)),
+                    _ => None
+                };
+
+                let texcoord = [("u", "v"), ("s", "t"), ("texture_u", "texture_v"),
+                                ("texture_s", "texture_t")].iter().filter_map(|&(u, v)| {
+                    match (element.find_scalar(u), element.find_scalar(v)) {
+                        (Some(u), Some(v)) => Some((u.0, v.0)),
+                        _ => None
+                    }
+                }).next();
+
+                let color = match (element.find_scalar("red"), element.find_scalar("green"),
+                                   element.find_scalar("blue"))
+                {
+                    (Some(r), Some(g), Some(b)) => Some((r, g, b, element.find_scalar("alpha"))),
+                    _ => None
+                };
+
+                has_normals = normal.is_some();
+                has_texcoords = texcoord.is_some();
+                has_colors = color.is_some();
+
+                let mut values = Vec::with_capacity(element.properties.len());
+                for _ in 0 .. element.count {
+                    values.clear();
+                    for property in element.properties.iter() {
+                        values.push(try!(read_property(&mut body, property)));
+                    }
+
+                    positions.push([values[x] as f32, values[y] as f32, values[z] as f32]);
+
+                    if let Some((x, y, z)) = normal {
+                        normals.push([values[x] as f32, values[y] as f32, values[z] as f32]);
+                    }
+
+                    if let Some((u, v)) = texcoord {
+                        texcoords.push([values[u] as f32, values[v] as f32]);
+                    }
+
+                    if let Some((r, g, b, a)) = color {
+                        let a = a.map(|(a, ty)| values[a] / ty.get_color_max()).unwrap_or(1.0);
+                        colors.push([(values[r.0] / r.1.get_color_max()) as f32,
+                                     (values[g.0] / g.1.get_color_max()) as f32,
+                                     (values[b.0] / b.1.get_color_max()) as f32,
+                                     a as f32]);
+                    }
+                }
+            },
+
+            "face" => {
+                let list = element.find_property("vertex_indices")
+                                  .or_else(|| element.find_property("vertex_index"));
+
+                let list = match list {
+                    Some(l) => l,
+                    None => return Err(parse_error(None, "the faces must have a \
+                                                          `vertex_indices` property"
+                                                          .to_string())),
+                };
+
+                let (len_ty, ty) = match element.properties[list] {
+                    Property::List(_, len_ty, ty) => (len_ty, ty),
+                    Property::Scalar(..) => return Err(parse_error(None, "`vertex_indices` \
+                                                                          must be a list"
+                                                                          .to_string())),
+                };
+
+                for _ in 0 .. element.count {
+                    let mut face = Vec::new();
+
+                    for (num, property) in element.properties.iter().enumerate() {
+                        if num == list {
+                            let len = try!(body.read(len_ty)) as usize;
+                            for _ in 0 .. len {
+                                face.push(try!(body.read(ty)));
+                            }
+                        } else {
+                            try!(read_property(&mut body, property));
+                        }
+                    }
+
+                    if face.len() < 3 {
+                        return Err(parse_error(None,
+                                               format!("a face must have at least 3 vertices, \
+                                                        found {}", face.len())));
+                    }
+
+                    // triangulating the polygon as a fan
+                    for i in 1 .. face.len() - 1 {
+                        indices.push(face[0]);
+                        indices.push(face[i]);
+                        indices.push(face[i + 1]);
+                    }
+                }
+            },
+
+            _ => {
+                // other elements are skipped
+                for _ in 0 .. element.count {
+                    for property in element.properties.iter() {
+                        try!(read_property(&mut body, property));
+                    }
+                }
+            },
+        }
+    }
+
+    if !has_vertices {
+        return Err(parse_error(None, "missing `vertex` element".to_string()));
+    }
+
+    let mut checked_indices = Vec::with_capacity(indices.len());
+    for index in indices.into_iter() {
+        if index < 0.0 || index >= positions.len() as f64 {
+            return Err(parse_error(None, format!("vertex index {} is out of range", index)));
+        }
+        checked_indices.push(index as u32);
+    }
+
+    let mut mesh = Mesh {
+        positions: positions,
+        normals: if has_normals { Some(normals) } else { None },
+        texcoords: if has_texcoords { Some(texcoords) } else { None },
+        colors: if has_colors { Some(colors) } else { None },
+        indices: checked_indices,
+        groups: Vec::new(),
+        material_libraries: Vec::new(),
+    };
+
+    if mesh.normals.is_none() {
+        mesh.generate_normals();
+    }
+
+    mesh.deduplicate();
+    Ok(mesh)
+}
+
+fn parse_type(name: &str, line_num: usize) -> Result<ScalarType, MeshError> {
+    match ScalarType::from_name(name) {
+        Some(ty) => Ok(ty),
+        None => Err(parse_error(Some(line_num), format!("unknown property type `{}`", name))),
+    }
+}
+
+/// Reads a property and returns its value. Lists are skipped and return `0.0`.
+fn read_property(body: &mut Body, property: &Property) -> Result<f64, MeshError> {
+    match property {
+        &Property::Scalar(_, ty) => body.read(ty),
+        &Property::List(_, len_ty, ty) => {
+            let len = try!(body.read(len_ty)) as usize;
+            for _ in 0 .. len {
+                try!(body.read(ty));
+            }
+            Ok(0.0)
+        },
+    }
+}
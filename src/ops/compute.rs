@@ -0,0 +1,78 @@
+use std::mem;
+
+use BufferViewExt;
+use BufferViewSliceExt;
+use DrawError;
+use ProgramExt;
+
+use buffer::{BufferType, BufferViewAnySlice};
+use context::Context;
+use ContextExt;
+use Program;
+use sync;
+use uniforms::Uniforms;
+use gl;
+
+use super::draw::bind_uniforms;
+
+/// Number of work groups to execute.
+pub enum WorkGroups<'a> {
+    /// The number of work groups in each dimension is known by the CPU.
+    Direct(u32, u32, u32),
+
+    /// The number of work groups is read by the GPU from a `ComputeCommand` in a buffer.
+    Indirect(BufferViewAnySlice<'a>),
+}
+
+/// Executes a compute shader.
+pub fn dispatch<U>(context: &Context, program: &Program, uniforms: &U, groups: WorkGroups)
+                   -> Result<(), DrawError> where U: Uniforms
+{
+    let mut ctxt = context.make_current();
+
+    if let WorkGroups::Direct(x, y, z) = groups {
+        let max = ctxt.capabilities.max_compute_work_group_count;
+        if x > max.0 as u32 || y > max.1 as u32 || z > max.2 as u32 {
+            return Err(DrawError::TooManyWorkGroups);
+        }
+    }
+
+    // this contains the list of fences that will need to be fulfilled after the dispatch
+    let mut fences = Vec::with_capacity(0);
+
+    program.use_program(&mut ctxt);
+    try!(bind_uniforms(&mut ctxt, context, program, uniforms, &mut fences));
+
+    match groups {
+        WorkGroups::Direct(x, y, z) => {
+            unsafe { ctxt.gl.DispatchCompute(x, y, z); }
+        },
+
+        WorkGroups::Indirect(buffer) => {
+            if let Some(fence) = buffer.add_fence() {
+                fences.push(fence);
+            }
+
+            unsafe {
+                buffer.bind_to(&mut ctxt, BufferType::DispatchIndirectBuffer);
+                ctxt.gl.DispatchComputeIndirect(buffer.get_offset_bytes()
+                                                as gl::types::GLintptr);
+            }
+        },
+    }
+
+    // fulfilling the fences
+    for fence in fences.into_iter() {
+        let mut new_fence = Some(unsafe {
+            sync::new_linear_sync_fence_if_supported(&mut ctxt)
+        }.unwrap());
+
+        mem::swap(&mut new_fence, &mut *fence.borrow_mut());
+
+        if let Some(new_fence) = new_fence {
+            unsafe { sync::destroy_linear_sync_fence(&mut ctxt, new_fence) };
+        }
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::cmp;
 use std::ptr;
 use std::mem;
 use std::cell::RefCell;
@@ -9,7 +10,7 @@ use ProgramExt;
 use DrawError;
 use RawUniformValue;
 
-use context::Context;
+use context::{Context, ImageUnitState};
 use ContextExt;
 use QueryExt;
 use TransformFeedbackSessionExt;
@@ -20,10 +21,12 @@ use fbo::{self, ValidatedAttachments};
 
 use sync;
 use buffer::BufferType;
-use uniforms::{Uniforms, UniformValue, SamplerBehavior};
+use uniforms::{Uniforms, UniformValue, SamplerBehavior, ImageUnit};
+use uniforms::{MinifySamplerFilter, SamplerWrapFunction};
 use sampler_object::SamplerObject;
+use texture::TextureType;
 use {Program, GlObject, ToGlEnum};
-use index::{self, IndicesSource};
+use index::{self, IndicesSource, DrawCommandIndices};
 use vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
 use vertex_array_object::VertexAttributesSystem;
 
@@ -49,6 +52,22 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
 {
     try!(draw_parameters::validate(context, draw_parameters));
 
+    // type of the indices that may restart primitives, or `None` if primitive restart is unused
+    let primitive_restart_index_type = if draw_parameters.primitive_restart_index {
+        match indices {
+            IndicesSource::IndexBuffer { data_type, primitives, .. } |
+            IndicesSource::MultidrawElement { data_type, primitives, .. } => {
+                if !primitives.supports_primitive_restart() {
+                    return Err(DrawError::PrimitiveRestartWithWrongPrimitives);
+                }
+                Some(data_type)
+            },
+            _ => None
+        }
+    } else {
+        None
+    };
+
     // this contains the list of fences that will need to be fulfilled after the draw command
     // has started
     let mut fences = Vec::with_capacity(0);
@@ -83,6 +102,86 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         },
     };
 
+    // handling base vertex and base instance
+    // if the backend doesn't support them, we emulate them by offsetting the vertices sources
+    let base_vertex_supported = context.get_version() >= &Version(Api::Gl, 3, 2) ||
+                                context.get_extensions().gl_arb_draw_elements_base_vertex;
+    let base_instance_supported = context.get_version() >= &Version(Api::Gl, 4, 2) ||
+                                  context.get_extensions().gl_arb_base_instance;
+    let instancing_supported = context.get_version() >= &Version(Api::Gl, 3, 1) ||
+                               context.get_version() >= &Version(Api::GlEs, 3, 0);
+
+    let base_vertex = draw_parameters.base_vertex;
+    let base_instance = draw_parameters.base_instance as usize;
+
+    // number of elements to skip at the start of each per-vertex source
+    let vertices_offset = match indices {
+        IndicesSource::IndexBuffer { .. } if !base_vertex_supported => {
+            if base_vertex < 0 {
+                return Err(DrawError::BaseVertexNotSupported);
+            }
+            base_vertex as usize
+        },
+        IndicesSource::NoIndices { .. } if base_vertex < 0 => {
+            return Err(DrawError::BaseVertexOutOfRange);
+        },
+        _ => 0
+    };
+
+    // number of elements to skip at the start of each per-instance source
+    let instances_offset = if base_instance_supported { 0 } else { base_instance };
+
+    // if multidraw indirect is not supported or if the indices are a slice that doesn't start
+    // at the beginning of their buffer, the commands are read back now and executed one by one
+    // later ; this must be done before the context is made current
+    let multidraw_elements_commands = match indices {
+        IndicesSource::MultidrawElement { ref commands, ref indices, ref draw_count, .. } => {
+            if (context.get_version() >= &Version(Api::Gl, 4, 3) ||
+                context.get_extensions().gl_arb_multi_draw_indirect ||
+                context.get_extensions().gl_ext_multi_draw_indirect) &&
+               indices.get_offset_bytes() == 0
+            {
+                None
+            } else {
+                let commands = unsafe { commands.read_if_supported::<DrawCommandIndices>() };
+                let mut commands = match commands {
+                    Some(c) => c,
+                    None => return Err(DrawError::MultidrawIndirectNotSupported),
+                };
+
+                // the number of commands to execute is stored in the first element of a buffer
+                if let &Some((ref count, max_count)) = draw_count {
+                    let count = match unsafe { count.read_if_supported::<u32>() } {
+                        Some(c) => c[0] as usize,
+                        None => return Err(DrawError::MultidrawIndirectNotSupported),
+                    };
+
+                    commands.truncate(cmp::min(count, max_count));
+                }
+
+                for command in commands.iter() {
+                    if command.base_vertex != 0 && !base_vertex_supported {
+                        return Err(DrawError::BaseVertexNotSupported);
+                    }
+                    if command.base_instance != 0 && !base_instance_supported {
+                        return Err(DrawError::BaseInstanceNotSupported);
+                    }
+                    if command.instance_count >= 2 && !instancing_supported {
+                        return Err(DrawError::InstancingNotSupported);
+                    }
+                    if command.first_index as usize + command.count as usize >
+                       indices.get_elements_count()
+                    {
+                        return Err(DrawError::DrawCommandIndicesOutOfRange);
+                    }
+                }
+
+                Some(commands)
+            }
+        },
+        _ => None
+    };
+
     // starting the state changes
     let mut ctxt = context.make_current();
 
@@ -91,6 +190,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         let ib_id = match indices {
             IndicesSource::IndexBuffer { ref buffer, .. } => buffer.get_buffer_id(&mut ctxt),
             IndicesSource::MultidrawArray { .. } => 0,
+            IndicesSource::MultidrawElement { ref indices, .. } => indices.get_buffer_id(&mut ctxt),
             IndicesSource::NoIndices { .. } => 0,
         };
 
@@ -98,25 +198,33 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         let mut binder = VertexAttributesSystem::start(&mut ctxt, program, ib_id);
         // number of vertices in the vertices sources, or `None` if there is a mismatch
         let mut vertices_count: Option<usize> = None;
-        // number of instances to draw
-        let mut instances_count: Option<usize> = None;
+        // range of instances counts that are compatible with all the per-instance sources,
+        // as `(min, max)` inclusive, or `None` if there is no per-instance source
+        let mut instances_range: Option<(usize, usize)> = None;
 
         for src in vertex_buffers.iter() {
             match src {
-                VerticesSource::VertexBuffer(buffer, format, per_instance) => {
+                VerticesSource::VertexBuffer(buffer, format, divisor) => {
                     // TODO: assert!(buffer.get_elements_size() == total_size(format));
 
                     if let Some(fence) = buffer.add_fence() {
                         fences.push(fence);
                     }
 
-                    binder = binder.add(&buffer, format, if per_instance { Some(1) } else { None });
+                    let offset = if divisor.is_some() { instances_offset } else { vertices_offset };
+                    let buffer = match buffer.slice(offset .. buffer.get_elements_count()) {
+                        Some(b) => b,
+                        None if divisor.is_some() => return Err(DrawError::InstancesCountMismatch),
+                        None => return Err(DrawError::BaseVertexOutOfRange),
+                    };
+
+                    binder = binder.add(&buffer, format, divisor);
                 },
                 _ => {}
             }
 
             match src {
-                VerticesSource::VertexBuffer(ref buffer, _, false) => {
+                VerticesSource::VertexBuffer(ref buffer, _, None) => {
                     if let Some(curr) = vertices_count {
                         if curr != buffer.get_elements_count() {
                             vertices_count = None;
@@ -126,14 +234,14 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                         vertices_count = Some(buffer.get_elements_count());
                     }
                 },
-                VerticesSource::VertexBuffer(ref buffer, _, true) => {
-                    if let Some(curr) = instances_count {
-                        if curr != buffer.get_elements_count() {
-                            return Err(DrawError::InstancesCountMismatch);
-                        }
-                    } else {
-                        instances_count = Some(buffer.get_elements_count());
+                VerticesSource::VertexBuffer(ref buffer, _, Some(divisor)) => {
+                    let len = buffer.get_elements_count();
+                    if base_instance > len {
+                        return Err(DrawError::InstancesCountMismatch);
                     }
+
+                    try!(restrict_instances_range(&mut instances_range, len - base_instance,
+                                                  divisor));
                 },
                 VerticesSource::Marker { len, per_instance } if !per_instance => {
                     if let Some(curr) = vertices_count {
@@ -146,18 +254,25 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     }
                 },
                 VerticesSource::Marker { len, per_instance } if per_instance => {
-                    if let Some(curr) = instances_count {
-                        if curr != len {
-                            return Err(DrawError::InstancesCountMismatch);
-                        }
-                    } else {
-                        instances_count = Some(len);
-                    }
+                    try!(restrict_instances_range(&mut instances_range, len, 1));
                 },
                 _ => ()
             }
         }
 
+        // number of instances to draw
+        let instances_count = match (draw_parameters.instances_count, instances_range) {
+            (Some(count), Some((_, max))) if count as usize > max => {
+                return Err(DrawError::InstancesCountMismatch);
+            },
+            (Some(count), _) => Some(count as usize),
+            (None, range) => range.map(|(_, max)| max),
+        };
+
+        if draw_parameters.instances_count.is_some() && !instancing_supported {
+            return Err(DrawError::InstancingNotSupported);
+        }
+
         binder.bind();
 
         (vertices_count, instances_count)
@@ -173,59 +288,8 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     // binding the program
     program.use_program(&mut ctxt);
 
-    // building the list of uniforms binders and the fences that must be fulfilled
-    // TODO: panic if uniforms of the program are not found in the parameter
-    {
-        let mut texture_bind_points = Bitsfield::new();
-        let mut buffer_bind_points = Bitsfield::new();
-
-        let mut visiting_result = Ok(());
-        uniforms.visit_values(|name, value| {
-            if visiting_result.is_err() { return; }
-
-            if let Some(uniform) = program.get_uniform(name) {
-                assert!(uniform.size.is_none(), "Uniform arrays not supported yet");
-
-                if !value.is_usable_with(&uniform.ty) {
-                    visiting_result = Err(DrawError::UniformTypeMismatch {
-                        name: name.to_string(),
-                        expected: uniform.ty,
-                    });
-                    return;
-                }
-
-                match bind_uniform(&mut ctxt, &mut context.get_samplers().borrow_mut(),
-                                   &value, program, uniform.location,
-                                   &mut texture_bind_points, name)
-                {
-                    Ok(_) => (),
-                    Err(e) => {
-                        visiting_result = Err(e);
-                        return;
-                    }
-                };
-
-            } else if let Some(block) = program.get_uniform_blocks().get(name) {
-                let fence = match bind_uniform_block(&mut ctxt, &value, block,
-                                                     program, &mut buffer_bind_points, name)
-                {
-                    Ok(f) => f,
-                    Err(e) => {
-                        visiting_result = Err(e);
-                        return;
-                    }
-                };
-
-                if let Some(fence) = fence {
-                    fences.push(fence);
-                }
-            }
-        });
-
-        if let Err(e) = visiting_result {
-            return Err(e);
-        }
-    }
+    // binding the uniforms and building the list of fences that must be fulfilled
+    try!(bind_uniforms(&mut ctxt, context, program, uniforms, &mut fences));
 
     // sync-ing draw_parameters
     unsafe {
@@ -243,6 +307,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                               dimensions);
         sync_rasterizer_discard(&mut ctxt, draw_parameters.draw_primitives);
         sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
+        sync_primitive_restart(&mut ctxt, primitive_restart_index_type);
         try!(sync_queries(&mut ctxt, draw_parameters.samples_passed_query,
                           draw_parameters.time_elapsed_query,
                           draw_parameters.primitives_generated_query,
@@ -280,13 +345,40 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     fences.push(fence);
                 }
 
+                let base_vertex = if base_vertex_supported { base_vertex } else { 0 };
+                let base_instance = if base_instance_supported { base_instance } else { 0 };
+
                 unsafe {
                     if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
-                                                      buffer.get_elements_count() as gl::types::GLsizei,
-                                                      data_type.to_glenum(),
-                                                      ptr as *const libc::c_void,
-                                                      instances_count as gl::types::GLsizei);
+                        if base_instance != 0 {
+                            ctxt.gl.DrawElementsInstancedBaseVertexBaseInstance(
+                                                primitives.to_glenum(),
+                                                buffer.get_elements_count() as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                instances_count as gl::types::GLsizei,
+                                                base_vertex as gl::types::GLint,
+                                                base_instance as gl::types::GLuint);
+                        } else if base_vertex != 0 {
+                            ctxt.gl.DrawElementsInstancedBaseVertex(primitives.to_glenum(),
+                                                buffer.get_elements_count() as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                instances_count as gl::types::GLsizei,
+                                                base_vertex as gl::types::GLint);
+                        } else {
+                            ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
+                                                buffer.get_elements_count() as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                instances_count as gl::types::GLsizei);
+                        }
+                    } else if base_vertex != 0 {
+                        ctxt.gl.DrawElementsBaseVertex(primitives.to_glenum(),
+                                                buffer.get_elements_count() as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                base_vertex as gl::types::GLint);
                     } else {
                         ctxt.gl.DrawElements(primitives.to_glenum(),
                                              buffer.get_elements_count() as gl::types::GLsizei,
@@ -296,7 +388,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
             },
 
-            &IndicesSource::MultidrawArray { ref buffer, primitives } => {
+            &IndicesSource::MultidrawArray { ref buffer, ref draw_count, primitives } => {
                 let ptr: *const u8 = ptr::null_mut();
                 let ptr = unsafe { ptr.offset(buffer.get_offset_bytes() as isize) };
 
@@ -306,9 +398,112 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
 
                 unsafe {
                     buffer.bind_to(&mut ctxt, BufferType::DrawIndirectBuffer);
-                    ctxt.gl.MultiDrawArraysIndirect(primitives.to_glenum(), ptr as *const _,
+
+                    if let &Some((ref count, max_count)) = draw_count {
+                        if let Some(fence) = count.add_fence() {
+                            fences.push(fence);
+                        }
+
+                        count.bind_to(&mut ctxt, BufferType::ParameterBuffer);
+                        ctxt.gl.MultiDrawArraysIndirectCountARB(primitives.to_glenum(),
+                                                    ptr as *const _,
+                                                    count.get_offset_bytes() as gl::types::GLintptr,
+                                                    max_count as gl::types::GLsizei, 0);
+                    } else {
+                        ctxt.gl.MultiDrawArraysIndirect(primitives.to_glenum(), ptr as *const _,
                                                     buffer.get_elements_count() as gl::types::GLsizei,
                                                     0);
+                    }
+                }
+            },
+
+            &IndicesSource::MultidrawElement { ref commands, ref indices, ref draw_count,
+                                               data_type, primitives } =>
+            {
+                if let Some(fence) = commands.add_fence() {
+                    fences.push(fence);
+                }
+
+                if let Some(fence) = indices.add_fence() {
+                    fences.push(fence);
+                }
+
+                if let Some(ref multidraw_elements_commands) = multidraw_elements_commands {
+                    for command in multidraw_elements_commands.iter() {
+                        if command.instance_count == 0 {
+                            continue;
+                        }
+
+                        // the `first_index` of the commands is relative to the start of the slice
+                        let ptr: *const u8 = ptr::null_mut();
+                        let ptr = unsafe {
+                            ptr.offset((indices.get_offset_bytes() +
+                                        command.first_index as usize * data_type.get_size())
+                                       as isize)
+                        };
+
+                        unsafe {
+                            if command.base_instance != 0 {
+                                ctxt.gl.DrawElementsInstancedBaseVertexBaseInstance(
+                                                primitives.to_glenum(),
+                                                command.count as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                command.instance_count as gl::types::GLsizei,
+                                                command.base_vertex as gl::types::GLint,
+                                                command.base_instance as gl::types::GLuint);
+                            } else if command.instance_count >= 2 && command.base_vertex != 0 {
+                                ctxt.gl.DrawElementsInstancedBaseVertex(primitives.to_glenum(),
+                                                command.count as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                command.instance_count as gl::types::GLsizei,
+                                                command.base_vertex as gl::types::GLint);
+                            } else if command.instance_count >= 2 {
+                                ctxt.gl.DrawElementsInstanced(primitives.to_glenum(),
+                                                command.count as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                command.instance_count as gl::types::GLsizei);
+                            } else if command.base_vertex != 0 {
+                                ctxt.gl.DrawElementsBaseVertex(primitives.to_glenum(),
+                                                command.count as gl::types::GLsizei,
+                                                data_type.to_glenum(),
+                                                ptr as *const libc::c_void,
+                                                command.base_vertex as gl::types::GLint);
+                            } else {
+                                ctxt.gl.DrawElements(primitives.to_glenum(),
+                                                     command.count as gl::types::GLsizei,
+                                                     data_type.to_glenum(),
+                                                     ptr as *const libc::c_void);
+                            }
+                        }
+                    }
+
+                } else {
+                    let ptr: *const u8 = ptr::null_mut();
+                    let ptr = unsafe { ptr.offset(commands.get_offset_bytes() as isize) };
+
+                    unsafe {
+                        commands.bind_to(&mut ctxt, BufferType::DrawIndirectBuffer);
+
+                        if let &Some((ref count, max_count)) = draw_count {
+                            if let Some(fence) = count.add_fence() {
+                                fences.push(fence);
+                            }
+
+                            count.bind_to(&mut ctxt, BufferType::ParameterBuffer);
+                            ctxt.gl.MultiDrawElementsIndirectCountARB(primitives.to_glenum(),
+                                                    data_type.to_glenum(), ptr as *const _,
+                                                    count.get_offset_bytes() as gl::types::GLintptr,
+                                                    max_count as gl::types::GLsizei, 0);
+                        } else {
+                            ctxt.gl.MultiDrawElementsIndirect(primitives.to_glenum(),
+                                                    data_type.to_glenum(), ptr as *const _,
+                                                    commands.get_elements_count()
+                                                        as gl::types::GLsizei, 0);
+                        }
+                    }
                 }
             },
 
@@ -318,14 +513,31 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     None => return Err(DrawError::VerticesSourcesLengthMismatch)
                 };
 
+                // `base_vertex` is guaranteed to be positive at this point
+                let first = base_vertex as usize;
+                if first > vertices_count {
+                    return Err(DrawError::BaseVertexOutOfRange);
+                }
+
+                let base_instance = if base_instance_supported { base_instance } else { 0 };
+
                 unsafe {
                     if let Some(instances_count) = instances_count {
-                        ctxt.gl.DrawArraysInstanced(primitives.to_glenum(), 0,
-                                                    vertices_count as gl::types::GLsizei,
+                        if base_instance != 0 {
+                            ctxt.gl.DrawArraysInstancedBaseInstance(primitives.to_glenum(),
+                                                    first as gl::types::GLint,
+                                                    (vertices_count - first) as gl::types::GLsizei,
+                                                    instances_count as gl::types::GLsizei,
+                                                    base_instance as gl::types::GLuint);
+                        } else {
+                            ctxt.gl.DrawArraysInstanced(primitives.to_glenum(),
+                                                    first as gl::types::GLint,
+                                                    (vertices_count - first) as gl::types::GLsizei,
                                                     instances_count as gl::types::GLsizei);
+                        }
                     } else {
-                        ctxt.gl.DrawArrays(primitives.to_glenum(), 0,
-                                           vertices_count as gl::types::GLsizei);
+                        ctxt.gl.DrawArrays(primitives.to_glenum(), first as gl::types::GLint,
+                                           (vertices_count - first) as gl::types::GLsizei);
                     }
                 }
             },
@@ -348,6 +560,93 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     Ok(())
 }
 
+/// Restricts the range of instances counts that are valid for all the per-instance sources
+/// encountered so far.
+///
+/// A source of `len` elements with a divisor of `divisor` can be used to draw between
+/// `(len - 1) * divisor + 1` and `len * divisor` instances.
+fn restrict_instances_range(range: &mut Option<(usize, usize)>, len: usize, divisor: u32)
+                            -> Result<(), DrawError>
+{
+    let divisor = divisor as usize;
+    let (min, max) = if len == 0 {
+        (0, 0)
+    } else {
+        ((len - 1) * divisor + 1, len * divisor)
+    };
+
+    let new_range = match *range {
+        Some((curr_min, curr_max)) => (if curr_min > min { curr_min } else { min },
+                                       if curr_max < max { curr_max } else { max }),
+        None => (min, max),
+    };
+
+    if new_range.0 > new_range.1 {
+        return Err(DrawError::InstancesCountMismatch);
+    }
+
+    *range = Some(new_range);
+    Ok(())
+}
+
+/// Binds the uniforms of a program, and adds to `fences` the fences that must be fulfilled
+/// once the command using them has been executed.
+pub fn bind_uniforms<'a, U>(ctxt: &mut context::CommandContext, context: &Context,
+                            program: &Program, uniforms: &'a U,
+                            fences: &mut Vec<&'a RefCell<Option<sync::LinearSyncFence>>>)
+                            -> Result<(), DrawError> where U: Uniforms
+{
+    // TODO: panic if uniforms of the program are not found in the parameter
+    let mut texture_bind_points = Bitsfield::new();
+    let mut image_bind_points = Bitsfield::new();
+    let mut buffer_bind_points = Bitsfield::new();
+
+    let mut visiting_result = Ok(());
+    uniforms.visit_values(|name, value| {
+        if visiting_result.is_err() { return; }
+
+        if let Some(uniform) = program.get_uniform(name) {
+            assert!(uniform.size.is_none(), "Uniform arrays not supported yet");
+
+            if !value.is_usable_with(&uniform.ty) {
+                visiting_result = Err(DrawError::UniformTypeMismatch {
+                    name: name.to_string(),
+                    expected: uniform.ty,
+                });
+                return;
+            }
+
+            match bind_uniform(ctxt, &mut context.get_samplers().borrow_mut(),
+                               &value, program, uniform.location,
+                               &mut texture_bind_points, &mut image_bind_points, name)
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    visiting_result = Err(e);
+                    return;
+                }
+            };
+
+        } else if let Some(block) = program.get_uniform_blocks().get(name) {
+            let fence = match bind_uniform_block(ctxt, &value, block,
+                                                 program, &mut buffer_bind_points, name)
+            {
+                Ok(f) => f,
+                Err(e) => {
+                    visiting_result = Err(e);
+                    return;
+                }
+            };
+
+            if let Some(fence) = fence {
+                fences.push(fence);
+            }
+        }
+    });
+
+    visiting_result
+}
+
 fn bind_uniform_block<'a>(ctxt: &mut context::CommandContext, value: &UniformValue<'a>,
                           block: &program::UniformBlock,
                           program: &Program, buffer_bind_points: &mut Bitsfield, name: &str)
@@ -383,8 +682,8 @@ fn bind_uniform_block<'a>(ctxt: &mut context::CommandContext, value: &UniformVal
 fn bind_uniform(ctxt: &mut context::CommandContext,
                 samplers: &mut HashMap<SamplerBehavior, SamplerObject>,
                 value: &UniformValue, program: &Program, location: gl::types::GLint,
-                texture_bind_points: &mut Bitsfield, name: &str)
-                -> Result<(), DrawError>
+                texture_bind_points: &mut Bitsfield, image_bind_points: &mut Bitsfield,
+                name: &str) -> Result<(), DrawError>
 {
     assert!(location >= 0);
 
@@ -486,6 +785,14 @@ fn bind_uniform(ctxt: &mut context::CommandContext,
             let texture = texture.get_id();
             bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D)
         },
+        UniformValue::StencilTexture2d(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D)
+        },
+        UniformValue::DepthStencilTexture2d(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D)
+        },
         UniformValue::Texture2dMultisample(texture, sampler) => {
             let texture = texture.get_id();
             bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D_MULTISAMPLE)
@@ -610,6 +917,75 @@ fn bind_uniform(ctxt: &mut context::CommandContext,
             let texture = texture.get_id();
             bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
+        UniformValue::Cubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::CompressedCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::SrgbCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::CompressedSrgbCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::IntegralCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::UnsignedCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::DepthCubemap(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::CubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::CompressedCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::SrgbCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::CompressedSrgbCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::IntegralCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::UnsignedCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::DepthCubemapArray(texture, sampler) => {
+            let texture = texture.get_id();
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::RectangleTexture(texture, sampler) => {
+            let texture = texture.get_id();
+            let sampler = sampler.map(rectangle_sampler);
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_RECTANGLE)
+        },
+        UniformValue::DepthRectangleTexture(texture, sampler) => {
+            let texture = texture.get_id();
+            let sampler = sampler.map(rectangle_sampler);
+            bind_texture_uniform(ctxt, samplers, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_RECTANGLE)
+        },
+        UniformValue::Image(unit) => {
+            bind_image_uniform(ctxt, &unit, location, program, image_bind_points)
+        },
     }
 }
 
@@ -622,13 +998,21 @@ fn bind_texture_uniform(mut ctxt: &mut context::CommandContext,
                         bind_point: gl::types::GLenum)
                         -> Result<(), DrawError>
 {
-    let sampler = if let Some(sampler) = sampler {
-        Some(try!(::sampler_object::get_sampler(ctxt, samplers, &sampler)))
-    } else {
-        None
-    };
+    if let Some(ref behavior) = sampler {
+        try!(::sampler_object::check_behavior(ctxt, behavior));
+    }
 
-    let sampler = sampler.unwrap_or(0);
+    // if sampler objects are not supported, the parameters are set on the texture instead
+    let sampler_objects = ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                          ctxt.extensions.gl_arb_sampler_objects;
+
+    let behavior = sampler;
+    let sampler = match behavior {
+        Some(ref behavior) if sampler_objects => {
+            try!(::sampler_object::get_sampler(ctxt, samplers, behavior))
+        },
+        _ => 0,
+    };
 
     // finding an appropriate texture unit
     let texture_unit =
@@ -689,6 +1073,132 @@ fn bind_texture_uniform(mut ctxt: &mut context::CommandContext,
         }
     }
 
+    // multisample textures don't have sampling parameters
+    if !sampler_objects && bind_point != gl::TEXTURE_2D_MULTISAMPLE &&
+       bind_point != gl::TEXTURE_2D_MULTISAMPLE_ARRAY
+    {
+        if ctxt.state.active_texture != texture_unit as gl::types::GLenum {
+            unsafe { ctxt.gl.ActiveTexture(texture_unit as gl::types::GLenum + gl::TEXTURE0) };
+            ctxt.state.active_texture = texture_unit as gl::types::GLenum;
+        }
+
+        // textures used without a sampler get back the parameters they were created with
+        ::sampler_object::set_texture_parameters(ctxt, texture, bind_point, behavior.as_ref());
+    }
+
+    Ok(())
+}
+
+/// Adjusts a sampler so that it can be used with a rectangle texture.
+///
+/// Rectangle textures don't have mipmaps and only support the clamping wrap functions. Using
+/// anything else makes the texture incomplete.
+fn rectangle_sampler(mut behavior: SamplerBehavior) -> SamplerBehavior {
+    fn wrap(function: SamplerWrapFunction) -> SamplerWrapFunction {
+        match function {
+            SamplerWrapFunction::Repeat | SamplerWrapFunction::Mirror |
+            SamplerWrapFunction::MirrorClamp => SamplerWrapFunction::Clamp,
+            f => f
+        }
+    }
+
+    behavior.wrap_function = (wrap(behavior.wrap_function.0), wrap(behavior.wrap_function.1),
+                              wrap(behavior.wrap_function.2));
+
+    behavior.minify_filter = match behavior.minify_filter {
+        MinifySamplerFilter::Nearest | MinifySamplerFilter::NearestMipmapNearest |
+        MinifySamplerFilter::NearestMipmapLinear => MinifySamplerFilter::Nearest,
+        MinifySamplerFilter::Linear | MinifySamplerFilter::LinearMipmapNearest |
+        MinifySamplerFilter::LinearMipmapLinear => MinifySamplerFilter::Linear,
+    };
+
+    behavior
+}
+
+fn bind_image_uniform(ctxt: &mut context::CommandContext, unit: &ImageUnit,
+                      location: gl::types::GLint, program: &Program,
+                      image_bind_points: &mut Bitsfield) -> Result<(), DrawError>
+{
+    let desired = ImageUnitState {
+        texture: unit.get_texture().get_id(),
+        level: unit.get_level() as gl::types::GLint,
+        layered: unit.get_layer().is_none(),
+        layer: unit.get_layer().unwrap_or(0) as gl::types::GLint,
+        access: unit.get_access().to_glenum(),
+        format: unit.get_format().to_glenum(),
+    };
+
+    if ctxt.capabilities.max_image_units == 0 {
+        return Err(DrawError::ImageUnitsNotSupported);
+    }
+
+    // binding a layer that doesn't exist is an OpenGL error
+    if let Some(layer) = unit.get_layer() {
+        let texture = unit.get_texture();
+        let layers = match texture.get_texture_type() {
+            TextureType::Texture1dArray | TextureType::Texture2dArray |
+            TextureType::Texture2dMultisampleArray => texture.get_array_size(),
+            TextureType::Texture3d => {
+                Some(cmp::max(1, texture.get_depth().unwrap_or(1) >> unit.get_level()))
+            },
+            TextureType::Cubemap => Some(6),
+            TextureType::CubemapArray => Some(6 * texture.get_array_size().unwrap_or(1)),
+            _ => None,
+        };
+
+        if let Some(layers) = layers {
+            if layer >= layers {
+                return Err(DrawError::ImageUnitLayerOutOfRange);
+            }
+        }
+    }
+
+    // finding an appropriate image unit
+    let image_unit =
+        ctxt.state.image_units
+            .iter().enumerate()
+            .find(|&(unit, content)| {
+                *content == desired && !image_bind_points.is_used(unit as u16)
+            })
+            .map(|(unit, _)| unit as u16)
+            .or_else(|| {
+                if ctxt.state.image_units.len() < ctxt.capabilities.max_image_units as usize {
+                    Some(ctxt.state.image_units.len() as u16)
+                } else {
+                    None
+                }
+            })
+            .or_else(|| image_bind_points.get_unused());
+
+    let image_unit = match image_unit {
+        Some(unit) if (unit as gl::types::GLint) < ctxt.capabilities.max_image_units => unit,
+        _ => return Err(DrawError::TooManyImageUnits),
+    };
+
+    image_bind_points.set_used(image_unit);
+
+    // updating the program to use the right unit
+    program.set_uniform(ctxt, location,
+                        &RawUniformValue::SignedInt(image_unit as gl::types::GLint));
+
+    // updating the state of the image unit
+    if ctxt.state.image_units.len() <= image_unit as usize {
+        for _ in (ctxt.state.image_units.len() .. image_unit as usize + 1) {
+            ctxt.state.image_units.push(Default::default());
+        }
+    }
+
+    if ctxt.state.image_units[image_unit as usize] != desired {
+        unsafe {
+            ctxt.gl.BindImageTexture(image_unit as gl::types::GLuint, desired.texture,
+                                     desired.level,
+                                     if desired.layered { gl::TRUE } else { gl::FALSE },
+                                     desired.layer, desired.access, desired.format);
+        }
+
+        ctxt.state.image_units[image_unit as usize] = desired;
+    }
+
     Ok(())
 }
 
@@ -1083,6 +1593,46 @@ fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives:
     }
 }
 
+unsafe fn sync_primitive_restart(ctxt: &mut context::CommandContext,
+                                 index_type: Option<index::IndexType>)
+{
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_arb_es3_compatibility
+    {
+        // the restart index is always the maximum value of the index type
+        if index_type.is_some() && !ctxt.state.enabled_primitive_restart_fixed_index {
+            ctxt.gl.Enable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            ctxt.state.enabled_primitive_restart_fixed_index = true;
+        } else if index_type.is_none() && ctxt.state.enabled_primitive_restart_fixed_index {
+            ctxt.gl.Disable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            ctxt.state.enabled_primitive_restart_fixed_index = false;
+        }
+
+    } else if let Some(index_type) = index_type {
+        debug_assert!(ctxt.version >= &Version(Api::Gl, 3, 1));
+
+        if !ctxt.state.enabled_primitive_restart {
+            ctxt.gl.Enable(gl::PRIMITIVE_RESTART);
+            ctxt.state.enabled_primitive_restart = true;
+        }
+
+        let value = match index_type {
+            index::IndexType::U8 => 0xff,
+            index::IndexType::U16 => 0xffff,
+            index::IndexType::U32 => 0xffffffff,
+        };
+
+        if ctxt.state.primitive_restart_index != value {
+            ctxt.gl.PrimitiveRestartIndex(value);
+            ctxt.state.primitive_restart_index = value;
+        }
+
+    } else if ctxt.state.enabled_primitive_restart {
+        ctxt.gl.Disable(gl::PRIMITIVE_RESTART);
+        ctxt.state.enabled_primitive_restart = false;
+    }
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
@@ -73,12 +73,13 @@ pub fn read_if_supported<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S,
 
     let pixels_to_read = rect.width * rect.height;
 
-    let (fbo, read_buffer) = match source {
+    // binding the framebuffer and adjusting `glReadBuffer`
+    match source {
         Source::Attachment(attachment, framebuffer_objects) => {
-            framebuffer_objects.get_framebuffer_for_reading(attachment, &mut ctxt)
+            framebuffer_objects.bind_framebuffer_for_reading(attachment, &mut ctxt);
         },
         Source::DefaultFramebuffer(read_buffer) => {
-            (0, read_buffer)
+            fbo::bind_default_framebuffer_for_reading(&mut ctxt, read_buffer);
         },
     };
 
@@ -87,14 +88,6 @@ pub fn read_if_supported<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S,
     let (format, gltype) = client_format_to_gl_enum(&<T as PixelValue>::get_format());
 
     unsafe {
-        // binding framebuffer
-        // FIXME: GLES2 only supports color attachment 0
-        fbo::bind_framebuffer(&mut ctxt, fbo, false, true);
-
-        // adjusting glReadBuffer
-        // FIXME: handle this in `fbo`
-        ctxt.gl.ReadBuffer(read_buffer);
-
         // reading
         match dest {
             Destination::Memory(dest) => {
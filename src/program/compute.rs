@@ -0,0 +1,148 @@
+use libc;
+
+use backend::Facade;
+use context::{self, Context};
+use buffer::BufferViewSlice;
+
+use ContextExt;
+use DrawError;
+use Handle;
+use GlObject;
+use Program;
+use ProgramCreationError;
+
+use ops;
+use program::program::build_compute_program;
+use program::{Uniform, UniformBlock};
+use uniforms::Uniforms;
+
+use std::collections::hash_map::{self, HashMap};
+use std::fmt;
+use std::rc::Rc;
+
+/// A program which contains only a compute shader.
+///
+/// Compute shaders are executed on the GPU independently of any drawing. They are run in *work
+/// groups*, whose size is declared in the source code with
+/// `layout(local_size_x = ..., local_size_y = ..., local_size_z = ...) in;`.
+///
+/// The results are usually written to images or to shader storage buffers. Call
+/// `memory_barrier` on the context before using them.
+pub struct ComputeShader {
+    context: Rc<Context>,
+    program: Program,
+    work_group_size: (u32, u32, u32),
+}
+
+impl ComputeShader {
+    /// Returns true if the backend supports compute shaders.
+    pub fn is_supported<F>(facade: &F) -> bool where F: Facade {
+        let context = facade.get_context();
+        context::is_compute_supported(context.get_version(), context.get_extensions())
+    }
+
+    /// Builds a new compute shader from GLSL source code.
+    ///
+    /// Returns `ShaderTypeNotSupported` if the backend doesn't support compute shaders.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// let shader = glium::program::ComputeShader::from_source(&display, "
+    ///     #version 430
+    ///
+    ///     layout(local_size_x = 64) in;
+    ///
+    ///     void main() {
+    ///     }
+    /// ");
+    /// ```
+    pub fn from_source<F>(facade: &F, src: &str) -> Result<ComputeShader, ProgramCreationError>
+                          where F: Facade
+    {
+        if !ComputeShader::is_supported(facade) {
+            return Err(ProgramCreationError::ShaderTypeNotSupported);
+        }
+
+        let (program, work_group_size) = try!(build_compute_program(facade, src));
+
+        Ok(ComputeShader {
+            context: facade.get_context().clone(),
+            program: program,
+            work_group_size: work_group_size,
+        })
+    }
+
+    /// Executes the compute shader with `x * y * z` work groups.
+    ///
+    /// Returns `DrawError::TooManyWorkGroups` if the number of work groups in a dimension
+    /// exceeds the maximum supported by the backend.
+    pub fn execute<U>(&self, uniforms: &U, x: u32, y: u32, z: u32) -> Result<(), DrawError>
+                      where U: Uniforms
+    {
+        ops::dispatch(&self.context, &self.program, uniforms, ops::WorkGroups::Direct(x, y, z))
+    }
+
+    /// Executes the compute shader with a number of work groups read by the GPU from the
+    /// first element of a buffer.
+    ///
+    /// The buffer must have been created with `BufferType::DispatchIndirectBuffer`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the buffer is empty.
+    pub fn execute_indirect<U>(&self, uniforms: &U, buffer: BufferViewSlice<ComputeCommand>)
+                               -> Result<(), DrawError> where U: Uniforms
+    {
+        assert!(buffer.len() >= 1);
+
+        ops::dispatch(&self.context, &self.program, uniforms,
+                      ops::WorkGroups::Indirect(buffer.as_slice_any()))
+    }
+
+    /// Returns the size of a work group, as declared in the source code.
+    pub fn get_work_group_size(&self) -> (u32, u32, u32) {
+        self.work_group_size
+    }
+
+    /// Returns informations about a uniform variable, if it exists.
+    pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
+        self.program.get_uniform(name)
+    }
+
+    /// Returns an iterator to the list of uniforms.
+    pub fn uniforms(&self) -> hash_map::Iter<String, Uniform> {
+        self.program.uniforms()
+    }
+
+    /// Returns a list of uniform blocks.
+    pub fn get_uniform_blocks(&self) -> &HashMap<String, UniformBlock> {
+        self.program.get_uniform_blocks()
+    }
+}
+
+impl fmt::Debug for ComputeShader {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        (format!("ComputeShader #{:?}", self.program.get_id())).fmt(formatter)
+    }
+}
+
+impl GlObject for ComputeShader {
+    type Id = Handle;
+    fn get_id(&self) -> Handle {
+        self.program.get_id()
+    }
+}
+
+/// Represents a compute command, as read by `execute_indirect`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ComputeCommand {
+    /// Number of work groups in the first dimension.
+    pub num_groups_x: libc::c_uint,
+    /// Number of work groups in the second dimension.
+    pub num_groups_y: libc::c_uint,
+    /// Number of work groups in the third dimension.
+    pub num_groups_z: libc::c_uint,
+}
@@ -1,9 +1,11 @@
 use std::sync::Mutex;
 
+pub use self::compute::{ComputeShader, ComputeCommand};
 pub use self::program::{Program, ProgramCreationError};
 pub use self::reflection::{Uniform, UniformBlock, UniformBlockMember, OutputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 
+mod compute;
 mod program;
 mod reflection;
 mod shader;
@@ -27,7 +27,7 @@ use program::reflection::{Uniform, UniformBlock, OutputPrimitives};
 use program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuffer};
 use program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
-use program::reflection::{reflect_tess_eval_output_type};
+use program::reflection::{reflect_tess_eval_output_type, reflect_compute_work_group_size};
 use program::shader::build_shader;
 
 use vertex::VertexFormat;
@@ -96,7 +96,7 @@ impl Error for ProgramCreationError {
 
 /// A combination of shaders linked together.
 pub struct Program {
-    context: Rc<Context>,
+    context: ProgramContext,
     id: Handle,
     uniform_values: UniformsStorage,
     uniforms: HashMap<String, Uniform>,
@@ -108,6 +108,25 @@ pub struct Program {
     has_tessellation_shaders: bool,
 }
 
+/// Context that a program belongs to.
+enum ProgramContext {
+    /// The program keeps its context alive, like every other object.
+    Shared(Rc<Context>),
+
+    /// The program is stored inside its context, which destroys it before being destroyed.
+    /// Keeping a `Rc` in this situation would prevent the context from ever being destroyed.
+    Owner(*const Context),
+}
+
+impl ProgramContext {
+    fn get(&self) -> &Context {
+        match *self {
+            ProgramContext::Shared(ref context) => &**context,
+            ProgramContext::Owner(context) => unsafe { &*context },
+        }
+    }
+}
+
 impl Program {
     /// Builds a new program.
     pub fn new<'a, F, I>(facade: &F, input: I) -> Result<Program, ProgramCreationError>
@@ -235,19 +254,8 @@ impl Program {
             let id = create_program(&mut ctxt);
 
             // attaching shaders
-            for sh in shaders_ids.iter() {
-                match (id, sh) {
-                    (Handle::Id(id), &Handle::Id(sh)) => {
-                        assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                                ctxt.version >= &Version(Api::GlEs, 2, 0));
-                        ctxt.gl.AttachShader(id, sh);
-                    },
-                    (Handle::Handle(id), &Handle::Handle(sh)) => {
-                        assert!(ctxt.extensions.gl_arb_shader_objects);
-                        ctxt.gl.AttachObjectARB(id, sh);
-                    },
-                    _ => unreachable!()
-                }
+            for &sh in shaders_ids.iter() {
+                attach_shader(&mut ctxt, id, sh);
             }
 
             // transform feedback varyings
@@ -287,27 +295,7 @@ impl Program {
                 }
             }
 
-            // linking
-            {
-                ctxt.report_debug_output_errors.set(false);
-
-                match id {
-                    Handle::Id(id) => {
-                        assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                                ctxt.version >= &Version(Api::GlEs, 2, 0));
-                        ctxt.gl.LinkProgram(id);
-                    },
-                    Handle::Handle(id) => {
-                        assert!(ctxt.extensions.gl_arb_shader_objects);
-                        ctxt.gl.LinkProgramARB(id);
-                    }
-                }
-
-                ctxt.report_debug_output_errors.set(true);
-            }
-
-            // checking for errors
-            try!(check_program_link_errors(&mut ctxt, id));
+            try!(link_program(&mut ctxt, id));
 
             id
         };
@@ -326,7 +314,7 @@ impl Program {
         };
 
         Ok(Program {
-            context: facade.get_context().clone(),
+            context: ProgramContext::Shared(facade.get_context().clone()),
             id: id,
             uniforms: uniforms,
             uniform_values: UniformsStorage::new(),
@@ -382,7 +370,7 @@ impl Program {
         };
 
         Ok(Program {
-            context: facade.get_context().clone(),
+            context: ProgramContext::Shared(facade.get_context().clone()),
             id: id,
             uniforms: uniforms,
             uniform_values: UniformsStorage::new(),
@@ -414,7 +402,7 @@ impl Program {
     /// getting or reloading the program's binary.
     pub fn get_binary_if_supported(&self) -> Option<Binary> {
         unsafe {
-            let ctxt = self.context.make_current();
+            let ctxt = self.context.get().make_current();
 
             if ctxt.version >= &Version(Api::Gl, 4, 1) ||
                ctxt.extensions.gl_arb_get_programy_binary
@@ -464,7 +452,7 @@ impl Program {
         // querying opengl
         let name_c = ffi::CString::new(name.as_bytes()).unwrap();
 
-        let ctxt = self.context.make_current();
+        let ctxt = self.context.get().make_current();
 
         let value = unsafe {
             match self.id {
@@ -632,11 +620,16 @@ impl ProgramExt for Program {
     {
         self.uniform_values.set_block_binding(ctxt, self.id, block_location, value);
     }
+
+    unsafe fn release_context(&mut self) {
+        let context = self.context.get() as *const Context;
+        self.context = ProgramContext::Owner(context);
+    }
 }
 
 impl Drop for Program {
     fn drop(&mut self) {
-        let mut ctxt = self.context.make_current();
+        let mut ctxt = self.context.get().make_current();
 
         // removing VAOs which contain this program
         VertexAttributesSystem::purge_program(&mut ctxt, self.id);
@@ -670,6 +663,50 @@ impl Drop for Program {
     }
 }
 
+/// Compiles and links a program made of a single compute shader.
+///
+/// Returns the program and the size of its work groups.
+pub fn build_compute_program<F>(facade: &F, compute_shader: &str)
+                                -> Result<(Program, (u32, u32, u32)), ProgramCreationError>
+                                where F: Facade
+{
+    let _lock = COMPILER_GLOBAL_LOCK.lock();
+
+    let shader = try!(build_shader(facade, gl::COMPUTE_SHADER, compute_shader));
+
+    let mut ctxt = facade.get_context().make_current();
+
+    let id = unsafe {
+        let id = create_program(&mut ctxt);
+        attach_shader(&mut ctxt, id, shader.get_id());
+        try!(link_program(&mut ctxt, id));
+        id
+    };
+
+    let (uniforms, blocks, work_group_size) = unsafe {
+        (
+            reflect_uniforms(&mut ctxt, id),
+            reflect_uniform_blocks(&mut ctxt, id),
+            reflect_compute_work_group_size(&mut ctxt, id),
+        )
+    };
+
+    let program = Program {
+        context: ProgramContext::Shared(facade.get_context().clone()),
+        id: id,
+        uniforms: uniforms,
+        uniform_values: UniformsStorage::new(),
+        uniform_blocks: blocks,
+        attributes: HashMap::new(),
+        frag_data_locations: RefCell::new(HashMap::new()),
+        tf_buffers: Vec::new(),
+        output_primitives: None,
+        has_tessellation_shaders: false,
+    };
+
+    Ok((program, work_group_size))
+}
+
 /// Builds an empty program from within the GL context.
 unsafe fn create_program(ctxt: &mut CommandContext) -> Handle {
     let id = if ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -689,6 +726,45 @@ unsafe fn create_program(ctxt: &mut CommandContext) -> Handle {
     id
 }
 
+/// Attaches a shader to a program from within the GL context.
+unsafe fn attach_shader(ctxt: &mut CommandContext, id: Handle, shader: Handle) {
+    match (id, shader) {
+        (Handle::Id(id), Handle::Id(sh)) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.AttachShader(id, sh);
+        },
+        (Handle::Handle(id), Handle::Handle(sh)) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.AttachObjectARB(id, sh);
+        },
+        _ => unreachable!()
+    }
+}
+
+/// Links a program from within the GL context and checks for errors.
+unsafe fn link_program(ctxt: &mut CommandContext, id: Handle)
+                       -> Result<(), ProgramCreationError>
+{
+    ctxt.report_debug_output_errors.set(false);
+
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.LinkProgram(id);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.LinkProgramARB(id);
+        }
+    }
+
+    ctxt.report_debug_output_errors.set(true);
+
+    check_program_link_errors(ctxt, id)
+}
+
 unsafe fn check_program_link_errors(ctxt: &mut CommandContext, id: Handle)
                                     -> Result<(), ProgramCreationError>
 {
@@ -606,6 +606,34 @@ pub unsafe fn reflect_tess_eval_output_type(ctxt: &mut CommandContext, program:
     }
 }
 
+/// Obtains the size of the work groups of a compute shader, as declared with
+/// `layout(local_size_x = ..., local_size_y = ..., local_size_z = ...) in;`.
+///
+/// # Unsafety
+///
+/// - `program` must be a valid handle to a program.
+/// - The program **must** contain a compute shader.
+pub unsafe fn reflect_compute_work_group_size(ctxt: &mut CommandContext, program: Handle)
+                                              -> (u32, u32, u32)
+{
+    let mut value: [gl::types::GLint; 3] = [0, 0, 0];
+
+    match program {
+        Handle::Id(program) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(program, gl::COMPUTE_WORK_GROUP_SIZE, value.as_mut_ptr());
+        },
+        Handle::Handle(program) => {
+            assert!(ctxt.extensions.gl_arb_vertex_shader);
+            ctxt.gl.GetObjectParameterivARB(program, gl::COMPUTE_WORK_GROUP_SIZE,
+                                            value.as_mut_ptr());
+        }
+    };
+
+    (value[0] as u32, value[1] as u32, value[2] as u32)
+}
+
 fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
     match ty {
         gl::FLOAT => UniformType::Float,
@@ -659,6 +687,8 @@ fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
         gl::SAMPLER_2D_MULTISAMPLE => UniformType::Sampler2dMultisample,
         gl::SAMPLER_2D_MULTISAMPLE_ARRAY => UniformType::Sampler2dMultisampleArray,
         gl::SAMPLER_CUBE_SHADOW => UniformType::SamplerCubeShadow,
+        gl::SAMPLER_CUBE_MAP_ARRAY => UniformType::SamplerCubeArray,
+        gl::SAMPLER_CUBE_MAP_ARRAY_SHADOW => UniformType::SamplerCubeArrayShadow,
         gl::SAMPLER_BUFFER => UniformType::SamplerBuffer,
         gl::SAMPLER_2D_RECT => UniformType::Sampler2dRect,
         gl::SAMPLER_2D_RECT_SHADOW => UniformType::Sampler2dRectShadow,
@@ -666,6 +696,7 @@ fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
         gl::INT_SAMPLER_2D => UniformType::ISampler2d,
         gl::INT_SAMPLER_3D => UniformType::ISampler3d,
         gl::INT_SAMPLER_CUBE => UniformType::ISamplerCube,
+        gl::INT_SAMPLER_CUBE_MAP_ARRAY => UniformType::ISamplerCubeArray,
         gl::INT_SAMPLER_1D_ARRAY => UniformType::ISampler1dArray,
         gl::INT_SAMPLER_2D_ARRAY => UniformType::ISampler2dArray,
         gl::INT_SAMPLER_2D_MULTISAMPLE => UniformType::ISampler2dMultisample,
@@ -676,6 +707,7 @@ fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
         gl::UNSIGNED_INT_SAMPLER_2D => UniformType::USampler2d,
         gl::UNSIGNED_INT_SAMPLER_3D => UniformType::USampler3d,
         gl::UNSIGNED_INT_SAMPLER_CUBE => UniformType::USamplerCube,
+        gl::UNSIGNED_INT_SAMPLER_CUBE_MAP_ARRAY => UniformType::USamplerCubeArray,
         gl::UNSIGNED_INT_SAMPLER_1D_ARRAY => UniformType::USampler2dArray,
         gl::UNSIGNED_INT_SAMPLER_2D_ARRAY => UniformType::USampler2dArray,
         gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE => UniformType::USampler2dMultisample,
@@ -1,11 +1,13 @@
 use DrawError;
 
-use uniforms::SamplerBehavior;
+use uniforms::{SamplerBehavior, SamplerWrapFunction, MinifySamplerFilter, MagnifySamplerFilter};
 
 use std::collections::HashMap;
+use std::mem;
 
 use gl;
 use context::CommandContext;
+use context::TextureParametersState;
 use version::Version;
 use version::Api;
 use GlObject;
@@ -25,34 +27,12 @@ impl SamplerObject {
                 ctxt.extensions.gl_arb_sampler_objects);
 
         let sampler = unsafe {
-            use std::mem;
             let mut sampler: gl::types::GLuint = mem::uninitialized();
             ctxt.gl.GenSamplers(1, &mut sampler);
             sampler
         };
 
-        unsafe {
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_S,
-                                      behavior.wrap_function.0.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_T,
-                                      behavior.wrap_function.1.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_R,
-                                      behavior.wrap_function.2.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_MIN_FILTER,
-                                      behavior.minify_filter.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_MAG_FILTER,
-                                      behavior.magnify_filter.to_glenum() as gl::types::GLint);
-
-            if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
-                let value = if behavior.max_anisotropy as f32 > max_value {
-                    max_value
-                } else {
-                    behavior.max_anisotropy as f32
-                };
-
-                ctxt.gl.SamplerParameterf(sampler, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
-            }
-        }
+        set_parameters(ctxt, ParametersTarget::Sampler(sampler), behavior);
 
         SamplerObject {
             id: sampler,
@@ -84,6 +64,210 @@ impl Drop for SamplerObject {
     }
 }
 
+/// Applies the parameters of a sampler behavior to `texture`, which must be currently bound to
+/// `bind_point` on the active texture unit.
+///
+/// Used when sampler objects are not supported by the backend. Passing `None` restores the
+/// parameters that the texture had before they were changed for the first time. The parameters
+/// of each texture are tracked in the state, so they are only set when they change.
+pub fn set_texture_parameters(ctxt: &mut CommandContext, texture: gl::types::GLuint,
+                              bind_point: gl::types::GLenum, behavior: Option<&SamplerBehavior>)
+{
+    let state = ctxt.state.texture_parameters.get(&texture).cloned();
+
+    match (behavior, state) {
+        (Some(behavior), Some(state)) => {
+            if state.current != *behavior {
+                set_parameters(ctxt, ParametersTarget::Texture(bind_point), behavior);
+                ctxt.state.texture_parameters.insert(texture, TextureParametersState {
+                    initial: state.initial,
+                    current: *behavior,
+                });
+            }
+        },
+
+        (Some(behavior), None) => {
+            let initial = get_initial_texture_parameters(ctxt, bind_point);
+            set_parameters(ctxt, ParametersTarget::Texture(bind_point), behavior);
+            ctxt.state.texture_parameters.insert(texture, TextureParametersState {
+                initial: initial,
+                current: *behavior,
+            });
+        },
+
+        (None, Some(state)) => {
+            set_parameters(ctxt, ParametersTarget::Texture(bind_point), &state.initial);
+            ctxt.state.texture_parameters.remove(&texture);
+        },
+
+        (None, None) => (),
+    }
+}
+
+/// Returns the parameters of the texture bound to `bind_point` on the active texture unit, which
+/// must not have been modified since its creation.
+fn get_initial_texture_parameters(ctxt: &mut CommandContext, bind_point: gl::types::GLenum)
+                                  -> SamplerBehavior
+{
+    // the minification filter depends on whether the texture has mipmaps
+    let minify_filter = unsafe {
+        let mut value: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexParameteriv(bind_point, gl::TEXTURE_MIN_FILTER, &mut value);
+
+        match value as gl::types::GLenum {
+            gl::NEAREST => MinifySamplerFilter::Nearest,
+            gl::LINEAR => MinifySamplerFilter::Linear,
+            gl::NEAREST_MIPMAP_NEAREST => MinifySamplerFilter::NearestMipmapNearest,
+            gl::LINEAR_MIPMAP_NEAREST => MinifySamplerFilter::LinearMipmapNearest,
+            gl::NEAREST_MIPMAP_LINEAR => MinifySamplerFilter::NearestMipmapLinear,
+            _ => MinifySamplerFilter::LinearMipmapLinear,
+        }
+    };
+
+    // the wrap functions depend on the type of texture, for example cubemaps and rectangle
+    // textures are created with clamping while the other textures repeat
+    let wrap_s = get_texture_wrap_function(ctxt, bind_point, gl::TEXTURE_WRAP_S);
+    let wrap_t = get_texture_wrap_function(ctxt, bind_point, gl::TEXTURE_WRAP_T);
+
+    // `GL_TEXTURE_WRAP_R` doesn't exist in OpenGL ES 2
+    let wrap_r = if ctxt.version >= &Version(Api::Gl, 1, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 3, 0)
+    {
+        get_texture_wrap_function(ctxt, bind_point, gl::TEXTURE_WRAP_R)
+    } else {
+        wrap_s
+    };
+
+    SamplerBehavior {
+        wrap_function: (wrap_s, wrap_t, wrap_r),
+        minify_filter: minify_filter,
+        magnify_filter: MagnifySamplerFilter::Linear,
+        .. Default::default()
+    }
+}
+
+/// Returns the wrap function of one coordinate of the texture bound to `bind_point` on the
+/// active texture unit.
+fn get_texture_wrap_function(ctxt: &mut CommandContext, bind_point: gl::types::GLenum,
+                             pname: gl::types::GLenum) -> SamplerWrapFunction
+{
+    let value = unsafe {
+        let mut value: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexParameteriv(bind_point, pname, &mut value);
+        value
+    };
+
+    match value as gl::types::GLenum {
+        gl::MIRRORED_REPEAT => SamplerWrapFunction::Mirror,
+        gl::CLAMP_TO_EDGE => SamplerWrapFunction::Clamp,
+        gl::MIRROR_CLAMP_TO_EDGE => SamplerWrapFunction::MirrorClamp,
+        gl::CLAMP_TO_BORDER => SamplerWrapFunction::BorderClamp,
+        _ => SamplerWrapFunction::Repeat,
+    }
+}
+
+/// Object whose sampling parameters are modified by `set_parameters`.
+#[derive(Copy, Clone)]
+enum ParametersTarget {
+    /// A sampler object.
+    Sampler(gl::types::GLuint),
+    /// The texture bound to this bind point of the active texture unit.
+    Texture(gl::types::GLenum),
+}
+
+fn set_parameters(ctxt: &mut CommandContext, target: ParametersTarget,
+                  behavior: &SamplerBehavior)
+{
+    let set_i = |ctxt: &mut CommandContext, name: gl::types::GLenum, value: gl::types::GLint| {
+        unsafe {
+            match target {
+                ParametersTarget::Sampler(id) => ctxt.gl.SamplerParameteri(id, name, value),
+                ParametersTarget::Texture(bind) => ctxt.gl.TexParameteri(bind, name, value),
+            }
+        }
+    };
+
+    let set_f = |ctxt: &mut CommandContext, name: gl::types::GLenum, value: f32| {
+        unsafe {
+            match target {
+                ParametersTarget::Sampler(id) => ctxt.gl.SamplerParameterf(id, name, value),
+                ParametersTarget::Texture(bind) => ctxt.gl.TexParameterf(bind, name, value),
+            }
+        }
+    };
+
+    set_i(ctxt, gl::TEXTURE_WRAP_S, behavior.wrap_function.0.to_glenum() as gl::types::GLint);
+    set_i(ctxt, gl::TEXTURE_WRAP_T, behavior.wrap_function.1.to_glenum() as gl::types::GLint);
+    set_i(ctxt, gl::TEXTURE_MIN_FILTER, behavior.minify_filter.to_glenum() as gl::types::GLint);
+    set_i(ctxt, gl::TEXTURE_MAG_FILTER, behavior.magnify_filter.to_glenum() as gl::types::GLint);
+
+    if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
+        let value = if behavior.max_anisotropy as f32 > max_value {
+            max_value
+        } else {
+            behavior.max_anisotropy as f32
+        };
+
+        set_f(ctxt, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
+    }
+
+    // the border color is only available on OpenGL ES 3.2
+    if ctxt.version >= &Version(Api::Gl, 1, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2) {
+        let (r, g, b, a) = behavior.border_color;
+        let color = [r, g, b, a];
+
+        unsafe {
+            match target {
+                ParametersTarget::Sampler(id) => {
+                    ctxt.gl.SamplerParameterfv(id, gl::TEXTURE_BORDER_COLOR, color.as_ptr())
+                },
+                ParametersTarget::Texture(bind) => {
+                    ctxt.gl.TexParameterfv(bind, gl::TEXTURE_BORDER_COLOR, color.as_ptr())
+                },
+            }
+        }
+    }
+
+    // the LOD bias is not available on OpenGL ES
+    if ctxt.version >= &Version(Api::Gl, 1, 0) {
+        set_f(ctxt, gl::TEXTURE_LOD_BIAS, behavior.lod_bias);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        set_i(ctxt, gl::TEXTURE_WRAP_R,
+              behavior.wrap_function.2.to_glenum() as gl::types::GLint);
+        set_f(ctxt, gl::TEXTURE_MIN_LOD, behavior.min_lod);
+        set_f(ctxt, gl::TEXTURE_MAX_LOD, behavior.max_lod);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 1, 4) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        if let Some(comparison) = behavior.depth_texture_comparison {
+            set_i(ctxt, gl::TEXTURE_COMPARE_MODE,
+                  gl::COMPARE_REF_TO_TEXTURE as gl::types::GLint);
+            set_i(ctxt, gl::TEXTURE_COMPARE_FUNC, comparison.to_glenum() as gl::types::GLint);
+        } else {
+            set_i(ctxt, gl::TEXTURE_COMPARE_MODE, gl::NONE as gl::types::GLint);
+        }
+    }
+}
+
+/// Returns an error if the backend doesn't support a parameter of the sampler behavior.
+pub fn check_behavior(ctxt: &CommandContext, behavior: &SamplerBehavior) -> Result<(), DrawError> {
+    let (s, t, r) = behavior.wrap_function;
+    let border_clamp = s == SamplerWrapFunction::BorderClamp ||
+                       t == SamplerWrapFunction::BorderClamp ||
+                       r == SamplerWrapFunction::BorderClamp;
+
+    // `GL_CLAMP_TO_BORDER` is only available on OpenGL ES 3.2
+    if border_clamp && !(ctxt.version >= &Version(Api::Gl, 1, 3)) &&
+       !(ctxt.version >= &Version(Api::GlEs, 3, 2))
+    {
+        return Err(DrawError::BorderClampNotSupported);
+    }
+
+    Ok(())
+}
+
 /// Returns the sampler corresponding to the given behavior, or a draw error if
 /// samplers are not supported.
 pub fn get_sampler(ctxt: &mut CommandContext,
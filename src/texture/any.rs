@@ -3,24 +3,38 @@ use GlObject;
 
 use backend::Facade;
 use version::Version;
-use context::Context;
+use context::{Context, CommandContext};
 use ContextExt;
 use TextureExt;
 use version::Api;
 use Rect;
+use BlitTarget;
 
 use pixel_buffer::PixelBuffer;
 use image_format::{self, TextureFormatRequest};
-use texture::Texture2dDataSink;
+use texture::{PixelValue, Texture2dDataSink};
+use texture::convert;
 use texture::{TextureFormat, ClientFormat};
 use texture::{TextureCreationError, TextureMaybeSupportedCreationError};
-use texture::{get_format, InternalFormat};
+use texture::{get_format, InternalFormat, MipmapsOption};
+use texture::{ComponentSwizzle, SwizzleNotSupportedError, TextureViewCreationError};
+use texture::{DepthStencilTextureMode, StencilTexturingNotSupportedError};
+use texture::{MipmapsGenerationNotSupportedError, ResolveError};
+#[cfg(feature = "image")]
+use texture::{InternalFormatType, TextureImage};
+
+#[cfg(feature = "image")]
+use image;
 
 use libc;
+use std::cmp;
 use std::fmt;
 use std::mem;
+use std::ops::Range;
 use std::ptr;
+use std::slice;
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::rc::Rc;
 
 use ops;
@@ -38,8 +52,14 @@ pub struct TextureAny {
     depth: Option<u32>,
     array_size: Option<u32>,
 
+    /// Number of samples for multisample textures, or `None` for other textures
+    samples: Option<u32>,
+
     /// Number of mipmap levels (`1` means just the main texture, `0` is not valid)
     levels: u32,
+
+    /// Component returned when sampling a depth-stencil texture, as currently set in OpenGL
+    depth_stencil_texture_mode: Cell<DepthStencilTextureMode>,
 }
 
 /// Represents a specific mipmap of a texture.
@@ -48,7 +68,8 @@ pub struct TextureAnyMipmap<'a> {
     /// The texture.
     texture: &'a TextureAny,
 
-    /// Layer for array textures, or 0 for other textures.
+    /// Layer for array textures, or 0 for other textures. For cubemaps, this is
+    /// `6 * cube + face`.
     layer: u32,
 
     /// Mipmap level.
@@ -66,11 +87,14 @@ pub enum TextureType {
     Texture2dMultisample,
     Texture2dMultisampleArray,
     Texture3d,
+    Cubemap,
+    CubemapArray,
+    Rectangle,
 }
 
 /// Builds a new texture.
 pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
-                             data: Option<(ClientFormat, Cow<'a, [P]>)>, generate_mipmaps: bool,
+                             data: Option<(ClientFormat, Cow<'a, [P]>)>, mipmaps: MipmapsOption,
                              width: u32, height: Option<u32>, depth: Option<u32>,
                              array_size: Option<u32>, samples: Option<u32>)
                              -> Result<TextureAny, TextureMaybeSupportedCreationError>
@@ -118,40 +142,23 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         (TextureType::Texture3d, gl::TEXTURE_3D)
     };
 
-    let generate_mipmaps = generate_mipmaps && match format {
-        TextureFormatRequest::AnyFloatingPoint |
-        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) |
-        TextureFormatRequest::AnyIntegral |
-        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
-        TextureFormatRequest::AnyUnsigned |
-        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => true,
-        _ => false,
-    };
-
-    let texture_levels = if generate_mipmaps {
-        let max = ::std::cmp::max(width, ::std::cmp::max(height.unwrap_or(1),
-                             depth.unwrap_or(1))) as f32;
-
-        match max {
-            0.0 => 1,
-            a => 1 + a.log2() as gl::types::GLsizei
-        }
-
+    let (texture_levels, generate_mipmaps) = if samples.is_some() {
+        (1, false)
     } else {
-        1
+        let max = cmp::max(width, cmp::max(height.unwrap_or(1), depth.unwrap_or(1)));
+        get_mipmaps_levels(facade.get_context(), mipmaps, format, max)
     };
 
+    // the data is converted on the CPU if the backend doesn't accept its client format
+    let data = data.as_ref().map(|&(client_format, ref data)| {
+        get_upload_data(facade.get_context(), format, client_format, data)
+    });
+
     let (teximg_internal_format, storage_internal_format) =
         try!(image_format::format_request_to_glenum(facade.get_context(), data.as_ref().map(|&(c, _)| c), format));
 
-    let (client_format, client_type) = match (&data, format) {
-        (&Some((client_format, _)), f) => image_format::client_format_to_glenum(facade.get_context(), client_format, f),
-        (&None, TextureFormatRequest::AnyDepth) => (gl::DEPTH_COMPONENT, gl::FLOAT),
-        (&None, TextureFormatRequest::Specific(TextureFormat::DepthFormat(_))) => (gl::DEPTH_COMPONENT, gl::FLOAT),
-        (&None, TextureFormatRequest::AnyDepthStencil) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
-        (&None, TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_))) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
-        (&None, _) => (gl::RGBA, gl::UNSIGNED_BYTE),
-    };
+    let (client_format, client_type) = get_client_format(facade.get_context(),
+                                                         data.as_ref().map(|&(c, _)| c), format);
 
     let mut ctxt = facade.get_context().make_current();
 
@@ -190,7 +197,7 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
             ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
         }
         ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
-        if generate_mipmaps {
+        if texture_levels >= 2 {
             ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
                                   gl::LINEAR_MIPMAP_LINEAR as i32);
         } else {
@@ -198,11 +205,9 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
                                   gl::LINEAR as i32);
         }
 
-        if !generate_mipmaps && (ctxt.version >= &Version(Api::Gl, 1, 2) ||
-                                 ctxt.version >= &Version(Api::GlEs, 3, 0))
-        {
+        if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
             ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_BASE_LEVEL, 0);
-            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL, 0);
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL, texture_levels - 1);
         }
 
         if texture_type == gl::TEXTURE_3D || texture_type == gl::TEXTURE_2D_ARRAY {
@@ -237,6 +242,23 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
                 ctxt.gl.TexImage3D(texture_type, 0, teximg_internal_format as i32, width,
                                    height, depth, 0, client_format as u32, client_type,
                                    data_raw);
+
+                // allocating the mipmaps that are not generated, the layers of arrays are
+                // not divided
+                if !generate_mipmaps {
+                    for level in (1 .. texture_levels) {
+                        let level_depth = if texture_type == gl::TEXTURE_3D {
+                            cmp::max(1, depth >> level)
+                        } else {
+                            depth
+                        };
+
+                        ctxt.gl.TexImage3D(texture_type, level, teximg_internal_format as i32,
+                                           cmp::max(1, width >> level),
+                                           cmp::max(1, height >> level), level_depth, 0,
+                                           client_format as u32, client_type, ptr::null());
+                    }
+                }
             }
 
         } else if texture_type == gl::TEXTURE_2D || texture_type == gl::TEXTURE_1D_ARRAY {
@@ -265,6 +287,21 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
             } else {
                 ctxt.gl.TexImage2D(texture_type, 0, teximg_internal_format as i32, width,
                                    height, 0, client_format as u32, client_type, data_raw);
+
+                // allocating the mipmaps that are not generated
+                if !generate_mipmaps {
+                    for level in (1 .. texture_levels) {
+                        let level_height = if texture_type == gl::TEXTURE_2D {
+                            cmp::max(1, height >> level)
+                        } else {
+                            height
+                        };
+
+                        ctxt.gl.TexImage2D(texture_type, level, teximg_internal_format as i32,
+                                           cmp::max(1, width >> level), level_height, 0,
+                                           client_format as u32, client_type, ptr::null());
+                    }
+                }
             }
 
         } else if texture_type == gl::TEXTURE_2D_MULTISAMPLE {
@@ -348,6 +385,15 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
             } else {
                 ctxt.gl.TexImage1D(texture_type, 0, teximg_internal_format as i32, width,
                                    0, client_format as u32, client_type, data_raw);
+
+                // allocating the mipmaps that are not generated
+                if !generate_mipmaps {
+                    for level in (1 .. texture_levels) {
+                        ctxt.gl.TexImage1D(texture_type, level, teximg_internal_format as i32,
+                                           cmp::max(1, width >> level), 0,
+                                           client_format as u32, client_type, ptr::null());
+                    }
+                }
             }
 
         } else {
@@ -356,20 +402,19 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
 
         // only generate mipmaps for color textures
         if generate_mipmaps {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) ||
-               ctxt.version >= &Version(Api::GlEs, 2, 0)
-            {
-                ctxt.gl.GenerateMipmap(texture_type);
-            } else if ctxt.extensions.gl_ext_framebuffer_object {
-                ctxt.gl.GenerateMipmapEXT(texture_type);
-            } else {
-                unreachable!();
-            }
+            generate_bound_mipmaps(&mut ctxt, texture_type);
         }
 
         id
     };
 
+    // the implementation is free to use more samples than requested
+    let samples = samples.map(|_| unsafe {
+        let mut value: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(texture_type, 0, gl::TEXTURE_SAMPLES, &mut value);
+        value as u32
+    });
+
     Ok(TextureAny {
         context: facade.get_context().clone(),
         id: id,
@@ -379,52 +424,71 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         height: height,
         depth: depth,
         array_size: array_size,
+        samples: samples,
         ty: stored_ty,
         levels: texture_levels as u32,
+        depth_stencil_texture_mode: Cell::new(DepthStencilTextureMode::Depth),
     })
 }
 
-impl<'a> TextureAnyMipmap<'a> {
-    /// Returns the texture.
-    pub fn get_texture(&self) -> &'a TextureAny {
-        self.texture
+/// Builds a new rectangle texture.
+///
+/// Rectangle textures can't have mipmaps, so `mipmaps` is ignored.
+pub fn new_rectangle<'a, F, P>(facade: &F, format: TextureFormatRequest,
+                               data: Option<(ClientFormat, Cow<'a, [P]>)>, _: MipmapsOption,
+                               width: u32, height: u32)
+                               -> Result<TextureAny, TextureMaybeSupportedCreationError>
+                               where P: Send + Clone + 'a, F: Facade
+{
+    if let Some((client_format, ref data)) = data {
+        if width as usize * height as usize * client_format.get_size() !=
+            data.len() * mem::size_of::<P>()
+        {
+            panic!("Texture data size mismatch");
+        }
     }
 
-    /// Returns the level of the texture.
-    pub fn get_level(&self) -> u32 {
-        self.level
+    {
+        let context = facade.get_context();
+        if !(context.get_version() >= &Version(Api::Gl, 3, 1)) &&
+           !context.get_extensions().gl_arb_texture_rectangle
+        {
+            return Err(TextureMaybeSupportedCreationError::NotSupported);
+        }
     }
 
-    /// Returns the layer of the texture.
-    pub fn get_layer(&self) -> u32 {
-        self.layer
-    }
-}
+    // the data is converted on the CPU if the backend doesn't accept its client format
+    let data = data.as_ref().map(|&(client_format, ref data)| {
+        get_upload_data(facade.get_context(), format, client_format, data)
+    });
 
-/// Changes some parts of the texture.
-pub fn upload_texture<'a, P>(tex: &TextureAny, x_offset: u32, y_offset: u32, z_offset: u32,
-                             (format, data): (ClientFormat, Cow<'a, [P]>), width: u32,
-                             height: Option<u32>, depth: Option<u32>, level: u32,
-                             regen_mipmaps: bool)
-                             where P: Send + Copy + Clone + 'a
-{
-    let id = tex.id;
-    let bind_point = tex.bind_point;
-    let regen_mipmaps = regen_mipmaps && tex.levels >= 2;
+    let (teximg_internal_format, storage_internal_format) =
+        try!(image_format::format_request_to_glenum(facade.get_context(),
+                                                    data.as_ref().map(|&(c, _)| c), format));
 
-    assert!(x_offset <= tex.width);
-    assert!(y_offset <= tex.height.unwrap_or(1));
-    assert!(z_offset <= tex.depth.unwrap_or(1));
-    assert!(x_offset + width <= tex.width);
-    assert!(y_offset + height.unwrap_or(1) <= tex.height.unwrap_or(1));
-    assert!(z_offset + depth.unwrap_or(1) <= tex.depth.unwrap_or(1));
+    let (client_format, client_type) = get_client_format(facade.get_context(),
+                                                         data.as_ref().map(|&(c, _)| c), format);
 
-    let (client_format, client_type) = image_format::client_format_to_glenum(&tex.context, format,
-                                                                             tex.requested_format);
+    let mut ctxt = facade.get_context().make_current();
 
-    let mut ctxt = tex.context.make_current();
+    let id = unsafe {
+        let data = data;
+        let mut data_raw = if let Some((_, ref data)) = data {
+            data.as_ptr() as *const libc::c_void
+        } else {
+            ptr::null()
+        };
+
+        let width = match width as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
+
+        let height = match height as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-    unsafe {
         if ctxt.state.pixel_store_unpack_alignment != 1 {
             ctxt.state.pixel_store_unpack_alignment = 1;
             ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
@@ -435,166 +499,1931 @@ pub fn upload_texture<'a, P>(tex: &TextureAny, x_offset: u32, y_offset: u32, z_o
             ctxt.gl.BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
         }
 
+        let id: gl::types::GLuint = mem::uninitialized();
+        ctxt.gl.GenTextures(1, mem::transmute(&id));
+
         {
-            ctxt.gl.BindTexture(bind_point, id);
+            ctxt.gl.BindTexture(gl::TEXTURE_RECTANGLE, id);
             let act = ctxt.state.active_texture as usize;
             ctxt.state.texture_units[act].texture = id;
         }
 
-        if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY {
-            unimplemented!();
+        // rectangle textures only support clamping and non-mipmapped filtering
+        ctxt.gl.TexParameteri(gl::TEXTURE_RECTANGLE, gl::TEXTURE_WRAP_S,
+                              gl::CLAMP_TO_EDGE as i32);
+        ctxt.gl.TexParameteri(gl::TEXTURE_RECTANGLE, gl::TEXTURE_WRAP_T,
+                              gl::CLAMP_TO_EDGE as i32);
+        ctxt.gl.TexParameteri(gl::TEXTURE_RECTANGLE, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        ctxt.gl.TexParameteri(gl::TEXTURE_RECTANGLE, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
 
-        } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY {
-            assert!(z_offset == 0);
-            ctxt.gl.TexSubImage2D(bind_point, level as gl::types::GLint,
-                                  x_offset as gl::types::GLint,
-                                  y_offset as gl::types::GLint,
-                                  width as gl::types::GLsizei,
-                                  height.unwrap_or(1) as gl::types::GLsizei,
-                                  client_format, client_type,
-                                  data.as_ptr() as *const libc::c_void);
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) ||
+                                                 ctxt.extensions.gl_arb_texture_storage)
+        {
+            ctxt.gl.TexStorage2D(gl::TEXTURE_RECTANGLE, 1,
+                                 storage_internal_format.unwrap() as gl::types::GLenum,
+                                 width, height);
 
-        } else {
-            assert!(z_offset == 0);
-            assert!(y_offset == 0);
+            if !data_raw.is_null() {
+                ctxt.gl.TexSubImage2D(gl::TEXTURE_RECTANGLE, 0, 0, 0, width, height,
+                                      client_format, client_type, data_raw);
+            }
 
-            unimplemented!();
+        } else {
+            ctxt.gl.TexImage2D(gl::TEXTURE_RECTANGLE, 0, teximg_internal_format as i32, width,
+                               height, 0, client_format as u32, client_type, data_raw);
         }
 
-        // regenerate mipmaps if there are some
-        if regen_mipmaps {
-            if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                ctxt.gl.GenerateMipmap(bind_point);
-            } else {
-                ctxt.gl.GenerateMipmapEXT(bind_point);
-            }
-        }
-    }
-}
+        id
+    };
 
-/// Returns the `Context` associated with this texture.
-pub fn get_context(tex: &TextureAny) -> &Rc<Context> {
-    &tex.context
+    Ok(TextureAny {
+        context: facade.get_context().clone(),
+        id: id,
+        requested_format: format,
+        bind_point: gl::TEXTURE_RECTANGLE,
+        width: width,
+        height: Some(height),
+        depth: None,
+        array_size: None,
+        samples: None,
+        ty: TextureType::Rectangle,
+        levels: 1,
+        depth_stencil_texture_mode: Cell::new(DepthStencilTextureMode::Depth),
+    })
 }
 
-/// Returns the bind point of this texture.
+/// Builds a new 2D texture, or an array of 2D textures if `array_size` is `Some`, from data that
+/// is already compressed.
 ///
-/// The returned GLenum is guaranteed to be supported by the context.
-pub fn get_bind_point(tex: &TextureAny) -> gl::types::GLenum {
-    tex.bind_point
-}
+/// `levels` contains the compressed blocks of each mipmap, starting with the main level. For
+/// arrays, each element contains the layers of the mipmap one after another.
+pub fn new_compressed_texture<F>(facade: &F, format: TextureFormat, levels: &[&[u8]],
+                                 width: u32, height: u32, array_size: Option<u32>)
+                                 -> Result<TextureAny, TextureMaybeSupportedCreationError>
+                                 where F: Facade
+{
+    let (block_width, block_height, block_size) = image_format::get_compressed_block_size(format)
+                                                      .expect("The format is not compressed");
 
-impl TextureAny {
-    /// UNSTABLE. Reads the content of a mipmap level of the texture.
-    // TODO: this function only works for level 0 right now
-    //       width/height need adjustements
-    pub fn read<T>(&self, level: u32) -> T
-                   where T: Texture2dDataSink<(u8, u8, u8, u8)>
-            // TODO: remove Clone for P
-    {
-        assert_eq!(level, 0);   // TODO:
+    assert!(levels.len() >= 1);
+    for (level, data) in levels.iter().enumerate() {
+        let level_width = cmp::max(1, width >> level);
+        let level_height = cmp::max(1, height >> level);
+        let blocks = ((level_width + block_width - 1) / block_width) as usize *
+                     ((level_height + block_height - 1) / block_height) as usize;
 
-        let attachment = fbo::Attachment::TextureLayer {
-            texture: self,
-            layer: 0,
-            level: 0,
-        };
+        if blocks * block_size * array_size.unwrap_or(1) as usize != data.len() {
+            panic!("Texture data size mismatch");
+        }
+    }
 
-        let rect = Rect {
-            bottom: 0,
-            left: 0,
-            width: self.width,
-            height: self.height.unwrap_or(1),
-        };
+    {
+        let context = facade.get_context();
 
-        let mut ctxt = self.context.make_current();
+        if !(context.get_version() >= &Version(Api::Gl, 1, 3)) &&
+           !(context.get_version() >= &Version(Api::GlEs, 2, 0))
+        {
+            return Err(TextureMaybeSupportedCreationError::NotSupported);
+        }
 
-        let mut data = Vec::with_capacity(0);
-        ops::read(&mut ctxt, ops::Source::Attachment(&attachment, &self.context.get_framebuffer_objects()), &rect, &mut data);
-        T::from_raw(Cow::Owned(data), self.width, self.height.unwrap_or(1))
+        if array_size.is_some() && !(context.get_version() >= &Version(Api::Gl, 3, 0)) &&
+           !(context.get_version() >= &Version(Api::GlEs, 3, 0)) &&
+           !context.get_extensions().gl_ext_texture_array
+        {
+            return Err(TextureMaybeSupportedCreationError::NotSupported);
+        }
     }
 
-    /// UNSTABLE. Reads the content of a mipmap level of the texture to a pixel buffer.
-    // TODO: this function only works for level 0 right now
-    //       width/height need adjustements
-    pub fn read_to_pixel_buffer(&self, level: u32) -> PixelBuffer<(u8, u8, u8, u8)> {
-        assert_eq!(level, 0);   // TODO:
+    let format_request = TextureFormatRequest::Specific(format);
+    let (internal_format, _) = try!(image_format::format_request_to_glenum(facade.get_context(),
+                                                                            None,
+                                                                            format_request));
 
-        let size = self.width as usize * self.height.unwrap_or(1) as usize * 4;
+    let (stored_ty, texture_type) = if array_size.is_some() {
+        (TextureType::Texture2dArray, gl::TEXTURE_2D_ARRAY)
+    } else {
+        (TextureType::Texture2d, gl::TEXTURE_2D)
+    };
 
-        let attachment = fbo::Attachment::TextureLayer {
-            texture: self,
-            layer: 0,
-            level: 0,
-        };
+    let mut ctxt = facade.get_context().make_current();
 
-        let rect = Rect {
-            bottom: 0,
-            left: 0,
-            width: self.width,
-            height: self.height.unwrap_or(1),
-        };
+    let id = unsafe {
+        if ctxt.state.pixel_unpack_buffer_binding != 0 {
+            ctxt.state.pixel_unpack_buffer_binding = 0;
+            ctxt.gl.BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+        }
 
-        let pb = PixelBuffer::new_empty(&self.context, size);
+        let id: gl::types::GLuint = mem::uninitialized();
+        ctxt.gl.GenTextures(1, mem::transmute(&id));
 
-        let mut ctxt = self.context.make_current();
-        ops::read(&mut ctxt, ops::Source::Attachment(&attachment, &self.context.get_framebuffer_objects()), &rect, &pb);
-        pb
-    }
+        {
+            ctxt.gl.BindTexture(texture_type, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
 
-    /// UNSTABLE. Returns the `Context` associated with this texture.
-    pub fn get_context(&self) -> &Rc<Context> {
-        &self.context
-    }
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+        if array_size.is_some() {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
+        }
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        if levels.len() >= 2 {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
+                                  gl::LINEAR_MIPMAP_LINEAR as i32);
+        } else {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
+                                  gl::LINEAR as i32);
+        }
 
-    /// Returns the width of the texture.
-    pub fn get_width(&self) -> u32 {
-        self.width
-    }
+        if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_BASE_LEVEL, 0);
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL,
+                                  levels.len() as gl::types::GLint - 1);
+        }
 
-    /// Returns the height of the texture.
-    pub fn get_height(&self) -> Option<u32> {
-        self.height.clone()
-    }
+        for (level, data) in levels.iter().enumerate() {
+            let level_width = cmp::max(1, width >> level) as gl::types::GLsizei;
+            let level_height = cmp::max(1, height >> level) as gl::types::GLsizei;
 
-    /// Returns the depth of the texture.
-    pub fn get_depth(&self) -> Option<u32> {
-        self.depth.clone()
-    }
+            if let Some(array_size) = array_size {
+                ctxt.gl.CompressedTexImage3D(texture_type, level as gl::types::GLint,
+                                             internal_format, level_width, level_height,
+                                             array_size as gl::types::GLsizei, 0,
+                                             data.len() as gl::types::GLsizei,
+                                             data.as_ptr() as *const libc::c_void);
+            } else {
+                ctxt.gl.CompressedTexImage2D(texture_type, level as gl::types::GLint,
+                                             internal_format, level_width, level_height, 0,
+                                             data.len() as gl::types::GLsizei,
+                                             data.as_ptr() as *const libc::c_void);
+            }
+        }
 
-    /// Returns the array size of the texture.
-    pub fn get_array_size(&self) -> Option<u32> {
-        self.array_size.clone()
-    }
+        id
+    };
 
-    /// Returns the number of mipmap levels of the texture.
-    pub fn get_mipmap_levels(&self) -> u32 {
-        self.levels
+    Ok(TextureAny {
+        context: facade.get_context().clone(),
+        id: id,
+        requested_format: format_request,
+        bind_point: texture_type,
+        width: width,
+        height: Some(height),
+        depth: None,
+        array_size: array_size,
+        samples: None,
+        ty: stored_ty,
+        levels: levels.len() as u32,
+        depth_stencil_texture_mode: Cell::new(DepthStencilTextureMode::Depth),
+    })
+}
+
+/// Builds a new cubemap, or an array of cubemaps if `array_size` is `Some`.
+///
+/// The data must contain the six faces of each cubemap one after another, in the order of
+/// `CubeLayer`.
+pub fn new_cubemap<'a, F, P>(facade: &F, format: TextureFormatRequest,
+                             data: Option<(ClientFormat, Cow<'a, [P]>)>, mipmaps: MipmapsOption,
+                             dimension: u32, array_size: Option<u32>)
+                             -> Result<TextureAny, TextureMaybeSupportedCreationError>
+                             where P: Send + Clone + 'a, F: Facade
+{
+    let faces = 6 * array_size.unwrap_or(1);
+
+    if let Some((client_format, ref data)) = data {
+        if dimension as usize * dimension as usize * faces as usize * client_format.get_size() !=
+            data.len() * mem::size_of::<P>()
+        {
+            panic!("Texture data size mismatch");
+        }
     }
 
-    /// Returns the type of the texture (1D, 2D, 3D, etc.).
-    pub fn get_texture_type(&self) -> TextureType {
-        self.ty
+    if array_size.is_some() &&
+        !(facade.get_context().get_version() >= &Version(Api::Gl, 4, 0)) &&
+        !facade.get_context().get_extensions().gl_arb_texture_cube_map_array
+    {
+        return Err(TextureMaybeSupportedCreationError::NotSupported);
     }
 
-    /// Determines the internal format of this texture.
-    ///
-    /// Returns `None` if the backend doesn't allow querying the actual format.
-    pub fn get_internal_format_if_supported(&self) -> Option<InternalFormat> {
-        // TODO: cache this value in the texture
-        let mut ctxt = self.context.make_current();
-        get_format::get_format_if_supported(&mut ctxt, self)
+    // checking non-power-of-two
+    if facade.get_context().get_version() < &Version(Api::Gl, 2, 0) &&
+        !facade.get_context().get_extensions().gl_arb_texture_non_power_of_two &&
+        !dimension.is_power_of_two()
+    {
+        let ce = TextureCreationError::DimensionsNotSupported;
+        return Err(TextureMaybeSupportedCreationError::CreationError(ce));
     }
 
-    /// Returns a structure that represents a specific mipmap of the texture.
-    ///
-    /// Returns `None` if out of range.
-    pub fn mipmap(&self, layer: u32, level: u32) -> Option<TextureAnyMipmap> {
-        if layer >= self.array_size.unwrap_or(1) {
-            return None;
-        }
+    let (stored_ty, texture_type) = match array_size {
+        None => (TextureType::Cubemap, gl::TEXTURE_CUBE_MAP),
+        Some(_) => (TextureType::CubemapArray, gl::TEXTURE_CUBE_MAP_ARRAY),
+    };
 
-        if level >= self.levels {
+    let (texture_levels, generate_mipmaps) = get_mipmaps_levels(facade.get_context(), mipmaps,
+                                                                format, dimension);
+
+    // the data is converted on the CPU if the backend doesn't accept its client format
+    let data = data.as_ref().map(|&(client_format, ref data)| {
+        get_upload_data(facade.get_context(), format, client_format, data)
+    });
+
+    let (teximg_internal_format, storage_internal_format) =
+        try!(image_format::format_request_to_glenum(facade.get_context(),
+                                                    data.as_ref().map(|&(c, _)| c), format));
+
+    let (client_format, client_type) = get_client_format(facade.get_context(),
+                                                         data.as_ref().map(|&(c, _)| c), format);
+
+    let mut ctxt = facade.get_context().make_current();
+
+    let id = unsafe {
+        let data = data;
+        let (mut data_raw, face_bytes) = if let Some((_, ref data)) = data {
+            (data.as_ptr(), data.len() / faces as usize)
+        } else {
+            (ptr::null(), 0)
+        };
+
+        let size = match dimension as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
+
+        if ctxt.state.pixel_store_unpack_alignment != 1 {
+            ctxt.state.pixel_store_unpack_alignment = 1;
+            ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+        }
+
+        if ctxt.state.pixel_unpack_buffer_binding != 0 {
+            ctxt.state.pixel_unpack_buffer_binding = 0;
+            ctxt.gl.BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+        }
+
+        let id: gl::types::GLuint = mem::uninitialized();
+        ctxt.gl.GenTextures(1, mem::transmute(&id));
+
+        {
+            ctxt.gl.BindTexture(texture_type, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
+
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_WRAP_R, gl::CLAMP_TO_EDGE as i32);
+        ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        if texture_levels >= 2 {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
+                                  gl::LINEAR_MIPMAP_LINEAR as i32);
+        } else {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MIN_FILTER,
+                                  gl::LINEAR as i32);
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_BASE_LEVEL, 0);
+            ctxt.gl.TexParameteri(texture_type, gl::TEXTURE_MAX_LEVEL, texture_levels - 1);
+        }
+
+        let use_storage = storage_internal_format.is_some() &&
+                          (ctxt.version >= &Version(Api::Gl, 4, 2) ||
+                           ctxt.extensions.gl_arb_texture_storage);
+
+        if texture_type == gl::TEXTURE_CUBE_MAP_ARRAY {
+            // the faces of all the cubemaps are stored as the layers of a 2D array
+            let data_raw = data_raw as *const libc::c_void;
+            let faces = faces as gl::types::GLsizei;
+
+            if use_storage {
+                ctxt.gl.TexStorage3D(texture_type, texture_levels,
+                                     storage_internal_format.unwrap() as gl::types::GLenum,
+                                     size, size, faces);
+
+                if !data_raw.is_null() {
+                    ctxt.gl.TexSubImage3D(texture_type, 0, 0, 0, 0, size, size, faces,
+                                          client_format, client_type, data_raw);
+                }
+
+            } else {
+                ctxt.gl.TexImage3D(texture_type, 0, teximg_internal_format as i32, size, size,
+                                   faces, 0, client_format as u32, client_type, data_raw);
+
+                // allocating the mipmaps that are not generated
+                if !generate_mipmaps {
+                    for level in (1 .. texture_levels) {
+                        let level_size = cmp::max(1, size >> level);
+                        ctxt.gl.TexImage3D(texture_type, level, teximg_internal_format as i32,
+                                           level_size, level_size, faces, 0,
+                                           client_format as u32, client_type, ptr::null());
+                    }
+                }
+            }
+
+        } else {
+            if use_storage {
+                ctxt.gl.TexStorage2D(texture_type, texture_levels,
+                                     storage_internal_format.unwrap() as gl::types::GLenum,
+                                     size, size);
+            }
+
+            // each face has its own target
+            for face in 0 .. 6 {
+                let face_data = if data_raw.is_null() {
+                    ptr::null()
+                } else {
+                    data_raw.offset((face * face_bytes) as isize) as *const libc::c_void
+                };
+
+                let target = gl::TEXTURE_CUBE_MAP_POSITIVE_X + face as gl::types::GLenum;
+
+                if use_storage {
+                    if !face_data.is_null() {
+                        ctxt.gl.TexSubImage2D(target, 0, 0, 0, size, size, client_format,
+                                              client_type, face_data);
+                    }
+
+                } else {
+                    ctxt.gl.TexImage2D(target, 0, teximg_internal_format as i32, size, size,
+                                       0, client_format as u32, client_type, face_data);
+
+                    // allocating the mipmaps that are not generated
+                    if !generate_mipmaps {
+                        for level in (1 .. texture_levels) {
+                            let level_size = cmp::max(1, size >> level);
+                            ctxt.gl.TexImage2D(target, level, teximg_internal_format as i32,
+                                               level_size, level_size, 0, client_format as u32,
+                                               client_type, ptr::null());
+                        }
+                    }
+                }
+            }
+        }
+
+        if generate_mipmaps {
+            generate_bound_mipmaps(&mut ctxt, texture_type);
+        }
+
+        id
+    };
+
+    Ok(TextureAny {
+        context: facade.get_context().clone(),
+        id: id,
+        requested_format: format,
+        bind_point: texture_type,
+        width: dimension,
+        height: Some(dimension),
+        depth: None,
+        array_size: array_size,
+        samples: None,
+        ty: stored_ty,
+        levels: texture_levels as u32,
+        depth_stencil_texture_mode: Cell::new(DepthStencilTextureMode::Depth),
+    })
+}
+
+/// Builds a new texture that shares the storage of `tex`, interpreting its content with another
+/// format and another texture type.
+///
+/// `levels` and `layers` are the ranges of mipmaps and layers of `tex` that are part of the
+/// view. For cubemaps, the layers are `6 * cube + face`.
+///
+/// ## Panic
+///
+/// Panics if the ranges are empty or out of range, or if the number of layers doesn't match
+/// the type of the view.
+pub fn new_view(tex: &TextureAny, format: TextureFormat, ty: TextureType, levels: Range<u32>,
+                layers: Range<u32>) -> Result<TextureAny, TextureViewCreationError>
+{
+    let source_layers = match tex.ty {
+        TextureType::Cubemap | TextureType::CubemapArray => 6 * tex.array_size.unwrap_or(1),
+        _ => tex.array_size.unwrap_or(1),
+    };
+
+    assert!(levels.start < levels.end && levels.end <= tex.levels);
+    assert!(layers.start < layers.end && layers.end <= source_layers);
+
+    let num_layers = layers.end - layers.start;
+    match ty {
+        TextureType::Texture1d | TextureType::Texture2d | TextureType::Texture3d |
+        TextureType::Texture2dMultisample | TextureType::Rectangle => assert!(num_layers == 1),
+        TextureType::Cubemap => assert!(num_layers == 6),
+        TextureType::CubemapArray => assert!(num_layers % 6 == 0),
+        TextureType::Texture1dArray | TextureType::Texture2dArray |
+        TextureType::Texture2dMultisampleArray => (),
+    };
+
+    // checking whether the source texture can be viewed as this type of texture
+    let compatible_type = match (tex.ty, ty) {
+        (TextureType::Texture1d, TextureType::Texture1d) |
+        (TextureType::Texture1d, TextureType::Texture1dArray) |
+        (TextureType::Texture1dArray, TextureType::Texture1d) |
+        (TextureType::Texture1dArray, TextureType::Texture1dArray) |
+        (TextureType::Texture2d, TextureType::Texture2d) |
+        (TextureType::Texture2d, TextureType::Texture2dArray) |
+        (TextureType::Texture3d, TextureType::Texture3d) |
+        (TextureType::Rectangle, TextureType::Rectangle) |
+        (TextureType::Texture2dMultisample, TextureType::Texture2dMultisample) |
+        (TextureType::Texture2dMultisample, TextureType::Texture2dMultisampleArray) |
+        (TextureType::Texture2dMultisampleArray, TextureType::Texture2dMultisample) |
+        (TextureType::Texture2dMultisampleArray, TextureType::Texture2dMultisampleArray) => true,
+
+        (TextureType::Texture2dArray, _) | (TextureType::Cubemap, _) |
+        (TextureType::CubemapArray, _) => {
+            match ty {
+                TextureType::Texture2d | TextureType::Texture2dArray | TextureType::Cubemap |
+                TextureType::CubemapArray => true,
+                _ => false
+            }
+        },
+
+        _ => false
+    };
+
+    if !compatible_type {
+        return Err(TextureViewCreationError::IncompatibleTextureType);
+    }
+
+    {
+        let context = &tex.context;
+        if !(context.get_version() >= &Version(Api::Gl, 4, 3)) &&
+           !context.get_extensions().gl_arb_texture_view
+        {
+            return Err(TextureViewCreationError::NotSupported);
+        }
+    }
+
+    // views require a sized format
+    let view_format = TextureFormatRequest::Specific(format);
+    let view_format = match image_format::format_request_to_glenum(&tex.context, None,
+                                                                   view_format)
+    {
+        Ok((_, Some(f))) => f,
+        Ok((_, None)) => return Err(TextureViewCreationError::IncompatibleFormat),
+        Err(_) => return Err(TextureViewCreationError::NotSupported),
+    };
+
+    let bind_point = match ty {
+        TextureType::Texture1d => gl::TEXTURE_1D,
+        TextureType::Texture1dArray => gl::TEXTURE_1D_ARRAY,
+        TextureType::Texture2d => gl::TEXTURE_2D,
+        TextureType::Texture2dArray => gl::TEXTURE_2D_ARRAY,
+        TextureType::Texture2dMultisample => gl::TEXTURE_2D_MULTISAMPLE,
+        TextureType::Texture2dMultisampleArray => gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
+        TextureType::Texture3d => gl::TEXTURE_3D,
+        TextureType::Cubemap => gl::TEXTURE_CUBE_MAP,
+        TextureType::CubemapArray => gl::TEXTURE_CUBE_MAP_ARRAY,
+        TextureType::Rectangle => gl::TEXTURE_RECTANGLE,
+    };
+
+    let mut ctxt = tex.context.make_current();
+
+    let id = unsafe {
+        {
+            ctxt.gl.BindTexture(tex.bind_point, tex.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = tex.id;
+        }
+
+        // only textures created with `glTexStorage*` can be viewed
+        let mut immutable: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexParameteriv(tex.bind_point, gl::TEXTURE_IMMUTABLE_FORMAT, &mut immutable);
+        if immutable == 0 {
+            return Err(TextureViewCreationError::NotSupported);
+        }
+
+        let query_target = match tex.ty {
+            TextureType::Cubemap => gl::TEXTURE_CUBE_MAP_POSITIVE_X,
+            _ => tex.bind_point,
+        };
+
+        let mut source_format: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(query_target, levels.start as gl::types::GLint,
+                                       gl::TEXTURE_INTERNAL_FORMAT, &mut source_format);
+        let source_format = source_format as gl::types::GLenum;
+
+        let compatible_format = source_format == view_format ||
+                                (image_format::get_view_class(source_format).is_some() &&
+                                 image_format::get_view_class(source_format) ==
+                                 image_format::get_view_class(view_format));
+
+        if !compatible_format {
+            return Err(TextureViewCreationError::IncompatibleFormat);
+        }
+
+        // the name must not have been bound before calling `glTextureView`
+        let mut id: gl::types::GLuint = mem::uninitialized();
+        ctxt.gl.GenTextures(1, &mut id);
+        ctxt.gl.TextureView(id, bind_point, tex.id, view_format, levels.start,
+                            levels.end - levels.start, layers.start, num_layers);
+        id
+    };
+
+    let width = cmp::max(1, tex.width >> levels.start);
+    let height = match ty {
+        TextureType::Texture1d | TextureType::Texture1dArray => None,
+        _ => tex.height.map(|h| cmp::max(1, h >> levels.start)),
+    };
+    let depth = match ty {
+        TextureType::Texture3d => tex.depth.map(|d| cmp::max(1, d >> levels.start)),
+        _ => None,
+    };
+    let array_size = match ty {
+        TextureType::Texture1dArray | TextureType::Texture2dArray |
+        TextureType::Texture2dMultisampleArray => Some(num_layers),
+        TextureType::CubemapArray => Some(num_layers / 6),
+        _ => None,
+    };
+
+    Ok(TextureAny {
+        context: tex.context.clone(),
+        id: id,
+        requested_format: TextureFormatRequest::Specific(format),
+        bind_point: bind_point,
+        width: width,
+        height: height,
+        depth: depth,
+        array_size: array_size,
+        samples: tex.samples,
+        ty: ty,
+        levels: levels.end - levels.start,
+        depth_stencil_texture_mode: Cell::new(DepthStencilTextureMode::Depth),
+    })
+}
+
+/// Resolves the main level of a multisample texture into the main level of a regular texture.
+///
+/// The samples of each pixel are averaged. For integral, depth and stencil formats, the
+/// implementation picks the value of one of the samples instead. The two textures must have
+/// the same internal format.
+///
+/// ## Panic
+///
+/// Panics if `source` is not a multisample texture, if `target` is a multisample texture, or
+/// if the dimensions of the two textures don't match.
+pub fn resolve_multisample(source: &TextureAny, target: &TextureAny) -> Result<(), ResolveError> {
+    assert!(source.samples.is_some());
+    assert!(target.samples.is_none());
+    assert!(source.width == target.width && source.height == target.height);
+
+    // blitting between different formats is an error when resolving
+    {
+        let mut ctxt = source.context.make_current();
+        if get_internal_format_glenum(&mut ctxt, source) !=
+           get_internal_format_glenum(&mut ctxt, target)
+        {
+            return Err(ResolveError::IncompatibleFormat);
+        }
+    }
+
+    let (source_attachments, mask) = main_level_attachments(source);
+    let (target_attachments, _) = main_level_attachments(target);
+    let source_attachments = match source_attachments.validate() {
+        Ok(attachments) => attachments,
+        Err(_) => return Err(ResolveError::FramebufferNotSupported),
+    };
+    let target_attachments = match target_attachments.validate() {
+        Ok(attachments) => attachments,
+        Err(_) => return Err(ResolveError::FramebufferNotSupported),
+    };
+
+    let height = source.height.unwrap_or(1);
+
+    let source_rect = Rect {
+        left: 0,
+        bottom: 0,
+        width: source.width,
+        height: height,
+    };
+
+    let target_rect = BlitTarget {
+        left: 0,
+        bottom: 0,
+        width: source.width as i32,
+        height: height as i32,
+    };
+
+    // multisample resolves require the `NEAREST` filter for non-color buffers, and the filter
+    // has no effect for color buffers since the dimensions are the same
+    ops::blit(&source.context, Some(&source_attachments), Some(&target_attachments), mask,
+              &source_rect, &target_rect, gl::NEAREST);
+
+    Ok(())
+}
+
+/// Returns the internal format of the main level of a texture, as chosen by the driver.
+fn get_internal_format_glenum(ctxt: &mut CommandContext, tex: &TextureAny) -> gl::types::GLenum {
+    unsafe {
+        {
+            ctxt.gl.BindTexture(tex.bind_point, tex.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = tex.id;
+        }
+
+        let mut internal_format: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(tex.bind_point, 0, gl::TEXTURE_INTERNAL_FORMAT,
+                                       &mut internal_format);
+        internal_format as gl::types::GLenum
+    }
+}
+
+/// Returns the attachments that bind the main level of a texture (or its first layer for
+/// arrays) to a framebuffer, and the buffers that these attachments correspond to.
+pub fn main_level_attachments(tex: &TextureAny)
+                              -> (fbo::FramebufferAttachments, gl::types::GLbitfield)
+{
+    let attachment = if tex.array_size.is_some() {
+        fbo::Attachment::TextureLayer { texture: tex, layer: 0, level: 0 }
+    } else {
+        fbo::Attachment::Texture { texture: tex, level: 0 }
+    };
+
+    let (colors, depth_stencil, mask) = match tex.requested_format {
+        TextureFormatRequest::AnyDepth |
+        TextureFormatRequest::Specific(TextureFormat::DepthFormat(_)) => {
+            (Vec::new(), fbo::FramebufferDepthStencilAttachments::DepthAttachment(attachment),
+             gl::DEPTH_BUFFER_BIT)
+        },
+        TextureFormatRequest::AnyStencil |
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) => {
+            (Vec::new(), fbo::FramebufferDepthStencilAttachments::StencilAttachment(attachment),
+             gl::STENCIL_BUFFER_BIT)
+        },
+        TextureFormatRequest::AnyDepthStencil |
+        TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_)) => {
+            (Vec::new(),
+             fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(attachment),
+             gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT)
+        },
+        _ => {
+            (vec![(0, attachment)], fbo::FramebufferDepthStencilAttachments::None,
+             gl::COLOR_BUFFER_BIT)
+        },
+    };
+
+    let attachments = fbo::FramebufferAttachments {
+        colors: colors,
+        depth_stencil: depth_stencil,
+    };
+
+    (attachments, mask)
+}
+
+/// Reads a mipmap level of a texture into an image, with the pixel type that best matches the
+/// internal format of the texture.
+///
+/// Depth textures are read as `f32` luminance, and integral and unsigned textures as `i32` and
+/// `u32`. If the internal format can't be queried, color textures are read as 8-bits RGBA.
+///
+/// Returns `None` if the backend doesn't support reading the texture, and for stencil and
+/// depth-stencil textures.
+#[cfg(feature = "image")]
+pub fn read_image(tex: &TextureAny, level: u32) -> Option<TextureImage> {
+    enum Precision { U8, U16, F32, I32, U32 }
+
+    // number of components, types of the components and largest number of bits per component
+    let internal_format = tex.get_internal_format_if_supported().map(|format| match format {
+        InternalFormat::OneComponent { ty1, bits1 } => (1, vec![ty1], bits1),
+        InternalFormat::TwoComponents { ty1, bits1, ty2, bits2 } => {
+            (2, vec![ty1, ty2], cmp::max(bits1, bits2))
+        },
+        InternalFormat::ThreeComponents { ty1, bits1, ty2, bits2, ty3, bits3 } => {
+            (3, vec![ty1, ty2, ty3], cmp::max(bits1, cmp::max(bits2, bits3)))
+        },
+        InternalFormat::FourComponents { ty1, bits1, ty2, bits2, ty3, bits3, ty4, bits4 } => {
+            (4, vec![ty1, ty2, ty3, ty4],
+             cmp::max(cmp::max(bits1, bits2), cmp::max(bits3, bits4)))
+        },
+    });
+
+    let components = internal_format.as_ref().map(|&(c, _, _)| c).unwrap_or(4);
+
+    let precision = match tex.requested_format {
+        TextureFormatRequest::AnyDepth |
+        TextureFormatRequest::Specific(TextureFormat::DepthFormat(_)) => {
+            return download_image(tex, level).map(TextureImage::LumaF32);
+        },
+
+        TextureFormatRequest::AnyStencil | TextureFormatRequest::AnyDepthStencil |
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) |
+        TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_)) => return None,
+
+        TextureFormatRequest::AnyIntegral |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) => Precision::I32,
+
+        TextureFormatRequest::AnyUnsigned |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => Precision::U32,
+
+        // half-floats are also read as `f32`
+        _ => match internal_format {
+            Some((_, ref types, _)) if types.iter().any(|&t| t == InternalFormatType::Float) => {
+                Precision::F32
+            },
+            Some((_, _, bits)) if bits > 8 => Precision::U16,
+            _ => Precision::U8,
+        },
+    };
+
+    match (components, precision) {
+        (1, Precision::U8) => download_image(tex, level).map(TextureImage::Luma8),
+        (2, Precision::U8) => download_image(tex, level).map(TextureImage::LumaA8),
+        (3, Precision::U8) => download_image(tex, level).map(TextureImage::Rgb8),
+        (_, Precision::U8) => download_image(tex, level).map(TextureImage::Rgba8),
+        (1, Precision::U16) => download_image(tex, level).map(TextureImage::Luma16),
+        (2, Precision::U16) => download_image(tex, level).map(TextureImage::LumaA16),
+        (3, Precision::U16) => download_image(tex, level).map(TextureImage::Rgb16),
+        (_, Precision::U16) => download_image(tex, level).map(TextureImage::Rgba16),
+        (1, Precision::F32) => download_image(tex, level).map(TextureImage::LumaF32),
+        (2, Precision::F32) => download_image(tex, level).map(TextureImage::LumaAF32),
+        (3, Precision::F32) => download_image(tex, level).map(TextureImage::RgbF32),
+        (_, Precision::F32) => download_image(tex, level).map(TextureImage::RgbaF32),
+        (1, Precision::I32) => download_image(tex, level).map(TextureImage::LumaI32),
+        (2, Precision::I32) => download_image(tex, level).map(TextureImage::LumaAI32),
+        (3, Precision::I32) => download_image(tex, level).map(TextureImage::RgbI32),
+        (_, Precision::I32) => download_image(tex, level).map(TextureImage::RgbaI32),
+        (1, Precision::U32) => download_image(tex, level).map(TextureImage::LumaU32),
+        (2, Precision::U32) => download_image(tex, level).map(TextureImage::LumaAU32),
+        (3, Precision::U32) => download_image(tex, level).map(TextureImage::RgbU32),
+        (_, Precision::U32) => download_image(tex, level).map(TextureImage::RgbaU32),
+    }
+}
+
+/// Reads the first layer of a mipmap level into an `ImageBuffer`.
+///
+/// Returns `None` if the backend doesn't support reading the texture in this format.
+#[cfg(feature = "image")]
+fn download_image<T, P>(tex: &TextureAny, level: u32) -> Option<image::ImageBuffer<P, Vec<T>>>
+                        where T: image::Primitive + Send + 'static,
+                              P: PixelValue + image::Pixel<Subpixel = T> + Clone + Copy
+{
+    download_texture_if_supported::<P>(tex, 0, level).map(|(data, width, height, _)| {
+        Texture2dDataSink::from_raw(Cow::Owned(data), width, height)
+    })
+}
+
+/// Returns the raw bytes of the data to upload, converted on the CPU to a client format that
+/// the backend accepts if necessary.
+fn get_upload_data<'a, P>(context: &Context, format: TextureFormatRequest,
+                          client_format: ClientFormat, data: &'a [P])
+                          -> (ClientFormat, Cow<'a, [u8]>)
+{
+    let bytes = unsafe {
+        slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * mem::size_of::<P>())
+    };
+
+    let upload_format = image_format::get_nearest_supported_client_format(context, client_format,
+                                                                          format);
+
+    if upload_format == client_format {
+        (client_format, Cow::Borrowed(bytes))
+    } else {
+        let converted = convert::convert(bytes, client_format, upload_format);
+        (upload_format, Cow::Owned(converted))
+    }
+}
+
+/// Converts pixels from a client format to another on the CPU.
+fn convert_pixels<T, P>(data: &[T], from: ClientFormat, to: ClientFormat) -> Vec<P> {
+    let bytes = unsafe {
+        slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * mem::size_of::<T>())
+    };
+
+    let converted = convert::convert(bytes, from, to);
+    let len = converted.len() / mem::size_of::<P>();
+
+    let mut result = Vec::with_capacity(len);
+    unsafe {
+        ptr::copy_nonoverlapping(converted.as_ptr(), result.as_mut_ptr() as *mut u8,
+                                 len * mem::size_of::<P>());
+        result.set_len(len);
+    }
+    result
+}
+
+/// Returns the format and type to pass to the `glTexImage` functions.
+fn get_client_format(context: &Context, client_format: Option<ClientFormat>,
+                     format: TextureFormatRequest) -> (gl::types::GLenum, gl::types::GLenum)
+{
+    match (client_format, format) {
+        (Some(client_format), f) => image_format::client_format_to_glenum(context, client_format, f),
+        (None, TextureFormatRequest::AnyDepth) => (gl::DEPTH_COMPONENT, gl::FLOAT),
+        (None, TextureFormatRequest::Specific(TextureFormat::DepthFormat(_))) => (gl::DEPTH_COMPONENT, gl::FLOAT),
+        (None, TextureFormatRequest::AnyDepthStencil) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
+        (None, TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_))) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
+        (None, _) => (gl::RGBA, gl::UNSIGNED_BYTE),
+    }
+}
+
+/// Returns the number of levels to allocate for a texture whose largest dimension is
+/// `max_dimension`, and whether the mipmaps must be generated.
+fn get_mipmaps_levels(context: &Context, mipmaps: MipmapsOption, format: TextureFormatRequest,
+                      max_dimension: u32) -> (gl::types::GLsizei, bool)
+{
+    let can_generate = is_mipmaps_generation_supported(context) && match format {
+        TextureFormatRequest::AnyFloatingPoint |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(_)) |
+        TextureFormatRequest::AnyIntegral |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
+        TextureFormatRequest::AnyUnsigned |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => true,
+        _ => false,
+    };
+
+    let full_chain = match max_dimension {
+        0 => 1,
+        a => 1 + (a as f32).log2() as gl::types::GLsizei
+    };
+
+    match mipmaps {
+        MipmapsOption::NoMipmap => (1, false),
+        MipmapsOption::EmptyMipmaps => (full_chain, false),
+        MipmapsOption::EmptyMipmapsMax(max) => {
+            (cmp::min(full_chain, 1 + max as gl::types::GLsizei), false)
+        },
+        MipmapsOption::AutoGeneratedMipmaps if can_generate => (full_chain, true),
+        MipmapsOption::AutoGeneratedMipmapsMax(max) if can_generate => {
+            (cmp::min(full_chain, 1 + max as gl::types::GLsizei), true)
+        },
+        MipmapsOption::AutoGeneratedMipmaps | MipmapsOption::AutoGeneratedMipmapsMax(_) => {
+            (1, false)
+        },
+    }
+}
+
+/// Returns true if the backend supports `glGenerateMipmap`.
+fn is_mipmaps_generation_supported(context: &Context) -> bool {
+    context.get_version() >= &Version(Api::Gl, 3, 0) ||
+    context.get_version() >= &Version(Api::GlEs, 2, 0) ||
+    context.get_extensions().gl_ext_framebuffer_object
+}
+
+/// Generates the mipmaps of the texture bound to `bind_point` from its main level.
+///
+/// The backend must support it, see `is_mipmaps_generation_supported`.
+unsafe fn generate_bound_mipmaps(ctxt: &mut CommandContext, bind_point: gl::types::GLenum) {
+    if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        ctxt.gl.GenerateMipmap(bind_point);
+    } else {
+        ctxt.gl.GenerateMipmapEXT(bind_point);
+    }
+}
+
+impl<'a> TextureAnyMipmap<'a> {
+    /// Returns the texture.
+    pub fn get_texture(&self) -> &'a TextureAny {
+        self.texture
+    }
+
+    /// Returns the level of the texture.
+    pub fn get_level(&self) -> u32 {
+        self.level
+    }
+
+    /// Returns the layer of the texture.
+    pub fn get_layer(&self) -> u32 {
+        self.layer
+    }
+}
+
+/// Changes some parts of a mipmap level of the texture.
+///
+/// `z_offset` and `depth` designate the depth for 3D textures, the layers for arrays and the
+/// faces (`6 * cubemap + face`) for cubemaps. The `height` must be `None` for 1D textures.
+pub fn upload_texture<'a, P>(tex: &TextureAny, x_offset: u32, y_offset: u32, z_offset: u32,
+                             (format, data): (ClientFormat, Cow<'a, [P]>), width: u32,
+                             height: Option<u32>, depth: Option<u32>, level: u32,
+                             regen_mipmaps: bool)
+                             where P: Send + Copy + Clone + 'a
+{
+    let id = tex.id;
+    let bind_point = tex.bind_point;
+    let regen_mipmaps = regen_mipmaps && tex.levels >= 2;
+
+    assert!(level < tex.levels);
+
+    // dimensions of the mipmap level
+    let level_width = cmp::max(1, tex.width >> level);
+    let level_height = cmp::max(1, tex.height.unwrap_or(1) >> level);
+    let layers = match tex.ty {
+        TextureType::Texture3d => cmp::max(1, tex.depth.unwrap_or(1) >> level),
+        TextureType::Cubemap | TextureType::CubemapArray => 6 * tex.array_size.unwrap_or(1),
+        _ => tex.array_size.unwrap_or(1),
+    };
+
+    assert!(x_offset + width <= level_width);
+    assert!(y_offset + height.unwrap_or(1) <= level_height);
+    assert!(z_offset + depth.unwrap_or(1) <= layers);
+
+    if width as usize * height.unwrap_or(1) as usize * depth.unwrap_or(1) as usize *
+        format.get_size() != data.len() * mem::size_of::<P>()
+    {
+        panic!("Texture data size mismatch");
+    }
+
+    // the data is converted on the CPU if the backend doesn't accept its client format
+    let (format, data) = get_upload_data(&tex.context, tex.requested_format, format, &data);
+
+    let (client_format, client_type) = image_format::client_format_to_glenum(&tex.context, format,
+                                                                             tex.requested_format);
+
+    let mut ctxt = tex.context.make_current();
+
+    unsafe {
+        if ctxt.state.pixel_store_unpack_alignment != 1 {
+            ctxt.state.pixel_store_unpack_alignment = 1;
+            ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+        }
+
+        if ctxt.state.pixel_unpack_buffer_binding != 0 {
+            ctxt.state.pixel_unpack_buffer_binding = 0;
+            ctxt.gl.BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+        }
+
+        {
+            ctxt.gl.BindTexture(bind_point, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
+
+        match bind_point {
+            gl::TEXTURE_1D => {
+                ctxt.gl.TexSubImage1D(bind_point, level as gl::types::GLint,
+                                      x_offset as gl::types::GLint,
+                                      width as gl::types::GLsizei,
+                                      client_format, client_type,
+                                      data.as_ptr() as *const libc::c_void);
+            },
+
+            gl::TEXTURE_1D_ARRAY => {
+                ctxt.gl.TexSubImage2D(bind_point, level as gl::types::GLint,
+                                      x_offset as gl::types::GLint,
+                                      z_offset as gl::types::GLint,
+                                      width as gl::types::GLsizei,
+                                      depth.unwrap_or(1) as gl::types::GLsizei,
+                                      client_format, client_type,
+                                      data.as_ptr() as *const libc::c_void);
+            },
+
+            gl::TEXTURE_2D | gl::TEXTURE_RECTANGLE => {
+                ctxt.gl.TexSubImage2D(bind_point, level as gl::types::GLint,
+                                      x_offset as gl::types::GLint,
+                                      y_offset as gl::types::GLint,
+                                      width as gl::types::GLsizei,
+                                      height.unwrap_or(1) as gl::types::GLsizei,
+                                      client_format, client_type,
+                                      data.as_ptr() as *const libc::c_void);
+            },
+
+            gl::TEXTURE_CUBE_MAP => {
+                // each face must be uploaded separately
+                let face_size = width as usize * height.unwrap_or(1) as usize *
+                                format.get_size();
+
+                for face in (0 .. depth.unwrap_or(1)) {
+                    let face_data = &data[face as usize * face_size ..];
+                    ctxt.gl.TexSubImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + z_offset + face,
+                                          level as gl::types::GLint,
+                                          x_offset as gl::types::GLint,
+                                          y_offset as gl::types::GLint,
+                                          width as gl::types::GLsizei,
+                                          height.unwrap_or(1) as gl::types::GLsizei,
+                                          client_format, client_type,
+                                          face_data.as_ptr() as *const libc::c_void);
+                }
+            },
+
+            gl::TEXTURE_3D | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_CUBE_MAP_ARRAY => {
+                ctxt.gl.TexSubImage3D(bind_point, level as gl::types::GLint,
+                                      x_offset as gl::types::GLint,
+                                      y_offset as gl::types::GLint,
+                                      z_offset as gl::types::GLint,
+                                      width as gl::types::GLsizei,
+                                      height.unwrap_or(1) as gl::types::GLsizei,
+                                      depth.unwrap_or(1) as gl::types::GLsizei,
+                                      client_format, client_type,
+                                      data.as_ptr() as *const libc::c_void);
+            },
+
+            // multisample textures can't be uploaded to
+            _ => unreachable!()
+        }
+
+        // regenerate mipmaps if there are some
+        if regen_mipmaps && is_mipmaps_generation_supported(&tex.context) {
+            generate_bound_mipmaps(&mut ctxt, bind_point);
+        }
+    }
+}
+
+/// Reads the content of a layer of a mipmap level of the texture.
+///
+/// For cubemaps, the layer is `6 * cube + face`.
+pub fn read_layer<T>(tex: &TextureAny, layer: u32, level: u32) -> T
+                     where T: Texture2dDataSink<(u8, u8, u8, u8)>
+{
+    let width = cmp::max(1, tex.width >> level);
+    let height = cmp::max(1, tex.height.unwrap_or(1) >> level);
+
+    let attachment = fbo::Attachment::TextureLayer {
+        texture: tex,
+        layer: layer,
+        level: level,
+    };
+
+    let rect = Rect {
+        bottom: 0,
+        left: 0,
+        width: width,
+        height: height,
+    };
+
+    let mut ctxt = tex.context.make_current();
+
+    let mut data = Vec::with_capacity(0);
+    ops::read(&mut ctxt, ops::Source::Attachment(&attachment,
+                                                 &tex.context.get_framebuffer_objects()),
+              &rect, &mut data);
+    T::from_raw(Cow::Owned(data), width, height)
+}
+
+/// Reads the content of a mipmap level of the texture in the client format of `P`.
+///
+/// For arrays, only the layer `layer` is read. For cubemaps, the layer is `6 * cube + face` and
+/// only this face is read. 3D textures are always read entirely.
+///
+/// Returns the data followed by its width, height and depth.
+///
+/// ## Panic
+///
+/// Panics if the backend doesn't support reading this texture. See
+/// `download_texture_if_supported`.
+pub fn download_texture<P>(tex: &TextureAny, layer: u32, level: u32) -> (Vec<P>, u32, u32, u32)
+                           where P: PixelValue
+{
+    match download_texture_if_supported(tex, layer, level) {
+        Some(result) => result,
+        None => panic!("Reading this texture is not supported by the backend"),
+    }
+}
+
+/// Reads the content of a mipmap level of the texture in the client format of `P`, like
+/// `download_texture`.
+///
+/// `glGetTexImage` is used when available. For arrays, only the requested layer is read, with
+/// `glGetTextureSubImage` or by attaching it to a framebuffer if the backend doesn't support it
+/// (all the layers are still read for compressed, depth, stencil and integral arrays). On
+/// OpenGL ES, each layer is attached to a framebuffer and read with `glReadPixels`, which only
+/// works for color textures. Returns `None` if the texture can't be read this way, or if a
+/// depth-stencil texture is read in another client format than `ClientFormat::U32`.
+pub fn download_texture_if_supported<P>(tex: &TextureAny, layer: u32, level: u32)
+                                        -> Option<(Vec<P>, u32, u32, u32)> where P: PixelValue
+{
+    assert!(level < tex.levels);
+
+    match tex.ty {
+        TextureType::Texture2dMultisample | TextureType::Texture2dMultisampleArray => {
+            panic!("Multisample textures can't be read");
+        },
+        _ => ()
+    }
+
+    // dimensions of the mipmap level
+    let width = cmp::max(1, tex.width >> level);
+    let height = cmp::max(1, tex.height.unwrap_or(1) >> level);
+    let depth = match tex.ty {
+        TextureType::Texture3d => cmp::max(1, tex.depth.unwrap_or(1) >> level),
+        _ => 1,
+    };
+
+    let layer_size = width as usize * height as usize * depth as usize;
+
+    let (format, ty) = match get_read_format(&tex.context, <P as PixelValue>::get_format(),
+                                             tex.requested_format)
+    {
+        Some(f) => f,
+        None => return None,
+    };
+
+    let mut ctxt = tex.context.make_current();
+
+    if ctxt.version >= &Version(Api::Gl, 1, 0) {
+        // `glGetTexImage` reads all the layers of the level at once, except for cubemaps
+        let (target, layers) = match tex.ty {
+            TextureType::Cubemap => (gl::TEXTURE_CUBE_MAP_POSITIVE_X + layer, 1),
+            TextureType::CubemapArray => (tex.bind_point, 6 * tex.array_size.unwrap_or(1)),
+            TextureType::Texture1dArray | TextureType::Texture2dArray => {
+                (tex.bind_point, tex.array_size.unwrap_or(1))
+            },
+            _ => (tex.bind_point, 1),
+        };
+
+        let sub_image_supported = ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                                  ctxt.extensions.gl_arb_get_texture_sub_image;
+
+        // without `glGetTextureSubImage`, a layer of an uncompressed color array is attached to
+        // a framebuffer and read alone
+        if layers >= 2 && !sub_image_supported {
+            let color = match format {
+                gl::RED | gl::RG | gl::RGB | gl::RGBA => true,
+                _ => false
+            };
+
+            let mipmap = TextureAnyMipmap { texture: tex, layer: layer, level: level };
+            if color && !get_compression(&mut ctxt, &mipmap).0 {
+                let rect = Rect {
+                    bottom: 0,
+                    left: 0,
+                    width: width,
+                    height: height,
+                };
+
+                let attachment = fbo::Attachment::TextureLayer {
+                    texture: tex,
+                    layer: layer,
+                    level: level,
+                };
+
+                return read_attachment(&mut ctxt, tex, &attachment, &rect)
+                                      .map(|data| (data, width, height, depth));
+            }
+        }
+
+        // otherwise the layer is read with `glGetTextureSubImage`, or all the layers are read
+        // with `glGetTexImage` and the requested one is extracted
+        let read_size = if sub_image_supported { layer_size } else { layer_size * layers as usize };
+        let mut data: Vec<P> = Vec::with_capacity(read_size);
+
+        unsafe {
+            if ctxt.state.pixel_store_pack_alignment != 1 {
+                ctxt.state.pixel_store_pack_alignment = 1;
+                ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+            }
+
+            if ctxt.state.pixel_pack_buffer_binding != 0 {
+                ctxt.state.pixel_pack_buffer_binding = 0;
+                ctxt.gl.BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+            }
+
+            if layers >= 2 && sub_image_supported {
+                // the layer of 1D arrays is the second coordinate
+                let (y, z) = match tex.ty {
+                    TextureType::Texture1dArray => (layer, 0),
+                    _ => (0, layer),
+                };
+
+                ctxt.gl.GetTextureSubImage(tex.id, level as gl::types::GLint, 0,
+                                           y as gl::types::GLint, z as gl::types::GLint,
+                                           width as gl::types::GLsizei,
+                                           height as gl::types::GLsizei, 1, format, ty,
+                                           (read_size * mem::size_of::<P>())
+                                                                    as gl::types::GLsizei,
+                                           data.as_mut_ptr() as *mut libc::c_void);
+
+            } else {
+                {
+                    ctxt.gl.BindTexture(tex.bind_point, tex.id);
+                    let act = ctxt.state.active_texture as usize;
+                    ctxt.state.texture_units[act].texture = tex.id;
+                }
+
+                ctxt.gl.GetTexImage(target, level as gl::types::GLint, format, ty,
+                                    data.as_mut_ptr() as *mut libc::c_void);
+            }
+
+            data.set_len(read_size);
+        }
+
+        if read_size != layer_size {
+            data = data.into_iter().skip(layer as usize * layer_size).take(layer_size).collect();
+        }
+
+        Some((data, width, height, depth))
+
+    } else {
+        // depth and stencil attachments can't be read with `glReadPixels` on OpenGL ES, and
+        // `ops::read` doesn't read integer attachments with the `*_INTEGER` formats
+        match format {
+            gl::DEPTH_COMPONENT | gl::STENCIL_INDEX | gl::DEPTH_STENCIL => return None,
+            gl::RED_INTEGER | gl::RG_INTEGER | gl::RGB_INTEGER | gl::RGBA_INTEGER => return None,
+            _ => ()
+        }
+
+        let rect = Rect {
+            bottom: 0,
+            left: 0,
+            width: width,
+            height: height,
+        };
+
+        // OpenGL ES only guarantees that colors can be read as four unsigned bytes, or as four
+        // floats for floating-point textures, so other client formats are converted on the CPU
+        let client_format = <P as PixelValue>::get_format();
+        let read_format = match client_format {
+            ClientFormat::F16 | ClientFormat::F16F16 | ClientFormat::F16F16F16 |
+            ClientFormat::F16F16F16F16 | ClientFormat::F32 | ClientFormat::F32F32 |
+            ClientFormat::F32F32F32 | ClientFormat::F32F32F32F32 => ClientFormat::F32F32F32F32,
+            _ => ClientFormat::U8U8U8U8,
+        };
+        let needs_conversion = client_format != read_format &&
+                               (format == gl::RED || format == gl::RG || format == gl::RGB ||
+                                format == gl::RGBA);
+
+        let mut data = Vec::with_capacity(layer_size);
+
+        // each layer of a 3D texture must be read separately
+        for z in (0 .. depth) {
+            let attachment = fbo::Attachment::TextureLayer {
+                texture: tex,
+                layer: if tex.ty == TextureType::Texture3d { z } else { layer },
+                level: level,
+            };
+
+            let layer_data: Option<Vec<P>> = if !needs_conversion {
+                read_attachment(&mut ctxt, tex, &attachment, &rect)
+            } else if read_format == ClientFormat::F32F32F32F32 {
+                let raw: Option<Vec<(f32, f32, f32, f32)>> =
+                                            read_attachment(&mut ctxt, tex, &attachment, &rect);
+                raw.map(|raw| convert_pixels(&raw, read_format, client_format))
+            } else {
+                let raw: Option<Vec<(u8, u8, u8, u8)>> =
+                                            read_attachment(&mut ctxt, tex, &attachment, &rect);
+                raw.map(|raw| convert_pixels(&raw, read_format, client_format))
+            };
+
+            match layer_data {
+                Some(layer_data) => data.extend(layer_data.into_iter()),
+                None => return None,
+            }
+        }
+
+        Some((data, width, height, depth))
+    }
+}
+
+/// Reads all the pixels of an attachment that belongs to the texture with `glReadPixels`.
+///
+/// Returns `None` if the backend doesn't support reading the attachment in this format.
+fn read_attachment<T>(ctxt: &mut CommandContext, tex: &TextureAny, attachment: &fbo::Attachment,
+                      rect: &Rect) -> Option<Vec<T>> where T: PixelValue
+{
+    let mut data = Vec::with_capacity(0);
+    match ops::read_if_supported(ctxt,
+                                 ops::Source::Attachment(attachment,
+                                                         &tex.context.get_framebuffer_objects()),
+                                 rect, &mut data)
+    {
+        Ok(_) => Some(data),
+        Err(_) => None,
+    }
+}
+
+/// Reads the compressed blocks of a mipmap of a 2D texture or of an array of 2D textures. For
+/// arrays, the blocks of all the layers are returned one after another.
+///
+/// Returns `None` if the backend doesn't support reading compressed data, or if the texture is
+/// not stored in a compressed format known by glium.
+pub fn download_compressed_data(tex: &TextureAny, level: u32)
+                                -> Option<(TextureFormat, Vec<u8>)>
+{
+    assert!(level < tex.levels);
+
+    match tex.ty {
+        TextureType::Texture2d | TextureType::Texture2dArray => (),
+        _ => panic!("Only the compressed data of 2D textures and arrays can be read"),
+    };
+
+    // `glGetCompressedTexImage` doesn't exist in OpenGL ES
+    if !(tex.context.get_version() >= &Version(Api::Gl, 1, 3)) {
+        return None;
+    }
+
+    let mut ctxt = tex.context.make_current();
+
+    unsafe {
+        {
+            ctxt.gl.BindTexture(tex.bind_point, tex.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = tex.id;
+        }
+
+        let mut compressed: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(tex.bind_point, level as gl::types::GLint,
+                                       gl::TEXTURE_COMPRESSED, &mut compressed);
+        if compressed == 0 {
+            return None;
+        }
+
+        // the driver may have chosen the format if it wasn't specified
+        let format = match tex.requested_format {
+            TextureFormatRequest::Specific(format) => format,
+            _ => {
+                let mut internal_format: gl::types::GLint = mem::uninitialized();
+                ctxt.gl.GetTexLevelParameteriv(tex.bind_point, level as gl::types::GLint,
+                                               gl::TEXTURE_INTERNAL_FORMAT,
+                                               &mut internal_format);
+
+                let internal_format = internal_format as gl::types::GLenum;
+                match image_format::compressed_glenum_to_format(internal_format) {
+                    Some(f) => f,
+                    None => return None,
+                }
+            },
+        };
+
+        let mut size: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(tex.bind_point, level as gl::types::GLint,
+                                       gl::TEXTURE_COMPRESSED_IMAGE_SIZE, &mut size);
+
+        if ctxt.state.pixel_pack_buffer_binding != 0 {
+            ctxt.state.pixel_pack_buffer_binding = 0;
+            ctxt.gl.BindBuffer(gl::PIXEL_PACK_BUFFER, 0);
+        }
+
+        let mut data: Vec<u8> = Vec::with_capacity(size as usize);
+        ctxt.gl.GetCompressedTexImage(tex.bind_point, level as gl::types::GLint,
+                                      data.as_mut_ptr() as *mut libc::c_void);
+        data.set_len(size as usize);
+
+        Some((format, data))
+    }
+}
+
+/// Returns the format and type to pass to `glGetTexImage` or `glReadPixels`.
+///
+/// Returns `None` if the texture can't be read in this client format. Depth-stencil textures
+/// can only be read as `ClientFormat::U32`.
+fn get_read_format(context: &Context, client: ClientFormat, format: TextureFormatRequest)
+                   -> Option<(gl::types::GLenum, gl::types::GLenum)>
+{
+    match format {
+        TextureFormatRequest::AnyStencil |
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) => {
+            let (_, ty) = image_format::client_format_to_glenum(context, client, format);
+            Some((gl::STENCIL_INDEX, ty))
+        },
+
+        TextureFormatRequest::AnyDepthStencil |
+        TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_)) => {
+            if client != ClientFormat::U32 {
+                return None;
+            }
+
+            Some((gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8))
+        },
+
+        f => Some(image_format::client_format_to_glenum(context, client, f)),
+    }
+}
+
+/// Copies a rectangle of pixels from a mipmap to another, without any conversion or filtering.
+///
+/// The mipmaps can belong to textures of different types, or to different layers or faces of
+/// the same texture. Their formats must be compatible, which means that they must be identical
+/// or belong to the same class as for texture views (for uncompressed formats, the same number
+/// of bits per pixel). Compressed textures are copied block by block, so the rectangle and the
+/// offset must be aligned on blocks unless they reach the border of the mipmap.
+///
+/// `glCopyImageSubData` is used if it is available. Otherwise the source is attached to a
+/// framebuffer and copied with `glCopyTexSubImage`, which requires an uncompressed and
+/// color-renderable source, and two different mipmaps if the texture is the same.
+///
+/// For 3D textures, only the first slice can be copied.
+///
+/// # Panic
+///
+/// Panics if the rectangle doesn't fit in the source or in the destination, if the formats
+/// are not compatible, if the blocks of a compressed texture are not aligned, or if the copy
+/// is not supported by the backend.
+pub fn copy_region<'a, 'b, S, D>(source: S, source_rect: &Rect, dest: D, dest_offset: (u32, u32))
+                                 where S: Into<TextureAnyMipmap<'a>>,
+                                       D: Into<TextureAnyMipmap<'b>>
+{
+    let source = source.into();
+    let dest = dest.into();
+
+    let (source_width, source_height) = get_mipmap_dimensions(&source);
+    let (dest_width, dest_height) = get_mipmap_dimensions(&dest);
+
+    assert!(source_rect.left + source_rect.width <= source_width);
+    assert!(source_rect.bottom + source_rect.height <= source_height);
+    assert!(dest_offset.0 + source_rect.width <= dest_width);
+    assert!(dest_offset.1 + source_rect.height <= dest_height);
+
+    let mut ctxt = source.texture.context.make_current();
+
+    let (source_compressed, source_block) = get_compression(&mut ctxt, &source);
+    let (dest_compressed, dest_block) = get_compression(&mut ctxt, &dest);
+    assert!(source_compressed == dest_compressed,
+            "Can't copy between a compressed and an uncompressed texture");
+
+    let aligned = |offset: u32, size: u32, max: u32, block: u32| {
+        offset % block == 0 && (size % block == 0 || offset + size == max)
+    };
+
+    if let Some((block_width, block_height)) = source_block {
+        assert!(aligned(source_rect.left, source_rect.width, source_width, block_width) &&
+                aligned(source_rect.bottom, source_rect.height, source_height, block_height),
+                "The region to copy is not aligned on the compressed blocks");
+    }
+
+    if let Some((block_width, block_height)) = dest_block {
+        assert!(aligned(dest_offset.0, source_rect.width, dest_width, block_width) &&
+                aligned(dest_offset.1, source_rect.height, dest_height, block_height),
+                "The region to copy is not aligned on the compressed blocks");
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+       ctxt.extensions.gl_arb_copy_image
+    {
+        // the format classes are the same as for texture views
+        let source_format = get_mipmap_internal_format(&mut ctxt, &source);
+        let dest_format = get_mipmap_internal_format(&mut ctxt, &dest);
+        let source_class = image_format::get_view_class(source_format);
+        assert!(source_format == dest_format ||
+                (source_class.is_some() &&
+                 source_class == image_format::get_view_class(dest_format)),
+                "The formats of the two textures are not compatible");
+
+        // the layer of 1D arrays is the second coordinate
+        let (source_y, source_z) = match source.texture.ty {
+            TextureType::Texture1dArray => (source.layer, 0),
+            _ => (source_rect.bottom, source.layer),
+        };
+
+        let (dest_y, dest_z) = match dest.texture.ty {
+            TextureType::Texture1dArray => (dest.layer, 0),
+            _ => (dest_offset.1, dest.layer),
+        };
+
+        unsafe {
+            ctxt.gl.CopyImageSubData(source.texture.id, source.texture.bind_point,
+                                     source.level as gl::types::GLint,
+                                     source_rect.left as gl::types::GLint,
+                                     source_y as gl::types::GLint,
+                                     source_z as gl::types::GLint,
+                                     dest.texture.id, dest.texture.bind_point,
+                                     dest.level as gl::types::GLint,
+                                     dest_offset.0 as gl::types::GLint,
+                                     dest_y as gl::types::GLint,
+                                     dest_z as gl::types::GLint,
+                                     source_rect.width as gl::types::GLsizei,
+                                     source_rect.height as gl::types::GLsizei, 1);
+        }
+
+        return;
+    }
+
+    if source_compressed {
+        panic!("Copying compressed textures is not supported by the backend");
+    }
+
+    // reading from the mipmap that is being written would be a feedback loop
+    assert!(source.texture.id != dest.texture.id || source.level != dest.level,
+            "Can't copy within the same mipmap without `glCopyImageSubData`");
+
+    let attachment = fbo::Attachment::TextureLayer {
+        texture: source.texture,
+        layer: source.layer,
+        level: source.level,
+    };
+
+    source.texture.context.get_framebuffer_objects()
+          .bind_framebuffer_for_reading(&attachment, &mut ctxt);
+
+    unsafe {
+        {
+            ctxt.gl.BindTexture(dest.texture.bind_point, dest.texture.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = dest.texture.id;
+        }
+
+        let level = dest.level as gl::types::GLint;
+        let x = source_rect.left as gl::types::GLint;
+        let y = source_rect.bottom as gl::types::GLint;
+        let width = source_rect.width as gl::types::GLsizei;
+        let height = source_rect.height as gl::types::GLsizei;
+
+        match dest.texture.bind_point {
+            gl::TEXTURE_1D => {
+                ctxt.gl.CopyTexSubImage1D(gl::TEXTURE_1D, level,
+                                          dest_offset.0 as gl::types::GLint, x, y, width);
+            },
+
+            gl::TEXTURE_1D_ARRAY => {
+                ctxt.gl.CopyTexSubImage2D(gl::TEXTURE_1D_ARRAY, level,
+                                          dest_offset.0 as gl::types::GLint,
+                                          dest.layer as gl::types::GLint, x, y, width, 1);
+            },
+
+            gl::TEXTURE_2D => {
+                ctxt.gl.CopyTexSubImage2D(gl::TEXTURE_2D, level,
+                                          dest_offset.0 as gl::types::GLint,
+                                          dest_offset.1 as gl::types::GLint,
+                                          x, y, width, height);
+            },
+
+            gl::TEXTURE_CUBE_MAP => {
+                ctxt.gl.CopyTexSubImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + dest.layer, level,
+                                          dest_offset.0 as gl::types::GLint,
+                                          dest_offset.1 as gl::types::GLint,
+                                          x, y, width, height);
+            },
+
+            gl::TEXTURE_3D | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_CUBE_MAP_ARRAY => {
+                ctxt.gl.CopyTexSubImage3D(dest.texture.bind_point, level,
+                                          dest_offset.0 as gl::types::GLint,
+                                          dest_offset.1 as gl::types::GLint,
+                                          dest.layer as gl::types::GLint,
+                                          x, y, width, height);
+            },
+
+            _ => panic!("Copying to multisample textures is not supported by the backend")
+        }
+    }
+}
+
+/// Returns the width and height of a mipmap.
+fn get_mipmap_dimensions(mipmap: &TextureAnyMipmap) -> (u32, u32) {
+    let width = cmp::max(1, mipmap.texture.width >> mipmap.level);
+    let height = cmp::max(1, mipmap.texture.height.unwrap_or(1) >> mipmap.level);
+    (width, height)
+}
+
+/// Returns whether a mipmap is stored in a compressed format, and the width and height of its
+/// blocks if the format is known by glium.
+///
+/// If the format has been chosen by the driver, the storage of the mipmap is queried like in
+/// `download_compressed_data`.
+fn get_compression(ctxt: &mut CommandContext, mipmap: &TextureAnyMipmap)
+                   -> (bool, Option<(u32, u32)>)
+{
+    let tex = mipmap.texture;
+
+    match tex.requested_format {
+        TextureFormatRequest::Specific(format) => {
+            let block = image_format::get_compressed_block_size(format);
+            return (block.is_some(), block.map(|(width, height, _)| (width, height)));
+        },
+        TextureFormatRequest::AnyCompressed | TextureFormatRequest::AnyCompressedSrgb => (),
+        _ => return (false, None),
+    };
+
+    // generic compressed formats are only used on desktop OpenGL, and
+    // `glGetTexLevelParameteriv` doesn't exist before OpenGL ES 3.1
+    if !(ctxt.version >= &Version(Api::Gl, 1, 0)) {
+        return (false, None);
+    }
+
+    // the faces of cubemaps are queried individually
+    let target = match tex.bind_point {
+        gl::TEXTURE_CUBE_MAP => gl::TEXTURE_CUBE_MAP_POSITIVE_X + mipmap.layer % 6,
+        bind_point => bind_point,
+    };
+
+    unsafe {
+        {
+            ctxt.gl.BindTexture(tex.bind_point, tex.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = tex.id;
+        }
+
+        let mut compressed: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(target, mipmap.level as gl::types::GLint,
+                                       gl::TEXTURE_COMPRESSED, &mut compressed);
+        if compressed == 0 {
+            return (false, None);
+        }
+
+        let mut internal_format: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(target, mipmap.level as gl::types::GLint,
+                                       gl::TEXTURE_INTERNAL_FORMAT, &mut internal_format);
+
+        let block = image_format::compressed_glenum_to_format(internal_format as gl::types::GLenum)
+                                 .and_then(|format| image_format::get_compressed_block_size(format))
+                                 .map(|(width, height, _)| (width, height));
+        (true, block)
+    }
+}
+
+/// Returns the internal format of a mipmap, as chosen by the driver.
+fn get_mipmap_internal_format(ctxt: &mut CommandContext, mipmap: &TextureAnyMipmap)
+                              -> gl::types::GLenum
+{
+    let tex = mipmap.texture;
+
+    // the faces of cubemaps are queried individually
+    let target = match tex.bind_point {
+        gl::TEXTURE_CUBE_MAP => gl::TEXTURE_CUBE_MAP_POSITIVE_X + mipmap.layer % 6,
+        bind_point => bind_point,
+    };
+
+    unsafe {
+        {
+            ctxt.gl.BindTexture(tex.bind_point, tex.id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = tex.id;
+        }
+
+        let mut internal_format: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetTexLevelParameteriv(target, mipmap.level as gl::types::GLint,
+                                       gl::TEXTURE_INTERNAL_FORMAT, &mut internal_format);
+        internal_format as gl::types::GLenum
+    }
+}
+
+/// Returns the `Context` associated with this texture.
+pub fn get_context(tex: &TextureAny) -> &Rc<Context> {
+    &tex.context
+}
+
+/// Returns the bind point of this texture.
+///
+/// The returned GLenum is guaranteed to be supported by the context.
+pub fn get_bind_point(tex: &TextureAny) -> gl::types::GLenum {
+    tex.bind_point
+}
+
+impl TextureAny {
+    /// UNSTABLE. Reads the content of a mipmap level of the texture.
+    pub fn read<T>(&self, level: u32) -> T
+                   where T: Texture2dDataSink<(u8, u8, u8, u8)>
+            // TODO: remove Clone for P
+    {
+        read_layer(self, 0, level)
+    }
+
+    /// UNSTABLE. Reads the content of a mipmap level of the texture to a pixel buffer.
+    // TODO: this function only works for level 0 right now
+    //       width/height need adjustements
+    pub fn read_to_pixel_buffer(&self, level: u32) -> PixelBuffer<(u8, u8, u8, u8)> {
+        assert_eq!(level, 0);   // TODO:
+
+        let size = self.width as usize * self.height.unwrap_or(1) as usize * 4;
+
+        let attachment = fbo::Attachment::TextureLayer {
+            texture: self,
+            layer: 0,
+            level: 0,
+        };
+
+        let rect = Rect {
+            bottom: 0,
+            left: 0,
+            width: self.width,
+            height: self.height.unwrap_or(1),
+        };
+
+        let pb = PixelBuffer::new_empty(&self.context, size);
+
+        let mut ctxt = self.context.make_current();
+        ops::read(&mut ctxt, ops::Source::Attachment(&attachment, &self.context.get_framebuffer_objects()), &rect, &pb);
+        pb
+    }
+
+    /// UNSTABLE. Returns the `Context` associated with this texture.
+    pub fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+
+    /// Returns the width of the texture.
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the texture.
+    pub fn get_height(&self) -> Option<u32> {
+        self.height.clone()
+    }
+
+    /// Returns the depth of the texture.
+    pub fn get_depth(&self) -> Option<u32> {
+        self.depth.clone()
+    }
+
+    /// Returns the array size of the texture.
+    pub fn get_array_size(&self) -> Option<u32> {
+        self.array_size.clone()
+    }
+
+    /// Returns the number of mipmap levels of the texture.
+    pub fn get_mipmap_levels(&self) -> u32 {
+        self.levels
+    }
+
+    /// Returns the number of samples of a multisample texture, or `None` if this is not a
+    /// multisample texture.
+    ///
+    /// This can be more than the number of samples that was requested when creating the texture.
+    pub fn get_samples(&self) -> Option<u32> {
+        self.samples
+    }
+
+    /// Returns the position of each sample within a pixel, or `None` if this is not a
+    /// multisample texture.
+    ///
+    /// The positions are between `0.0` and `1.0`, where `(0.5, 0.5)` is the center of the
+    /// pixel. The index of an element corresponds to the index of the sample when using
+    /// `texelFetch` on a `sampler2DMS`.
+    ///
+    /// Also returns `None` if the texture can't be attached to a framebuffer by the backend.
+    pub fn get_sample_positions(&self) -> Option<Vec<(f32, f32)>> {
+        let samples = match self.samples {
+            Some(s) => s,
+            None => return None
+        };
+
+        // sample positions are a property of the framebuffer that is bound for drawing
+        let (attachments, _) = main_level_attachments(self);
+        let attachments = match attachments.validate() {
+            Ok(attachments) => attachments,
+            Err(_) => return None,
+        };
+
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            let framebuffer = self.context.get_framebuffer_objects()
+                                          .get_framebuffer_for_drawing(Some(&attachments),
+                                                                       &mut ctxt);
+            fbo::bind_framebuffer(&mut ctxt, framebuffer, true, false);
+
+            Some((0 .. samples).map(|sample| {
+                let mut position: [gl::types::GLfloat; 2] = mem::uninitialized();
+                ctxt.gl.GetMultisamplefv(gl::SAMPLE_POSITION, sample, position.as_mut_ptr());
+                (position[0], position[1])
+            }).collect())
+        }
+    }
+
+    /// Generates the content of all the mipmaps of the texture from its main level.
+    ///
+    /// This is usually done after drawing on the main level. Does nothing if the texture has no
+    /// mipmap.
+    ///
+    /// Returns an error if generating mipmaps is not supported by the backend, or if the texture
+    /// is a compressed, depth, stencil or depth-stencil texture.
+    pub fn generate_mipmaps(&self) -> Result<(), MipmapsGenerationNotSupportedError> {
+        if !is_mipmaps_generation_supported(&self.context) {
+            return Err(MipmapsGenerationNotSupportedError);
+        }
+
+        match self.requested_format {
+            TextureFormatRequest::AnyCompressed | TextureFormatRequest::AnyCompressedSrgb |
+            TextureFormatRequest::AnyDepth | TextureFormatRequest::AnyStencil |
+            TextureFormatRequest::AnyDepthStencil |
+            TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::DepthFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_)) => {
+                return Err(MipmapsGenerationNotSupportedError);
+            },
+            _ => ()
+        }
+
+        if self.levels <= 1 {
+            return Ok(());
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            {
+                ctxt.gl.BindTexture(self.bind_point, self.id);
+                let act = ctxt.state.active_texture as usize;
+                ctxt.state.texture_units[act].texture = self.id;
+            }
+
+            generate_bound_mipmaps(&mut ctxt, self.bind_point);
+        }
+
+        Ok(())
+    }
+
+    /// Changes the source of each component of the values returned when sampling the texture.
+    ///
+    /// For example passing `(Red, Red, Red, One)` makes a single-channel texture return its
+    /// red component as a greyscale color. The default is `(Red, Green, Blue, Alpha)`.
+    ///
+    /// Returns an error if swizzling is not supported by the backend.
+    pub fn set_swizzle(&self, swizzle: (ComponentSwizzle, ComponentSwizzle, ComponentSwizzle,
+                                        ComponentSwizzle))
+                       -> Result<(), SwizzleNotSupportedError>
+    {
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 3)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+           !ctxt.extensions.gl_arb_texture_swizzle && !ctxt.extensions.gl_ext_texture_swizzle
+        {
+            return Err(SwizzleNotSupportedError);
+        }
+
+        let to_glenum = |component| -> gl::types::GLint {
+            (match component {
+                ComponentSwizzle::Red => gl::RED,
+                ComponentSwizzle::Green => gl::GREEN,
+                ComponentSwizzle::Blue => gl::BLUE,
+                ComponentSwizzle::Alpha => gl::ALPHA,
+                ComponentSwizzle::Zero => gl::ZERO,
+                ComponentSwizzle::One => gl::ONE,
+            }) as gl::types::GLint
+        };
+
+        unsafe {
+            {
+                ctxt.gl.BindTexture(self.bind_point, self.id);
+                let act = ctxt.state.active_texture as usize;
+                ctxt.state.texture_units[act].texture = self.id;
+            }
+
+            // `GL_TEXTURE_SWIZZLE_RGBA` doesn't exist in OpenGL ES
+            ctxt.gl.TexParameteri(self.bind_point, gl::TEXTURE_SWIZZLE_R, to_glenum(swizzle.0));
+            ctxt.gl.TexParameteri(self.bind_point, gl::TEXTURE_SWIZZLE_G, to_glenum(swizzle.1));
+            ctxt.gl.TexParameteri(self.bind_point, gl::TEXTURE_SWIZZLE_B, to_glenum(swizzle.2));
+            ctxt.gl.TexParameteri(self.bind_point, gl::TEXTURE_SWIZZLE_A, to_glenum(swizzle.3));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the component that is returned when sampling this texture, if it is a
+    /// depth-stencil texture.
+    pub fn get_depth_stencil_texture_mode(&self) -> DepthStencilTextureMode {
+        self.depth_stencil_texture_mode.get()
+    }
+
+    /// Chooses whether the depth or the stencil component is returned when sampling this
+    /// texture, if it is a depth-stencil texture. The default is `Depth`.
+    ///
+    /// Returns an error if sampling the stencil component is not supported by the backend.
+    pub fn set_depth_stencil_texture_mode(&self, mode: DepthStencilTextureMode)
+                                          -> Result<(), StencilTexturingNotSupportedError>
+    {
+        if self.depth_stencil_texture_mode.get() == mode {
+            return Ok(());
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 1)) &&
+           !ctxt.extensions.gl_arb_stencil_texturing
+        {
+            return Err(StencilTexturingNotSupportedError);
+        }
+
+        let value = match mode {
+            DepthStencilTextureMode::Depth => gl::DEPTH_COMPONENT,
+            DepthStencilTextureMode::Stencil => gl::STENCIL_INDEX,
+        };
+
+        unsafe {
+            {
+                ctxt.gl.BindTexture(self.bind_point, self.id);
+                let act = ctxt.state.active_texture as usize;
+                ctxt.state.texture_units[act].texture = self.id;
+            }
+
+            ctxt.gl.TexParameteri(self.bind_point, gl::DEPTH_STENCIL_TEXTURE_MODE,
+                                  value as gl::types::GLint);
+        }
+
+        self.depth_stencil_texture_mode.set(mode);
+        Ok(())
+    }
+
+    /// Returns the type of the texture (1D, 2D, 3D, etc.).
+    pub fn get_texture_type(&self) -> TextureType {
+        self.ty
+    }
+
+    /// Determines the internal format of this texture.
+    ///
+    /// Returns `None` if the backend doesn't allow querying the actual format.
+    pub fn get_internal_format_if_supported(&self) -> Option<InternalFormat> {
+        // TODO: cache this value in the texture
+        let mut ctxt = self.context.make_current();
+        get_format::get_format_if_supported(&mut ctxt, self)
+    }
+
+    /// Returns a structure that represents a specific mipmap of the texture.
+    ///
+    /// For cubemaps, the layer is `6 * cube + face`.
+    ///
+    /// Returns `None` if out of range.
+    pub fn mipmap(&self, layer: u32, level: u32) -> Option<TextureAnyMipmap> {
+        let layers = match self.ty {
+            TextureType::Cubemap | TextureType::CubemapArray => 6 * self.array_size.unwrap_or(1),
+            _ => self.array_size.unwrap_or(1),
+        };
+
+        if layer >= layers {
+            return None;
+        }
+
+        if level >= self.levels {
             return None;
         }
 
@@ -642,6 +2471,14 @@ impl Drop for TextureAny {
             }
         }
 
+        for image_unit in &mut ctxt.state.image_units {
+            if image_unit.texture == self.id {
+                *image_unit = Default::default();
+            }
+        }
+
+        ctxt.state.texture_parameters.remove(&self.id);
+
         unsafe { ctxt.gl.DeleteTextures(1, [ self.id ].as_ptr()); }
     }
 }
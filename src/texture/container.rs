@@ -0,0 +1,669 @@
+/*!
+Loading pre-compressed textures from DDS and KTX files.
+
+DDS, KTX and KTX2 files store textures that are already compressed, along with their mipmaps.
+Loading them with this module uploads the stored blocks directly, instead of letting the
+driver compress the pixels.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+let image = glium::texture::container::load("texture.dds").unwrap();
+let texture = image.into_compressed_srgb_texture_2d(&display).unwrap();
+```
+
+# Formats
+
+Only 2D textures and arrays of 2D textures are supported, in the S3TC, RGTC, BPTC, ETC2, EAC
+and ASTC formats. DDS files can't contain ETC2, EAC or ASTC data.
+Cubemaps, 3D textures, uncompressed formats and supercompressed KTX2 files return an
+`UnsupportedFeature` error.
+
+The color space of the blocks is not always stored in the file (for example legacy DDS files
+don't make the difference between linear and sRGB data). Therefore an image can be turned into
+either a regular compressed texture or a compressed sRGB texture, provided that the format
+exists in both color spaces.
+
+*/
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::{cmp, fmt, usize};
+
+use backend::Facade;
+use image_format;
+use texture::any;
+use texture::{CompressedFormat, CompressedSrgbFormat, TextureFormat};
+use texture::{TextureCreationError, TextureMaybeSupportedCreationError};
+use texture::{CompressedTexture2d, CompressedSrgbTexture2d};
+use texture::{CompressedTexture2dArray, CompressedSrgbTexture2dArray};
+
+/// Error that can happen while loading a DDS or KTX file.
+#[derive(Debug)]
+pub enum ContainerError {
+    /// Error while reading a file.
+    IoError(io::Error),
+
+    /// The file is not a valid DDS or KTX file.
+    InvalidFile(String),
+
+    /// The file uses a feature that is not supported.
+    UnsupportedFeature(String),
+
+    /// The format of the image is not supported by the backend.
+    FormatNotSupported,
+
+    /// The texture type or the dimensions of the image are not supported by the backend.
+    CreationError(TextureCreationError),
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &ContainerError::IoError(ref err) =>
+                formatter.write_fmt(format_args!("Error while reading a file: {}", err)),
+            &ContainerError::InvalidFile(ref s) =>
+                formatter.write_fmt(format_args!("Invalid file: {}", s)),
+            &ContainerError::UnsupportedFeature(ref s) =>
+                formatter.write_fmt(format_args!("Unsupported feature: {}", s)),
+            &ContainerError::FormatNotSupported =>
+                formatter.write_str("The format of the image is not supported by the backend"),
+            &ContainerError::CreationError(ref err) =>
+                formatter.write_fmt(format_args!("Error while creating the texture: {:?}", err)),
+        }
+    }
+}
+
+impl Error for ContainerError {
+    fn description(&self) -> &str {
+        match self {
+            &ContainerError::IoError(_) => "Error while reading a file",
+            &ContainerError::InvalidFile(_) => "The file is not a valid DDS or KTX file",
+            &ContainerError::UnsupportedFeature(_) => "The file uses an unsupported feature",
+            &ContainerError::FormatNotSupported => "The format is not supported by the backend",
+            &ContainerError::CreationError(_) => "Error while creating the texture",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match self {
+            &ContainerError::IoError(ref err) => Some(err as &Error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for ContainerError {
+    fn from(err: io::Error) -> ContainerError {
+        ContainerError::IoError(err)
+    }
+}
+
+impl From<TextureMaybeSupportedCreationError> for ContainerError {
+    fn from(err: TextureMaybeSupportedCreationError) -> ContainerError {
+        match err {
+            TextureMaybeSupportedCreationError::NotSupported => ContainerError::FormatNotSupported,
+            TextureMaybeSupportedCreationError::CreationError(err) => {
+                ContainerError::CreationError(err)
+            },
+        }
+    }
+}
+
+/// The content of a DDS or KTX file.
+#[derive(Debug, Clone)]
+pub struct CompressedImage {
+    /// Format of the compressed blocks. Always a `CompressedFormat` or a `CompressedSrgbFormat`.
+    pub format: TextureFormat,
+
+    /// Width of the main level in pixels.
+    pub width: u32,
+
+    /// Height of the main level in pixels.
+    pub height: u32,
+
+    /// Number of layers if the file contains an array of textures.
+    pub array_size: Option<u32>,
+
+    /// The compressed blocks of each mipmap, starting with the main level. For arrays, each
+    /// element contains the layers of the mipmap one after another.
+    pub levels: Vec<Vec<u8>>,
+}
+
+impl CompressedImage {
+    /// Uploads the image as a `CompressedTexture2d`.
+    ///
+    /// Returns an `UnsupportedFeature` error if the image contains more than one layer.
+    pub fn into_compressed_texture_2d<F>(self, facade: &F)
+                                         -> Result<CompressedTexture2d, ContainerError>
+                                         where F: Facade
+    {
+        let format = try!(to_linear(self.format));
+        let any = try!(self.into_texture(facade, format, false));
+        Ok(CompressedTexture2d(any))
+    }
+
+    /// Uploads the image as a `CompressedSrgbTexture2d`.
+    ///
+    /// Returns an `UnsupportedFeature` error if the image contains more than one layer.
+    pub fn into_compressed_srgb_texture_2d<F>(self, facade: &F)
+                                              -> Result<CompressedSrgbTexture2d, ContainerError>
+                                              where F: Facade
+    {
+        let format = try!(to_srgb(self.format));
+        let any = try!(self.into_texture(facade, format, false));
+        Ok(CompressedSrgbTexture2d(any))
+    }
+
+    /// Uploads the image as a `CompressedTexture2dArray`.
+    ///
+    /// An image that is not an array is uploaded as an array with one layer.
+    pub fn into_compressed_texture_2d_array<F>(self, facade: &F)
+                                               -> Result<CompressedTexture2dArray, ContainerError>
+                                               where F: Facade
+    {
+        let format = try!(to_linear(self.format));
+        let any = try!(self.into_texture(facade, format, true));
+        Ok(CompressedTexture2dArray(any))
+    }
+
+    /// Uploads the image as a `CompressedSrgbTexture2dArray`.
+    ///
+    /// An image that is not an array is uploaded as an array with one layer.
+    pub fn into_compressed_srgb_texture_2d_array<F>(self, facade: &F)
+                                                    -> Result<CompressedSrgbTexture2dArray,
+                                                              ContainerError>
+                                                    where F: Facade
+    {
+        let format = try!(to_srgb(self.format));
+        let any = try!(self.into_texture(facade, format, true));
+        Ok(CompressedSrgbTexture2dArray(any))
+    }
+
+    fn into_texture<F>(self, facade: &F, format: TextureFormat, array: bool)
+                       -> Result<any::TextureAny, ContainerError> where F: Facade
+    {
+        let array_size = match (self.array_size, array) {
+            (None, false) | (Some(1), false) => None,
+            (Some(_), false) => {
+                return Err(ContainerError::UnsupportedFeature("the image is an array of \
+                                                               textures".to_string()));
+            },
+            (array_size, true) => Some(array_size.unwrap_or(1)),
+        };
+
+        let levels = self.levels.iter().map(|l| &l[..]).collect::<Vec<_>>();
+        let texture = try!(any::new_compressed_texture(facade, format, &levels, self.width,
+                                                       self.height, array_size));
+        Ok(texture)
+    }
+}
+
+/// Loads a DDS, KTX or KTX2 file.
+pub fn load<P>(path: P) -> Result<CompressedImage, ContainerError> where P: AsRef<Path> {
+    let mut data = Vec::new();
+    try!(try!(File::open(path.as_ref())).read_to_end(&mut data));
+    load_from_memory(&data)
+}
+
+/// Loads the content of a DDS, KTX or KTX2 file. The type of file is determined from its
+/// first bytes.
+pub fn load_from_memory(data: &[u8]) -> Result<CompressedImage, ContainerError> {
+    if data.len() >= 4 && &data[.. 4] == b"DDS " {
+        parse_dds(data)
+    } else if data.len() >= 12 && &data[.. 12] == KTX_IDENTIFIER {
+        parse_ktx(data)
+    } else if data.len() >= 12 && &data[.. 12] == KTX2_IDENTIFIER {
+        parse_ktx2(data)
+    } else {
+        Err(invalid("the file is neither a DDS nor a KTX file"))
+    }
+}
+
+const KTX_IDENTIFIER: &'static [u8] = &[0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB,
+                                        0x0D, 0x0A, 0x1A, 0x0A];
+
+const KTX2_IDENTIFIER: &'static [u8] = &[0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB,
+                                         0x0D, 0x0A, 0x1A, 0x0A];
+
+fn parse_dds(data: &[u8]) -> Result<CompressedImage, ContainerError> {
+    // the header starts after the magic number
+    let header = 4;
+
+    if try!(read_u32(data, header, false)) != 124 {
+        return Err(invalid("wrong DDS header size"));
+    }
+
+    let flags = try!(read_u32(data, header + 4, false));
+    let height = try!(read_u32(data, header + 8, false));
+    let width = try!(read_u32(data, header + 12, false));
+    let mipmap_count = try!(read_u32(data, header + 24, false));
+    let pixel_format_flags = try!(read_u32(data, header + 76, false));
+    let four_cc = {
+        let bytes = try!(read_bytes(data, header + 80, 4));
+        [bytes[0], bytes[1], bytes[2], bytes[3]]
+    };
+    let caps2 = try!(read_u32(data, header + 108, false));
+
+    // DDSCAPS2_CUBEMAP and DDSCAPS2_VOLUME
+    if caps2 & 0x200 != 0 {
+        return Err(unsupported("cubemaps are not supported"));
+    }
+    if caps2 & 0x200000 != 0 {
+        return Err(unsupported("3D textures are not supported"));
+    }
+
+    // DDPF_FOURCC
+    if pixel_format_flags & 0x4 == 0 {
+        return Err(unsupported("uncompressed DDS files are not supported"));
+    }
+
+    // DDSD_MIPMAPCOUNT
+    let levels = if flags & 0x20000 != 0 { cmp::max(1, mipmap_count) } else { 1 };
+
+    let mut offset = header + 124;
+
+    let (format, array_size) = match &four_cc {
+        b"DXT1" => {
+            // DDPF_ALPHAPIXELS
+            if pixel_format_flags & 0x1 != 0 {
+                (TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha), None)
+            } else {
+                (TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha), None)
+            }
+        },
+        b"DXT2" | b"DXT3" => {
+            (TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha), None)
+        },
+        b"DXT4" | b"DXT5" => {
+            (TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha), None)
+        },
+        b"ATI1" | b"BC4U" => (TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU), None),
+        b"BC4S" => (TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatI), None),
+        b"ATI2" | b"BC5U" => {
+            (TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatUU), None)
+        },
+        b"BC5S" => (TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatII), None),
+        b"DX10" => {
+            let dxgi_format = try!(read_u32(data, offset, false));
+            let resource_dimension = try!(read_u32(data, offset + 4, false));
+            let misc_flag = try!(read_u32(data, offset + 8, false));
+            let array_size = try!(read_u32(data, offset + 12, false));
+            offset += 20;
+
+            // D3D10_RESOURCE_DIMENSION_TEXTURE2D
+            if resource_dimension != 3 {
+                return Err(unsupported("only 2D textures are supported"));
+            }
+
+            // D3D10_RESOURCE_MISC_TEXTURECUBE
+            if misc_flag & 0x4 != 0 {
+                return Err(unsupported("cubemaps are not supported"));
+            }
+
+            let array_size = match array_size {
+                0 => return Err(invalid("the array size is 0")),
+                1 => None,
+                a => Some(a),
+            };
+
+            (try!(dxgi_format_to_format(dxgi_format)), array_size)
+        },
+        _ => return Err(unsupported("unknown DDS format")),
+    };
+
+    // DDS files store all the mipmaps of a layer before the next layer
+    let level_sizes = try!(get_level_sizes(format, width, height, levels, array_size));
+    let mut result = level_sizes.iter().map(|_| Vec::new()).collect::<Vec<Vec<u8>>>();
+
+    for _ in (0 .. array_size.unwrap_or(1)) {
+        for (level, &size) in level_sizes.iter().enumerate() {
+            result[level].extend(try!(read_bytes(data, offset, size)).iter().cloned());
+            offset += size;
+        }
+    }
+
+    Ok(CompressedImage {
+        format: format,
+        width: width,
+        height: height,
+        array_size: array_size,
+        levels: result,
+    })
+}
+
+fn parse_ktx(data: &[u8]) -> Result<CompressedImage, ContainerError> {
+    let swap = match try!(read_u32(data, 12, false)) {
+        0x04030201 => false,
+        0x01020304 => true,
+        _ => return Err(invalid("wrong endianness marker")),
+    };
+
+    let gl_type = try!(read_u32(data, 16, swap));
+    let gl_internal_format = try!(read_u32(data, 28, swap));
+    let width = try!(read_u32(data, 36, swap));
+    let height = try!(read_u32(data, 40, swap));
+    let depth = try!(read_u32(data, 44, swap));
+    let array_elements = try!(read_u32(data, 48, swap));
+    let faces = try!(read_u32(data, 52, swap));
+    let levels = cmp::max(1, try!(read_u32(data, 56, swap)));
+    let key_value_data = try!(read_u32(data, 60, swap));
+
+    if gl_type != 0 {
+        return Err(unsupported("uncompressed KTX files are not supported"));
+    }
+    if height == 0 || depth != 0 {
+        return Err(unsupported("only 2D textures are supported"));
+    }
+    if faces != 1 {
+        return Err(unsupported("cubemaps are not supported"));
+    }
+
+    let format = match image_format::compressed_glenum_to_format(gl_internal_format) {
+        Some(f) => f,
+        None => return Err(unsupported("unsupported KTX internal format")),
+    };
+    let array_size = if array_elements == 0 { None } else { Some(array_elements) };
+
+    let level_sizes = try!(get_level_sizes(format, width, height, levels, array_size));
+    let mut offset = match (key_value_data as usize).checked_add(64) {
+        Some(o) => o,
+        None => return Err(invalid("unexpected end of file")),
+    };
+    let mut result = Vec::with_capacity(level_sizes.len());
+
+    for &size in level_sizes.iter() {
+        let size = size * array_size.unwrap_or(1) as usize;
+
+        if try!(read_u32(data, offset, swap)) as usize != size {
+            return Err(invalid("wrong mipmap size"));
+        }
+
+        result.push(try!(read_bytes(data, offset + 4, size)).to_vec());
+
+        // each mipmap is padded to a multiple of four bytes
+        offset += 4 + (size + 3) / 4 * 4;
+    }
+
+    Ok(CompressedImage {
+        format: format,
+        width: width,
+        height: height,
+        array_size: array_size,
+        levels: result,
+    })
+}
+
+fn parse_ktx2(data: &[u8]) -> Result<CompressedImage, ContainerError> {
+    let vk_format = try!(read_u32(data, 12, false));
+    let width = try!(read_u32(data, 20, false));
+    let height = try!(read_u32(data, 24, false));
+    let depth = try!(read_u32(data, 28, false));
+    let layers = try!(read_u32(data, 32, false));
+    let faces = try!(read_u32(data, 36, false));
+    let levels = cmp::max(1, try!(read_u32(data, 40, false)));
+    let supercompression = try!(read_u32(data, 44, false));
+
+    if supercompression != 0 {
+        return Err(unsupported("supercompressed KTX2 files are not supported"));
+    }
+    if height == 0 || depth != 0 {
+        return Err(unsupported("only 2D textures are supported"));
+    }
+    if faces != 1 {
+        return Err(unsupported("cubemaps are not supported"));
+    }
+
+    let format = try!(vk_format_to_format(vk_format));
+    let array_size = if layers == 0 { None } else { Some(layers) };
+
+    let level_sizes = try!(get_level_sizes(format, width, height, levels, array_size));
+    let mut result = Vec::with_capacity(level_sizes.len());
+
+    // the level index follows the 80 bytes of the header and of the section index
+    for (level, &size) in level_sizes.iter().enumerate() {
+        let size = size * array_size.unwrap_or(1) as usize;
+
+        let byte_offset = try!(read_u64(data, 80 + level * 24));
+        let byte_length = try!(read_u64(data, 80 + level * 24 + 8));
+
+        if byte_length != size as u64 {
+            return Err(invalid("wrong mipmap size"));
+        }
+
+        if byte_offset > usize::MAX as u64 {
+            return Err(invalid("unexpected end of file"));
+        }
+
+        result.push(try!(read_bytes(data, byte_offset as usize, size)).to_vec());
+    }
+
+    Ok(CompressedImage {
+        format: format,
+        width: width,
+        height: height,
+        array_size: array_size,
+        levels: result,
+    })
+}
+
+/// Returns the size in bytes of one layer of each mipmap.
+///
+/// Returns an error if the size of all the layers of all the mipmaps doesn't fit in a `usize`,
+/// so that the callers can multiply the returned sizes by `array_size` without overflowing.
+fn get_level_sizes(format: TextureFormat, width: u32, height: u32, levels: u32,
+                   array_size: Option<u32>) -> Result<Vec<usize>, ContainerError>
+{
+    if width == 0 || height == 0 {
+        return Err(invalid("the image is empty"));
+    }
+
+    // the last mipmap of the chain is 1x1
+    if levels > 32 - cmp::max(width, height).leading_zeros() {
+        return Err(invalid("too many mipmaps"));
+    }
+
+    let (block_width, block_height, block_size) =
+        image_format::get_compressed_block_size(format).unwrap();
+
+    let mut total: usize = 0;
+    let mut result = Vec::with_capacity(levels as usize);
+
+    for level in (0 .. levels) {
+        let width = cmp::max(1, width >> level);
+        let height = cmp::max(1, height >> level);
+
+        // written this way to avoid overflowing when the dimensions are close to the maximum
+        let blocks_x = width / block_width + if width % block_width != 0 { 1 } else { 0 };
+        let blocks_y = height / block_height + if height % block_height != 0 { 1 } else { 0 };
+
+        let size = (blocks_x as usize).checked_mul(blocks_y as usize)
+                                      .and_then(|s| s.checked_mul(block_size));
+        let size = match size {
+            Some(s) => s,
+            None => return Err(invalid("the mipmaps are too large")),
+        };
+
+        total = match size.checked_mul(array_size.unwrap_or(1) as usize)
+                          .and_then(|s| s.checked_add(total))
+        {
+            Some(t) => t,
+            None => return Err(invalid("the mipmaps are too large")),
+        };
+
+        result.push(size);
+    }
+
+    Ok(result)
+}
+
+fn dxgi_format_to_format(format: u32) -> Result<TextureFormat, ContainerError> {
+    Ok(match format {
+        70 | 71 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha),
+        72 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha),
+        73 | 74 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha),
+        75 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha),
+        76 | 77 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha),
+        78 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha),
+        79 | 80 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU),
+        81 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatI),
+        82 | 83 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatUU),
+        84 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatII),
+        94 | 95 => TextureFormat::CompressedFormat(CompressedFormat::BptcUnsignedFloat3),
+        96 => TextureFormat::CompressedFormat(CompressedFormat::BptcSignedFloat3),
+        97 | 98 => TextureFormat::CompressedFormat(CompressedFormat::BptcUnorm4),
+        99 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc),
+        _ => return Err(unsupported("unsupported DXGI format")),
+    })
+}
+
+fn vk_format_to_format(format: u32) -> Result<TextureFormat, ContainerError> {
+    Ok(match format {
+        131 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha),
+        132 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha),
+        133 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha),
+        134 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha),
+        135 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha),
+        136 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha),
+        137 => TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha),
+        138 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha),
+        139 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatU),
+        140 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatI),
+        141 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatUU),
+        142 => TextureFormat::CompressedFormat(CompressedFormat::RgtcFormatII),
+        143 => TextureFormat::CompressedFormat(CompressedFormat::BptcUnsignedFloat3),
+        144 => TextureFormat::CompressedFormat(CompressedFormat::BptcSignedFloat3),
+        145 => TextureFormat::CompressedFormat(CompressedFormat::BptcUnorm4),
+        146 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc),
+        147 => TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8),
+        148 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8),
+        149 => TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8A1),
+        150 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1),
+        151 => TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgba8),
+        152 => TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgba8),
+        153 => TextureFormat::CompressedFormat(CompressedFormat::EacR11Unsigned),
+        154 => TextureFormat::CompressedFormat(CompressedFormat::EacR11Signed),
+        155 => TextureFormat::CompressedFormat(CompressedFormat::EacRg11Unsigned),
+        156 => TextureFormat::CompressedFormat(CompressedFormat::EacRg11Signed),
+
+        // the ASTC formats are in the same order as in `ASTC_FORMATS`, the sRGB variant
+        // following the linear one
+        157 ... 184 => {
+            let index = (format as usize - 157) / 2;
+            let (linear, srgb, _, _, _, _) = image_format::ASTC_FORMATS[index];
+            if format % 2 == 1 {
+                TextureFormat::CompressedFormat(linear)
+            } else {
+                TextureFormat::CompressedSrgbFormat(srgb)
+            }
+        },
+
+        _ => return Err(unsupported("unsupported Vulkan format")),
+    })
+}
+
+/// Returns the linear equivalent of a compressed format.
+fn to_linear(format: TextureFormat) -> Result<TextureFormat, ContainerError> {
+    Ok(TextureFormat::CompressedFormat(match format {
+        TextureFormat::CompressedFormat(f) => f,
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc) => {
+            CompressedFormat::BptcUnorm4
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha) => {
+            CompressedFormat::S3tcDxt1NoAlpha
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha) => {
+            CompressedFormat::S3tcDxt1Alpha
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha) => {
+            CompressedFormat::S3tcDxt3Alpha
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha) => {
+            CompressedFormat::S3tcDxt5Alpha
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8) => {
+            CompressedFormat::Etc2Rgb8
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1) => {
+            CompressedFormat::Etc2Rgb8A1
+        },
+        TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgba8) => {
+            CompressedFormat::Etc2Rgba8
+        },
+        TextureFormat::CompressedSrgbFormat(format) => {
+            match image_format::ASTC_FORMATS.iter().find(|f| f.1 == format) {
+                Some(f) => f.0,
+                None => unreachable!(),
+            }
+        },
+        _ => return Err(unsupported("the format of the image is not compressed")),
+    }))
+}
+
+/// Returns the sRGB equivalent of a compressed format.
+fn to_srgb(format: TextureFormat) -> Result<TextureFormat, ContainerError> {
+    Ok(TextureFormat::CompressedSrgbFormat(match format {
+        TextureFormat::CompressedSrgbFormat(f) => f,
+        TextureFormat::CompressedFormat(CompressedFormat::BptcUnorm4) => {
+            CompressedSrgbFormat::Bptc
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha) => {
+            CompressedSrgbFormat::S3tcDxt1NoAlpha
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1Alpha) => {
+            CompressedSrgbFormat::S3tcDxt1Alpha
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt3Alpha) => {
+            CompressedSrgbFormat::S3tcDxt3Alpha
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha) => {
+            CompressedSrgbFormat::S3tcDxt5Alpha
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8) => {
+            CompressedSrgbFormat::Etc2Rgb8
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgb8A1) => {
+            CompressedSrgbFormat::Etc2Rgb8A1
+        },
+        TextureFormat::CompressedFormat(CompressedFormat::Etc2Rgba8) => {
+            CompressedSrgbFormat::Etc2Rgba8
+        },
+        TextureFormat::CompressedFormat(format) => {
+            match image_format::ASTC_FORMATS.iter().find(|f| f.0 == format) {
+                Some(f) => f.1,
+                None => return Err(unsupported("the format of the image has no sRGB \
+                                                equivalent")),
+            }
+        },
+        _ => return Err(unsupported("the format of the image is not compressed")),
+    }))
+}
+
+fn invalid(message: &str) -> ContainerError {
+    ContainerError::InvalidFile(message.to_string())
+}
+
+fn unsupported(message: &str) -> ContainerError {
+    ContainerError::UnsupportedFeature(message.to_string())
+}
+
+fn read_bytes(data: &[u8], offset: usize, len: usize) -> Result<&[u8], ContainerError> {
+    if offset.checked_add(len).map(|end| end > data.len()).unwrap_or(true) {
+        return Err(invalid("unexpected end of file"));
+    }
+
+    Ok(&data[offset .. offset + len])
+}
+
+fn read_u32(data: &[u8], offset: usize, swap: bool) -> Result<u32, ContainerError> {
+    let bytes = try!(read_bytes(data, offset, 4));
+    let value = bytes[0] as u32 | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16 |
+                (bytes[3] as u32) << 24;
+    Ok(if swap { value.swap_bytes() } else { value })
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, ContainerError> {
+    let low = try!(read_u32(data, offset, false)) as u64;
+    let high = try!(read_u32(data, offset + 4, false)) as u64;
+    Ok(low | high << 32)
+}
@@ -0,0 +1,389 @@
+/*!
+Conversions between client formats on the CPU.
+
+The backend doesn't necessarily accept all the client formats when uploading or reading
+textures. OpenGL ES 2.0 for example only supports a few of them. In this situation, glium
+converts the data itself with the functions of this module.
+
+All the integral components are considered to be normalized, which means that for example the
+value `255` of a `U8` component corresponds to `1.0` and the value `-127` of an `I8` component
+corresponds to `-1.0`. This includes the `U32` and `I32` components, whose values are therefore
+only exact up to 24 bits when converted to `F32`. The data of integral and unsigned textures is
+never converted, since it isn't normalized.
+
+*/
+use image_format::ClientFormat;
+
+use std::f32;
+use std::mem;
+
+/// Type of the components of a client format that is not packed.
+#[derive(Copy, Clone, Debug)]
+enum ComponentType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F16,
+    F32,
+}
+
+/// Converts pixels stored in the `from` client format to the `to` client format.
+///
+/// Multi-byte values are in the native endianness. Components that are missing from the source
+/// format are set to zero, except for alpha which is set to one. Components that don't exist in
+/// the destination format are dropped, and values outside of the range of the destination are
+/// clamped.
+///
+/// ## Panic
+///
+/// Panics if the length of `data` is not a multiple of the size of a pixel of `from`.
+pub fn convert(data: &[u8], from: ClientFormat, to: ClientFormat) -> Vec<u8> {
+    assert!(data.len() % from.get_size() == 0,
+            "The data doesn't contain a whole number of pixels");
+
+    if from == to {
+        return data.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(data.len() / from.get_size() * to.get_size());
+
+    for pixel in data.chunks(from.get_size()) {
+        let value = decode(pixel, from);
+        encode(value, to, &mut result);
+    }
+
+    result
+}
+
+/// Returns the number of bits of each component of a packed format, starting with the most
+/// significant ones.
+fn get_packed_bits(format: ClientFormat) -> Option<&'static [u32]> {
+    match format {
+        ClientFormat::U3U3U2 => Some(&[3, 3, 2]),
+        ClientFormat::U5U6U5 => Some(&[5, 6, 5]),
+        ClientFormat::U4U4U4U4 => Some(&[4, 4, 4, 4]),
+        ClientFormat::U5U5U5U1 => Some(&[5, 5, 5, 1]),
+        ClientFormat::U10U10U10U2 => Some(&[10, 10, 10, 2]),
+        _ => None
+    }
+}
+
+/// Returns the type of the components of a format that is not packed.
+fn get_component_type(format: ClientFormat) -> ComponentType {
+    match format {
+        ClientFormat::U8 | ClientFormat::U8U8 | ClientFormat::U8U8U8 |
+        ClientFormat::U8U8U8U8 => ComponentType::U8,
+        ClientFormat::I8 | ClientFormat::I8I8 | ClientFormat::I8I8I8 |
+        ClientFormat::I8I8I8I8 => ComponentType::I8,
+        ClientFormat::U16 | ClientFormat::U16U16 | ClientFormat::U16U16U16 |
+        ClientFormat::U16U16U16U16 => ComponentType::U16,
+        ClientFormat::I16 | ClientFormat::I16I16 | ClientFormat::I16I16I16 |
+        ClientFormat::I16I16I16I16 => ComponentType::I16,
+        ClientFormat::U32 | ClientFormat::U32U32 | ClientFormat::U32U32U32 |
+        ClientFormat::U32U32U32U32 => ComponentType::U32,
+        ClientFormat::I32 | ClientFormat::I32I32 | ClientFormat::I32I32I32 |
+        ClientFormat::I32I32I32I32 => ComponentType::I32,
+        ClientFormat::F16 | ClientFormat::F16F16 | ClientFormat::F16F16F16 |
+        ClientFormat::F16F16F16F16 => ComponentType::F16,
+        ClientFormat::F32 | ClientFormat::F32F32 | ClientFormat::F32F32F32 |
+        ClientFormat::F32F32F32F32 => ComponentType::F32,
+        _ => unreachable!()
+    }
+}
+
+/// Turns one pixel into its RGBA value.
+fn decode(pixel: &[u8], format: ClientFormat) -> [f64; 4] {
+    let mut value = [0.0, 0.0, 0.0, 1.0];
+
+    if let Some(bits) = get_packed_bits(format) {
+        let packed = match pixel.len() {
+            1 => pixel[0] as u32,
+            2 => read_u16(pixel) as u32,
+            4 => read_u32(pixel),
+            _ => unreachable!()
+        };
+
+        let mut shift = bits.iter().fold(0, |a, &b| a + b);
+        for (component, &bits) in value.iter_mut().zip(bits.iter()) {
+            shift -= bits;
+            let max = (1u32 << bits) - 1;
+            *component = ((packed >> shift) & max) as f64 / max as f64;
+        }
+
+    } else {
+        let ty = get_component_type(format);
+        let num_components = format.get_num_components() as usize;
+        let component_size = pixel.len() / num_components;
+
+        for (component, bytes) in value.iter_mut().zip(pixel.chunks(component_size)) {
+            *component = match ty {
+                ComponentType::U8 => bytes[0] as f64 / 255.0,
+                ComponentType::I8 => snorm_to_float(bytes[0] as i8 as f64, 127.0),
+                ComponentType::U16 => read_u16(bytes) as f64 / 65535.0,
+                ComponentType::I16 => snorm_to_float(read_u16(bytes) as i16 as f64, 32767.0),
+                ComponentType::U32 => read_u32(bytes) as f64 / 4294967295.0,
+                ComponentType::I32 => snorm_to_float(read_u32(bytes) as i32 as f64,
+                                                     2147483647.0),
+                ComponentType::F16 => f16_to_f32(read_u16(bytes)) as f64,
+                ComponentType::F32 => {
+                    unsafe { mem::transmute::<u32, f32>(read_u32(bytes)) as f64 }
+                },
+            };
+        }
+    }
+
+    value
+}
+
+/// Writes a RGBA value at the end of `output` in the given format.
+fn encode(value: [f64; 4], format: ClientFormat, output: &mut Vec<u8>) {
+    if let Some(bits) = get_packed_bits(format) {
+        let mut packed = 0u32;
+        for (&component, &bits) in value.iter().zip(bits.iter()) {
+            let max = ((1u32 << bits) - 1) as f64;
+            packed = (packed << bits) | float_to_unorm(component, max) as u32;
+        }
+
+        match format.get_size() {
+            1 => output.push(packed as u8),
+            2 => write_u16(packed as u16, output),
+            4 => write_u32(packed, output),
+            _ => unreachable!()
+        }
+
+    } else {
+        let ty = get_component_type(format);
+        let num_components = format.get_num_components() as usize;
+
+        for &component in value.iter().take(num_components) {
+            match ty {
+                ComponentType::U8 => output.push(float_to_unorm(component, 255.0) as u8),
+                ComponentType::I8 => output.push(float_to_snorm(component, 127.0) as i8 as u8),
+                ComponentType::U16 => {
+                    write_u16(float_to_unorm(component, 65535.0) as u16, output)
+                },
+                ComponentType::I16 => {
+                    write_u16(float_to_snorm(component, 32767.0) as i16 as u16, output)
+                },
+                ComponentType::U32 => {
+                    write_u32(float_to_unorm(component, 4294967295.0) as u32, output)
+                },
+                ComponentType::I32 => {
+                    write_u32(float_to_snorm(component, 2147483647.0) as i32 as u32, output)
+                },
+                ComponentType::F16 => write_u16(f32_to_f16(component as f32), output),
+                ComponentType::F32 => {
+                    write_u32(unsafe { mem::transmute::<f32, u32>(component as f32) }, output)
+                },
+            }
+        }
+    }
+}
+
+fn snorm_to_float(value: f64, max: f64) -> f64 {
+    // the minimum value is one lower than `-max` and is also mapped to -1.0
+    (value / max).max(-1.0)
+}
+
+fn float_to_unorm(value: f64, max: f64) -> f64 {
+    (value.max(0.0).min(1.0) * max).round()
+}
+
+fn float_to_snorm(value: f64, max: f64) -> f64 {
+    (value.max(-1.0).min(1.0) * max).round()
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    unsafe { mem::transmute([bytes[0], bytes[1]]) }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    unsafe { mem::transmute([bytes[0], bytes[1], bytes[2], bytes[3]]) }
+}
+
+fn write_u16(value: u16, output: &mut Vec<u8>) {
+    let bytes: [u8; 2] = unsafe { mem::transmute(value) };
+    output.extend(bytes.iter().cloned());
+}
+
+fn write_u32(value: u32, output: &mut Vec<u8>) {
+    let bytes: [u8; 4] = unsafe { mem::transmute(value) };
+    output.extend(bytes.iter().cloned());
+}
+
+/// Turns a half-precision float into a single-precision float.
+fn f16_to_f32(half: u16) -> f32 {
+    let exponent = ((half >> 10) & 0x1f) as i32;
+    let mantissa = (half & 0x3ff) as f32;
+
+    let magnitude = match exponent {
+        0 => mantissa * 2.0f32.powi(-24),
+        0x1f => if mantissa == 0.0 { f32::INFINITY } else { f32::NAN },
+        e => (1.0 + mantissa / 1024.0) * 2.0f32.powi(e - 15),
+    };
+
+    if (half & 0x8000) != 0 { -magnitude } else { magnitude }
+}
+
+/// Turns a single-precision float into the nearest half-precision float.
+fn f32_to_f16(value: f32) -> u16 {
+    let bits: u32 = unsafe { mem::transmute(value) };
+
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7fffff;
+
+    // infinity and NaN
+    if exponent == 0xff {
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let exponent = exponent - 127 + 15;
+
+    if exponent >= 0x1f {
+        // too large, becomes infinity
+        sign | 0x7c00
+
+    } else if exponent <= 0 {
+        // too small, becomes a subnormal number or zero
+        if exponent < -10 {
+            return sign;
+        }
+
+        let mantissa = mantissa | 0x800000;
+        let shift = (14 - exponent) as u32;
+        let rounding = (mantissa >> (shift - 1)) & 1;
+        sign | ((mantissa >> shift) + rounding) as u16
+
+    } else {
+        // a carry caused by the rounding correctly increments the exponent
+        let rounding = (mantissa >> 12) & 1;
+        sign | ((((exponent as u32) << 10) | (mantissa >> 13)) + rounding) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert;
+    use image_format::ClientFormat;
+    use std::f32;
+    use std::mem;
+
+    fn u16_bytes(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|&v| {
+            let bytes: [u8; 2] = unsafe { mem::transmute(v) };
+            bytes.to_vec().into_iter()
+        }).collect()
+    }
+
+    fn u32_bytes(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|&v| {
+            let bytes: [u8; 4] = unsafe { mem::transmute(v) };
+            bytes.to_vec().into_iter()
+        }).collect()
+    }
+
+    fn f32_bytes(values: &[f32]) -> Vec<u8> {
+        values.iter().flat_map(|&v| {
+            let bytes: [u8; 4] = unsafe { mem::transmute(v) };
+            bytes.to_vec().into_iter()
+        }).collect()
+    }
+
+    #[test]
+    fn same_format() {
+        let data = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(convert(&data, ClientFormat::U8U8U8, ClientFormat::U8U8U8), data);
+    }
+
+    #[test]
+    fn missing_components() {
+        let result = convert(&[12, 34], ClientFormat::U8, ClientFormat::U8U8U8U8);
+        assert_eq!(result, vec![12, 0, 0, 255, 34, 0, 0, 255]);
+    }
+
+    #[test]
+    fn dropped_components() {
+        let result = convert(&[1, 2, 3, 4], ClientFormat::U8U8U8U8, ClientFormat::U8U8);
+        assert_eq!(result, vec![1, 2]);
+    }
+
+    #[test]
+    fn unsigned_normalized() {
+        let result = convert(&[0, 255], ClientFormat::U8, ClientFormat::U16);
+        assert_eq!(result, u16_bytes(&[0, 65535]));
+
+        let result = convert(&u16_bytes(&[0, 65535]), ClientFormat::U16, ClientFormat::F32);
+        assert_eq!(result, f32_bytes(&[0.0, 1.0]));
+    }
+
+    #[test]
+    fn signed_normalized() {
+        let result = convert(&[0x80, 0x81, 0, 0x7f], ClientFormat::I8, ClientFormat::F32);
+        assert_eq!(result, f32_bytes(&[-1.0, -1.0, 0.0, 1.0]));
+
+        let result = convert(&f32_bytes(&[-2.0, 0.5]), ClientFormat::F32, ClientFormat::I8);
+        assert_eq!(result, vec![0x81, 64]);
+    }
+
+    #[test]
+    fn float_clamped() {
+        let result = convert(&f32_bytes(&[-1.0, 0.5, 3.0]), ClientFormat::F32F32F32,
+                             ClientFormat::U8U8U8);
+        assert_eq!(result, vec![0, 128, 255]);
+    }
+
+    #[test]
+    fn u5u6u5() {
+        let result = convert(&u16_bytes(&[0xf800, 0x07e0, 0x001f]), ClientFormat::U5U6U5,
+                             ClientFormat::U8U8U8);
+        assert_eq!(result, vec![255, 0, 0, 0, 255, 0, 0, 0, 255]);
+
+        let result = convert(&[255, 0, 255], ClientFormat::U8U8U8, ClientFormat::U5U6U5);
+        assert_eq!(result, u16_bytes(&[0xf81f]));
+    }
+
+    #[test]
+    fn u10u10u10u2() {
+        let packed = (1023 << 22) | (1023 << 2) | 1;
+        let result = convert(&u32_bytes(&[packed]), ClientFormat::U10U10U10U2,
+                             ClientFormat::U8U8U8U8);
+        assert_eq!(result, vec![255, 0, 255, 85]);
+
+        let result = convert(&result, ClientFormat::U8U8U8U8, ClientFormat::U10U10U10U2);
+        assert_eq!(result, u32_bytes(&[packed]));
+    }
+
+    #[test]
+    fn u3u3u2_and_u4u4u4u4() {
+        let result = convert(&[0b111_000_11], ClientFormat::U3U3U2, ClientFormat::U4U4U4U4);
+        assert_eq!(result, u16_bytes(&[0xf0ff]));
+    }
+
+    #[test]
+    fn f16() {
+        let halves = u16_bytes(&[0x3c00, 0xc000, 0x3800, 0x7bff, 0x0001, 0x7c00]);
+        let result = convert(&halves, ClientFormat::F16, ClientFormat::F32);
+        assert_eq!(result, f32_bytes(&[1.0, -2.0, 0.5, 65504.0, 0.000000059604645,
+                                       f32::INFINITY]));
+
+        let result = convert(&result, ClientFormat::F32, ClientFormat::F16);
+        assert_eq!(result, halves);
+    }
+
+    #[test]
+    fn f16_rounding() {
+        let result = convert(&f32_bytes(&[1.0009765625, 100000.0, 0.00000001]),
+                             ClientFormat::F32, ClientFormat::F16);
+        assert_eq!(result, u16_bytes(&[0x3c01, 0x7c00, 0x0000]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_length() {
+        convert(&[1, 2, 3], ClientFormat::U8U8, ClientFormat::U8);
+    }
+}
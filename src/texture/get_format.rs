@@ -131,35 +131,42 @@ pub fn get_format_if_supported(ctxt: &mut CommandContext, texture: &TextureAny)
             let bind_point = any::get_bind_point(texture);
             ctxt.gl.BindTexture(bind_point, texture.get_id());
 
+            // the faces of a cubemap must be queried individually
+            let query_target = if bind_point == gl::TEXTURE_CUBE_MAP {
+                gl::TEXTURE_CUBE_MAP_POSITIVE_X
+            } else {
+                bind_point
+            };
+
             let mut red_sz = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_RED_SIZE, &mut red_sz);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_RED_SIZE, &mut red_sz);
 
             let mut red_ty = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_RED_TYPE, &mut red_ty);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_RED_TYPE, &mut red_ty);
 
             let mut green_sz = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_GREEN_SIZE, &mut green_sz);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_GREEN_SIZE, &mut green_sz);
 
             let mut green_ty = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_GREEN_TYPE, &mut green_ty);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_GREEN_TYPE, &mut green_ty);
 
             let mut blue_sz = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_BLUE_SIZE, &mut blue_sz);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_BLUE_SIZE, &mut blue_sz);
 
             let mut blue_ty = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_BLUE_TYPE, &mut blue_ty);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_BLUE_TYPE, &mut blue_ty);
 
             let mut alpha_sz = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_ALPHA_SIZE, &mut alpha_sz);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_ALPHA_SIZE, &mut alpha_sz);
 
             let mut alpha_ty = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_ALPHA_TYPE, &mut alpha_ty);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_ALPHA_TYPE, &mut alpha_ty);
 
             let mut depth_sz = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_DEPTH_SIZE, &mut depth_sz);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_DEPTH_SIZE, &mut depth_sz);
 
             let mut depth_ty = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_DEPTH_TYPE, &mut depth_ty);
+            ctxt.gl.GetTexLevelParameteriv(query_target, 0, gl::TEXTURE_DEPTH_TYPE, &mut depth_ty);
 
             (red_sz as gl::types::GLenum, red_ty as gl::types::GLenum,
              green_sz as gl::types::GLenum, green_ty as gl::types::GLenum,
@@ -32,6 +32,10 @@ don't exist, like `DepthBufferTexture`.
 The difference between compressed textures and uncompressed textures is that you can't do
 render-to-texture on the former.
 
+Cube textures are named `Cubemap` and arrays of cube textures `CubemapArray`. Their individual
+faces are designated with the `CubeLayer` enum, and can be written, read or rendered to
+separately.
+
 The most common types of textures are `CompressedTexture2d` and `Texture2d` (the two dimensions
 being the width and height). These are what you will use most of the time.
 
@@ -59,17 +63,23 @@ use BlitTarget;
 use uniforms;
 
 use image_format::{TextureFormatRequest, FormatNotSupportedError};
+use program::ProgramCreationError;
+use DrawError;
 
 pub use image_format::{ClientFormat, TextureFormat};
 pub use image_format::{UncompressedFloatFormat, UncompressedIntFormat, UncompressedUintFormat};
 pub use image_format::{CompressedFormat, DepthFormat, DepthStencilFormat, StencilFormat};
 pub use image_format::{CompressedSrgbFormat, SrgbFormat};
-pub use self::any::{TextureAny, TextureAnyMipmap, TextureType};
+pub use self::any::{TextureAny, TextureAnyMipmap, TextureType, copy_region};
 pub use self::get_format::{InternalFormat, InternalFormatType};
 pub use self::pixel::PixelValue;
 
+pub mod container;
+pub mod convert;
+
 mod any;
 mod get_format;
+mod multisample;
 mod pixel;
 
 include!(concat!(env!("OUT_DIR"), "/textures.rs"));
@@ -331,6 +341,100 @@ impl Texture2dDataSink<(u8, u8, u8, u8)> for image::DynamicImage {
     }
 }
 
+/// Content of a texture read with `into_image`.
+///
+/// The pixel type is chosen from the internal format of the texture, so that 16-bits and
+/// floating-point data are not truncated to 8 bits. The name of each variant is the layout of
+/// the pixels followed by the type of their components.
+#[cfg(feature = "image")]
+pub enum TextureImage {
+    /// One 8-bits unsigned normalized component.
+    Luma8(image::ImageBuffer<image::Luma<u8>, Vec<u8>>),
+    /// Two 8-bits unsigned normalized components.
+    LumaA8(image::ImageBuffer<image::LumaA<u8>, Vec<u8>>),
+    /// Three 8-bits unsigned normalized components.
+    Rgb8(image::ImageBuffer<image::Rgb<u8>, Vec<u8>>),
+    /// Four 8-bits unsigned normalized components.
+    Rgba8(image::ImageBuffer<image::Rgba<u8>, Vec<u8>>),
+    /// One 16-bits unsigned normalized component.
+    Luma16(image::ImageBuffer<image::Luma<u16>, Vec<u16>>),
+    /// Two 16-bits unsigned normalized components.
+    LumaA16(image::ImageBuffer<image::LumaA<u16>, Vec<u16>>),
+    /// Three 16-bits unsigned normalized components.
+    Rgb16(image::ImageBuffer<image::Rgb<u16>, Vec<u16>>),
+    /// Four 16-bits unsigned normalized components.
+    Rgba16(image::ImageBuffer<image::Rgba<u16>, Vec<u16>>),
+    /// One floating-point component. Also used for depth textures.
+    LumaF32(image::ImageBuffer<image::Luma<f32>, Vec<f32>>),
+    /// Two floating-point components.
+    LumaAF32(image::ImageBuffer<image::LumaA<f32>, Vec<f32>>),
+    /// Three floating-point components.
+    RgbF32(image::ImageBuffer<image::Rgb<f32>, Vec<f32>>),
+    /// Four floating-point components.
+    RgbaF32(image::ImageBuffer<image::Rgba<f32>, Vec<f32>>),
+    /// One signed integral component.
+    LumaI32(image::ImageBuffer<image::Luma<i32>, Vec<i32>>),
+    /// Two signed integral components.
+    LumaAI32(image::ImageBuffer<image::LumaA<i32>, Vec<i32>>),
+    /// Three signed integral components.
+    RgbI32(image::ImageBuffer<image::Rgb<i32>, Vec<i32>>),
+    /// Four signed integral components.
+    RgbaI32(image::ImageBuffer<image::Rgba<i32>, Vec<i32>>),
+    /// One unsigned integral component.
+    LumaU32(image::ImageBuffer<image::Luma<u32>, Vec<u32>>),
+    /// Two unsigned integral components.
+    LumaAU32(image::ImageBuffer<image::LumaA<u32>, Vec<u32>>),
+    /// Three unsigned integral components.
+    RgbU32(image::ImageBuffer<image::Rgb<u32>, Vec<u32>>),
+    /// Four unsigned integral components.
+    RgbaU32(image::ImageBuffer<image::Rgba<u32>, Vec<u32>>),
+}
+
+#[cfg(feature = "image")]
+impl TextureImage {
+    /// Returns the width and height of the image.
+    pub fn dimensions(&self) -> (u32, u32) {
+        use image::GenericImage;
+
+        match self {
+            &TextureImage::Luma8(ref i) => i.dimensions(),
+            &TextureImage::LumaA8(ref i) => i.dimensions(),
+            &TextureImage::Rgb8(ref i) => i.dimensions(),
+            &TextureImage::Rgba8(ref i) => i.dimensions(),
+            &TextureImage::Luma16(ref i) => i.dimensions(),
+            &TextureImage::LumaA16(ref i) => i.dimensions(),
+            &TextureImage::Rgb16(ref i) => i.dimensions(),
+            &TextureImage::Rgba16(ref i) => i.dimensions(),
+            &TextureImage::LumaF32(ref i) => i.dimensions(),
+            &TextureImage::LumaAF32(ref i) => i.dimensions(),
+            &TextureImage::RgbF32(ref i) => i.dimensions(),
+            &TextureImage::RgbaF32(ref i) => i.dimensions(),
+            &TextureImage::LumaI32(ref i) => i.dimensions(),
+            &TextureImage::LumaAI32(ref i) => i.dimensions(),
+            &TextureImage::RgbI32(ref i) => i.dimensions(),
+            &TextureImage::RgbaI32(ref i) => i.dimensions(),
+            &TextureImage::LumaU32(ref i) => i.dimensions(),
+            &TextureImage::LumaAU32(ref i) => i.dimensions(),
+            &TextureImage::RgbU32(ref i) => i.dimensions(),
+            &TextureImage::RgbaU32(ref i) => i.dimensions(),
+        }
+    }
+
+    /// Turns the image into an `image::DynamicImage`.
+    ///
+    /// Returns `None` if the pixels don't have 8 bits per component, since `DynamicImage`
+    /// can't represent them.
+    pub fn into_dynamic_image(self) -> Option<image::DynamicImage> {
+        match self {
+            TextureImage::Luma8(i) => Some(image::DynamicImage::ImageLuma8(i)),
+            TextureImage::LumaA8(i) => Some(image::DynamicImage::ImageLumaA8(i)),
+            TextureImage::Rgb8(i) => Some(image::DynamicImage::ImageRgb8(i)),
+            TextureImage::Rgba8(i) => Some(image::DynamicImage::ImageRgba8(i)),
+            _ => None
+        }
+    }
+}
+
 /// Trait that describes data for a two-dimensional texture.
 pub trait Texture3dDataSource<'a> {
     /// The type of each pixel.
@@ -431,8 +535,10 @@ impl<'a, P: PixelValue + Clone> Texture3dDataSource<'a> for RawImage3d<'a, P> {
 }
 
 impl<P> Texture3dDataSink<P> for Vec<Vec<Vec<P>>> where P: Copy + Clone {
-    fn from_raw(_data: Cow<[P]>, _width: u32, _height: u32, _depth: u32) -> Self {
-        unimplemented!()
+    fn from_raw(data: Cow<[P]>, width: u32, height: u32, _depth: u32) -> Self {
+        data.chunks(width as usize * height as usize)
+            .map(|layer| layer.chunks(width as usize).map(|e| e.to_vec()).collect())
+            .collect()
     }
 }
 
@@ -501,6 +607,75 @@ impl<'a> FboAttachments for TextureSurface<'a> {
     }
 }
 
+/// Represents a face of a cubemap.
+///
+/// The faces are listed in the order in which they are stored in the texture, which is also
+/// the order in which the data of a cubemap must be passed when creating it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CubeLayer {
+    /// The face towards the positive X axis.
+    PositiveX,
+    /// The face towards the negative X axis.
+    NegativeX,
+    /// The face towards the positive Y axis.
+    PositiveY,
+    /// The face towards the negative Y axis.
+    NegativeY,
+    /// The face towards the positive Z axis.
+    PositiveZ,
+    /// The face towards the negative Z axis.
+    NegativeZ,
+}
+
+impl CubeLayer {
+    /// Returns the index of the face within its cubemap, between 0 and 5.
+    pub fn get_layer_index(&self) -> u32 {
+        match self {
+            &CubeLayer::PositiveX => 0,
+            &CubeLayer::NegativeX => 1,
+            &CubeLayer::PositiveY => 2,
+            &CubeLayer::NegativeY => 3,
+            &CubeLayer::PositiveZ => 4,
+            &CubeLayer::NegativeZ => 5,
+        }
+    }
+}
+
+/// Describes what to do about the mipmaps when creating a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MipmapsOption {
+    /// Only the main level is allocated.
+    NoMipmap,
+
+    /// The whole chain of mipmaps is allocated but not generated. The levels contain undefined
+    /// data until you write them or call `generate_mipmaps`.
+    EmptyMipmaps,
+
+    /// Same as `EmptyMipmaps`, but at most the given number of mipmaps are allocated in
+    /// addition to the main level.
+    EmptyMipmapsMax(u32),
+
+    /// The whole chain of mipmaps is allocated and generated from the main level.
+    ///
+    /// No mipmap is created if the format of the texture doesn't support generating them,
+    /// for example with compressed or depth textures.
+    AutoGeneratedMipmaps,
+
+    /// Same as `AutoGeneratedMipmaps`, but at most the given number of mipmaps are generated in
+    /// addition to the main level.
+    AutoGeneratedMipmapsMax(u32),
+}
+
+impl From<bool> for MipmapsOption {
+    fn from(mipmaps: bool) -> MipmapsOption {
+        if mipmaps {
+            MipmapsOption::AutoGeneratedMipmaps
+        } else {
+            MipmapsOption::NoMipmap
+        }
+    }
+}
+
 /// Error that can happen when creating a texture.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextureCreationError {
@@ -526,3 +701,78 @@ impl From<FormatNotSupportedError> for TextureMaybeSupportedCreationError {
         TextureMaybeSupportedCreationError::NotSupported
     }
 }
+
+/// Error that can happen when creating a view of a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureViewCreationError {
+    /// Texture views are not supported by the backend, or the storage of the texture was not
+    /// allocated in a way that allows views.
+    NotSupported,
+
+    /// The format of the view is not in the same compatibility class as the format of the
+    /// texture, or is not supported by the backend.
+    IncompatibleFormat,
+
+    /// The type of the view can't be used to view this type of texture.
+    IncompatibleTextureType,
+}
+
+/// Source of a component of the values returned when sampling a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentSwizzle {
+    /// The red component of the texture.
+    Red,
+    /// The green component of the texture.
+    Green,
+    /// The blue component of the texture.
+    Blue,
+    /// The alpha component of the texture.
+    Alpha,
+    /// Always `0`.
+    Zero,
+    /// Always `1`.
+    One,
+}
+
+/// Error that is returned if the backend doesn't support swizzling the components of textures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwizzleNotSupportedError;
+
+/// Which component of a depth-stencil texture is returned when sampling it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DepthStencilTextureMode {
+    /// The depth component is returned. Requires a `sampler*` or `sampler*Shadow` uniform.
+    Depth,
+
+    /// The stencil component is returned as an unsigned integer. Requires a `usampler*`
+    /// uniform and nearest filtering.
+    Stencil,
+}
+
+/// Error that is returned if the backend doesn't support sampling the stencil component of
+/// depth-stencil textures.
+///
+/// This requires OpenGL 4.3, OpenGL ES 3.1 or the `GL_ARB_stencil_texturing` extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StencilTexturingNotSupportedError;
+
+/// Error that is returned if the backend doesn't support generating mipmaps, or if the format
+/// of the texture doesn't allow it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MipmapsGenerationNotSupportedError;
+
+/// Error that can happen when resolving a multisample texture into a regular texture.
+#[derive(Clone, Debug)]
+pub enum ResolveError {
+    /// The two textures don't have the same internal format.
+    IncompatibleFormat,
+
+    /// The textures can't be attached to a framebuffer by the backend.
+    FramebufferNotSupported,
+
+    /// The built-in program that copies the samples couldn't be built.
+    ProgramCreationError(ProgramCreationError),
+
+    /// Drawing with the built-in program that copies the samples failed.
+    DrawError(DrawError),
+}
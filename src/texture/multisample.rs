@@ -0,0 +1,124 @@
+use index::{IndicesSource, PrimitiveType};
+use program::{Program, ProgramCreationError};
+use uniforms::{AsUniformValue, UniformsStorage};
+use vertex::EmptyVertexAttributes;
+use version::{Api, Version};
+use context::Context;
+use ContextExt;
+use ProgramExt;
+use texture::ResolveError;
+use texture::any::{self, TextureAny};
+
+use gl;
+use ops;
+
+use std::rc::Rc;
+
+/// Kind of values stored in a multisample texture.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SamplesKind {
+    Float,
+    Integral,
+    Unsigned,
+}
+
+/// Copies the value of one sample of each pixel of a multisample texture to the main level of
+/// a regular texture, by drawing with a built-in program.
+///
+/// `source` must be the uniform value of `source_texture`. Contrary to a resolve with
+/// `glBlitFramebuffer`, this works the same way for all formats. The program is built the
+/// first time a sample of this kind is copied, and is then stored in the context.
+///
+/// ## Panic
+///
+/// Panics if `sample` is out of range or if the dimensions of the two textures don't match.
+pub fn copy_sample<T>(source: T, source_texture: &TextureAny, target: &TextureAny,
+                      kind: SamplesKind, sample: u32) -> Result<(), ResolveError>
+                      where T: AsUniformValue
+{
+    assert!(sample < source_texture.get_samples().unwrap());
+    assert!(target.get_samples().is_none());
+    assert!(source_texture.get_width() == target.get_width() &&
+            source_texture.get_height() == target.get_height());
+
+    let context = source_texture.get_context();
+
+    let sampler_type = match kind {
+        SamplesKind::Float => gl::SAMPLER_2D_MULTISAMPLE,
+        SamplesKind::Integral => gl::INT_SAMPLER_2D_MULTISAMPLE,
+        SamplesKind::Unsigned => gl::UNSIGNED_INT_SAMPLER_2D_MULTISAMPLE,
+    };
+
+    let programs = context.get_copy_sample_programs();
+    if !programs.borrow().contains_key(&sampler_type) {
+        let mut new_program = match build_program(context, kind) {
+            Ok(p) => p,
+            Err(e) => return Err(ResolveError::ProgramCreationError(e)),
+        };
+
+        // the program is stored inside the context, so it must not keep it alive
+        unsafe { new_program.release_context() };
+        programs.borrow_mut().insert(sampler_type, new_program);
+    }
+
+    let programs = programs.borrow();
+    let program = programs.get(&sampler_type).unwrap();
+
+    let uniforms = UniformsStorage::new("source", source)
+                                   .add("sample_index", sample as i32);
+
+    let (attachments, _) = any::main_level_attachments(target);
+    let attachments = match attachments.validate() {
+        Ok(attachments) => attachments,
+        Err(_) => return Err(ResolveError::FramebufferNotSupported),
+    };
+
+    let dimensions = (target.get_width(), target.get_height().unwrap_or(1));
+
+    match ops::draw(context, Some(&attachments), EmptyVertexAttributes { len: 4 },
+                    IndicesSource::NoIndices { primitives: PrimitiveType::TriangleStrip },
+                    program, &uniforms, &Default::default(), dimensions)
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(ResolveError::DrawError(e)),
+    }
+}
+
+/// Builds the program that copies one sample of each pixel of a multisample texture.
+fn build_program(context: &Rc<Context>, kind: SamplesKind)
+                 -> Result<Program, ProgramCreationError>
+{
+    // multisample textures require either OpenGL 3.2 or OpenGL ES 3.1, so one of these
+    // versions of GLSL is always available
+    let header = if context.get_version() >= &Version(Api::GlEs, 3, 1) {
+        "#version 310 es\nprecision highp float;\nprecision highp int;\n"
+    } else {
+        "#version 150\n"
+    };
+
+    let prefix = match kind {
+        SamplesKind::Float => "",
+        SamplesKind::Integral => "i",
+        SamplesKind::Unsigned => "u",
+    };
+
+    // drawing a triangle strip that covers the whole viewport, without any vertex attribute
+    let vertex_shader = format!("{}
+        void main() {{
+            vec2 position = vec2(float(gl_VertexID % 2), float(gl_VertexID / 2));
+            gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+        }}
+    ", header);
+
+    let fragment_shader = format!("{header}
+        uniform highp {prefix}sampler2DMS source;
+        uniform int sample_index;
+        out {prefix}vec4 color;
+
+        void main() {{
+            color = texelFetch(source, ivec2(gl_FragCoord.xy), sample_index);
+        }}
+    ", header = header, prefix = prefix);
+
+    Program::from_source(context, &vertex_shader, &fragment_shader, None)
+}
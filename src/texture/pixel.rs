@@ -189,6 +189,34 @@ unsafe impl PixelValue for image::Rgba<u8> {
     }
 }
 
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgb<u16> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U16U16U16
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgba<u16> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U16U16U16U16
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgb<f32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::F32F32F32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgba<f32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::F32F32F32F32
+    }
+}
+
 #[cfg(feature = "image")]
 unsafe impl PixelValue for image::Luma<u8> {
     fn get_format() -> super::ClientFormat {
@@ -230,3 +258,59 @@ unsafe impl PixelValue for image::LumaA<f32> {
         super::ClientFormat::F32F32
     }
 }
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Luma<i32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::I32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Luma<u32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::LumaA<i32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::I32I32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::LumaA<u32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U32U32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgb<i32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::I32I32I32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgb<u32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U32U32U32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgba<i32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::I32I32I32I32
+    }
+}
+
+#[cfg(feature = "image")]
+unsafe impl PixelValue for image::Rgba<u32> {
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U32U32U32U32
+    }
+}
@@ -0,0 +1,429 @@
+use ToGlEnum;
+use gl;
+
+use texture::{TextureAny, TextureAnyMipmap, TextureType};
+use uniforms::{AsUniformValue, UniformValue, UniformType};
+
+/// How a shader is allowed to access an image unit.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ImageUnitAccess {
+    /// The shader can only load from the image.
+    Read,
+
+    /// The shader can only store to the image.
+    Write,
+
+    /// The shader can both load from and store to the image.
+    ReadWrite,
+}
+
+impl ToGlEnum for ImageUnitAccess {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ImageUnitAccess::Read => gl::READ_ONLY,
+            ImageUnitAccess::Write => gl::WRITE_ONLY,
+            ImageUnitAccess::ReadWrite => gl::READ_WRITE,
+        }
+    }
+}
+
+/// Format in which the texels of an image are loaded and stored by a shader.
+///
+/// This must be the same as the format in the `layout` qualifier of the image uniform in the
+/// shader, and must have the same size as the internal format of the texture.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ImageUnitFormat {
+    RGBA32F,
+    RGBA16F,
+    RG32F,
+    RG16F,
+    R11FG11FB10F,
+    R32F,
+    R16F,
+    RGBA16,
+    RGB10A2,
+    RGBA8,
+    RG16,
+    RG8,
+    R16,
+    R8,
+    RGBA16SNorm,
+    RGBA8SNorm,
+    RG16SNorm,
+    RG8SNorm,
+    R16SNorm,
+    R8SNorm,
+    RGBA32I,
+    RGBA16I,
+    RGBA8I,
+    RG32I,
+    RG16I,
+    RG8I,
+    R32I,
+    R16I,
+    R8I,
+    RGBA32UI,
+    RGBA16UI,
+    RGB10A2UI,
+    RGBA8UI,
+    RG32UI,
+    RG16UI,
+    RG8UI,
+    R32UI,
+    R16UI,
+    R8UI,
+}
+
+impl ImageUnitFormat {
+    /// Returns the kind of data, which determines the type of the image uniform.
+    fn get_kind(&self) -> FormatKind {
+        match *self {
+            ImageUnitFormat::RGBA32I | ImageUnitFormat::RGBA16I | ImageUnitFormat::RGBA8I |
+            ImageUnitFormat::RG32I | ImageUnitFormat::RG16I | ImageUnitFormat::RG8I |
+            ImageUnitFormat::R32I | ImageUnitFormat::R16I | ImageUnitFormat::R8I => {
+                FormatKind::Integral
+            },
+
+            ImageUnitFormat::RGBA32UI | ImageUnitFormat::RGBA16UI | ImageUnitFormat::RGB10A2UI |
+            ImageUnitFormat::RGBA8UI | ImageUnitFormat::RG32UI | ImageUnitFormat::RG16UI |
+            ImageUnitFormat::RG8UI | ImageUnitFormat::R32UI | ImageUnitFormat::R16UI |
+            ImageUnitFormat::R8UI => FormatKind::Unsigned,
+
+            _ => FormatKind::Float,
+        }
+    }
+}
+
+impl ToGlEnum for ImageUnitFormat {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ImageUnitFormat::RGBA32F => gl::RGBA32F,
+            ImageUnitFormat::RGBA16F => gl::RGBA16F,
+            ImageUnitFormat::RG32F => gl::RG32F,
+            ImageUnitFormat::RG16F => gl::RG16F,
+            ImageUnitFormat::R11FG11FB10F => gl::R11F_G11F_B10F,
+            ImageUnitFormat::R32F => gl::R32F,
+            ImageUnitFormat::R16F => gl::R16F,
+            ImageUnitFormat::RGBA16 => gl::RGBA16,
+            ImageUnitFormat::RGB10A2 => gl::RGB10_A2,
+            ImageUnitFormat::RGBA8 => gl::RGBA8,
+            ImageUnitFormat::RG16 => gl::RG16,
+            ImageUnitFormat::RG8 => gl::RG8,
+            ImageUnitFormat::R16 => gl::R16,
+            ImageUnitFormat::R8 => gl::R8,
+            ImageUnitFormat::RGBA16SNorm => gl::RGBA16_SNORM,
+            ImageUnitFormat::RGBA8SNorm => gl::RGBA8_SNORM,
+            ImageUnitFormat::RG16SNorm => gl::RG16_SNORM,
+            ImageUnitFormat::RG8SNorm => gl::RG8_SNORM,
+            ImageUnitFormat::R16SNorm => gl::R16_SNORM,
+            ImageUnitFormat::R8SNorm => gl::R8_SNORM,
+            ImageUnitFormat::RGBA32I => gl::RGBA32I,
+            ImageUnitFormat::RGBA16I => gl::RGBA16I,
+            ImageUnitFormat::RGBA8I => gl::RGBA8I,
+            ImageUnitFormat::RG32I => gl::RG32I,
+            ImageUnitFormat::RG16I => gl::RG16I,
+            ImageUnitFormat::RG8I => gl::RG8I,
+            ImageUnitFormat::R32I => gl::R32I,
+            ImageUnitFormat::R16I => gl::R16I,
+            ImageUnitFormat::R8I => gl::R8I,
+            ImageUnitFormat::RGBA32UI => gl::RGBA32UI,
+            ImageUnitFormat::RGBA16UI => gl::RGBA16UI,
+            ImageUnitFormat::RGB10A2UI => gl::RGB10_A2UI,
+            ImageUnitFormat::RGBA8UI => gl::RGBA8UI,
+            ImageUnitFormat::RG32UI => gl::RG32UI,
+            ImageUnitFormat::RG16UI => gl::RG16UI,
+            ImageUnitFormat::RG8UI => gl::RG8UI,
+            ImageUnitFormat::R32UI => gl::R32UI,
+            ImageUnitFormat::R16UI => gl::R16UI,
+            ImageUnitFormat::R8UI => gl::R8UI,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum FormatKind {
+    Float,
+    Integral,
+    Unsigned,
+}
+
+/// A mipmap of a texture to bind to an image unit, so that a shader can load from and store to
+/// it with `imageLoad` and `imageStore`.
+///
+/// By default, the whole main mipmap level is bound with read and write access. Arrays, 3D
+/// textures and cubemaps are bound with all their layers, unless `layer` is used.
+///
+/// Stores done by a shader are not guaranteed to be visible to later commands. Call
+/// `memory_barrier` on the context before using the content of the texture.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+/// use glium::uniforms::{ImageUnit, ImageUnitAccess, ImageUnitFormat};
+///
+/// let uniforms = uniform! {
+///     output: ImageUnit::new(&texture, ImageUnitFormat::RGBA8).access(ImageUnitAccess::Write)
+/// };
+/// # }
+/// ```
+#[derive(Copy, Clone)]
+pub struct ImageUnit<'t> {
+    texture: &'t TextureAny,
+    level: u32,
+    layer: Option<u32>,
+    access: ImageUnitAccess,
+    format: ImageUnitFormat,
+}
+
+impl<'t> ImageUnit<'t> {
+    /// Builds a new `ImageUnit` that binds the main mipmap level of a texture.
+    pub fn new(texture: &'t TextureAny, format: ImageUnitFormat) -> ImageUnit<'t> {
+        ImageUnit {
+            texture: texture,
+            level: 0,
+            layer: None,
+            access: ImageUnitAccess::ReadWrite,
+            format: format,
+        }
+    }
+
+    /// Builds a new `ImageUnit` that binds a single layer of a mipmap level.
+    ///
+    /// For cubemaps, the layer designates a face.
+    pub fn from_mipmap(mipmap: TextureAnyMipmap<'t>, format: ImageUnitFormat) -> ImageUnit<'t> {
+        ImageUnit {
+            texture: mipmap.get_texture(),
+            level: mipmap.get_level(),
+            layer: Some(mipmap.get_layer()),
+            access: ImageUnitAccess::ReadWrite,
+            format: format,
+        }
+    }
+
+    /// Changes the mipmap level to bind.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the level is out of range.
+    pub fn level(mut self, level: u32) -> ImageUnit<'t> {
+        assert!(level < self.texture.get_mipmap_levels());
+        self.level = level;
+        self
+    }
+
+    /// Changes the layer to bind. `None` binds all the layers.
+    pub fn layer(mut self, layer: Option<u32>) -> ImageUnit<'t> {
+        self.layer = layer;
+        self
+    }
+
+    /// Changes the way the shader is allowed to access the image.
+    pub fn access(mut self, access: ImageUnitAccess) -> ImageUnit<'t> {
+        self.access = access;
+        self
+    }
+
+    /// Returns the texture.
+    pub fn get_texture(&self) -> &'t TextureAny {
+        self.texture
+    }
+
+    /// Returns the mipmap level.
+    pub fn get_level(&self) -> u32 {
+        self.level
+    }
+
+    /// Returns the layer, or `None` if all the layers are bound.
+    pub fn get_layer(&self) -> Option<u32> {
+        self.layer
+    }
+
+    /// Returns the access of the shader.
+    pub fn get_access(&self) -> ImageUnitAccess {
+        self.access
+    }
+
+    /// Returns the format of the image.
+    pub fn get_format(&self) -> ImageUnitFormat {
+        self.format
+    }
+}
+
+impl<'t> AsUniformValue for ImageUnit<'t> {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Image(*self)
+    }
+
+    fn matches(_: &UniformType) -> bool {
+        false
+    }
+}
+
+/// Returns true if the image unit can be bound to a uniform of the given type.
+pub fn is_usable_with(unit: &ImageUnit, ty: &UniformType) -> bool {
+    // binding a single layer of a layered texture gives a non-layered image
+    let dimensions = match (unit.texture.get_texture_type(), unit.layer) {
+        (TextureType::Texture1d, _) => ImageDimensions::Image1d,
+        (TextureType::Texture1dArray, None) => ImageDimensions::Image1dArray,
+        (TextureType::Texture1dArray, Some(_)) => ImageDimensions::Image1d,
+        (TextureType::Texture2d, _) => ImageDimensions::Image2d,
+        (TextureType::Texture2dArray, None) => ImageDimensions::Image2dArray,
+        (TextureType::Texture2dArray, Some(_)) => ImageDimensions::Image2d,
+        (TextureType::Texture2dMultisample, _) => ImageDimensions::Image2dMultisample,
+        (TextureType::Texture2dMultisampleArray, None) => {
+            ImageDimensions::Image2dMultisampleArray
+        },
+        (TextureType::Texture2dMultisampleArray, Some(_)) => ImageDimensions::Image2dMultisample,
+        (TextureType::Texture3d, None) => ImageDimensions::Image3d,
+        (TextureType::Texture3d, Some(_)) => ImageDimensions::Image2d,
+        (TextureType::Cubemap, None) => ImageDimensions::ImageCube,
+        (TextureType::Cubemap, Some(_)) => ImageDimensions::Image2d,
+        (TextureType::CubemapArray, None) => return false,
+        (TextureType::CubemapArray, Some(_)) => ImageDimensions::Image2d,
+        (TextureType::Rectangle, _) => ImageDimensions::Image2dRect,
+    };
+
+    let expected = match (dimensions, unit.format.get_kind()) {
+        (ImageDimensions::Image1d, FormatKind::Float) => UniformType::Image1d,
+        (ImageDimensions::Image1d, FormatKind::Integral) => UniformType::IImage1d,
+        (ImageDimensions::Image1d, FormatKind::Unsigned) => UniformType::UImage1d,
+        (ImageDimensions::Image2d, FormatKind::Float) => UniformType::Image2d,
+        (ImageDimensions::Image2d, FormatKind::Integral) => UniformType::IImage2d,
+        (ImageDimensions::Image2d, FormatKind::Unsigned) => UniformType::UImage2d,
+        (ImageDimensions::Image2dRect, FormatKind::Float) => UniformType::Image2dRect,
+        (ImageDimensions::Image2dRect, FormatKind::Integral) => UniformType::IImage2dRect,
+        (ImageDimensions::Image2dRect, FormatKind::Unsigned) => UniformType::UImage2dRect,
+        (ImageDimensions::Image3d, FormatKind::Float) => UniformType::Image3d,
+        (ImageDimensions::Image3d, FormatKind::Integral) => UniformType::IImage3d,
+        (ImageDimensions::Image3d, FormatKind::Unsigned) => UniformType::UImage3d,
+        (ImageDimensions::ImageCube, FormatKind::Float) => UniformType::ImageCube,
+        (ImageDimensions::ImageCube, FormatKind::Integral) => UniformType::IImageCube,
+        (ImageDimensions::ImageCube, FormatKind::Unsigned) => UniformType::UImageCube,
+        (ImageDimensions::Image1dArray, FormatKind::Float) => UniformType::Image1dArray,
+        (ImageDimensions::Image1dArray, FormatKind::Integral) => UniformType::IImage1dArray,
+        (ImageDimensions::Image1dArray, FormatKind::Unsigned) => UniformType::UImage1dArray,
+        (ImageDimensions::Image2dArray, FormatKind::Float) => UniformType::Image2dArray,
+        (ImageDimensions::Image2dArray, FormatKind::Integral) => UniformType::IImage2dArray,
+        (ImageDimensions::Image2dArray, FormatKind::Unsigned) => UniformType::UImage2dArray,
+        (ImageDimensions::Image2dMultisample, FormatKind::Float) => {
+            UniformType::Image2dMultisample
+        },
+        (ImageDimensions::Image2dMultisample, FormatKind::Integral) => {
+            UniformType::IImage2dMultisample
+        },
+        (ImageDimensions::Image2dMultisample, FormatKind::Unsigned) => {
+            UniformType::UImage2dMultisample
+        },
+        (ImageDimensions::Image2dMultisampleArray, FormatKind::Float) => {
+            UniformType::Image2dMultisampleArray
+        },
+        (ImageDimensions::Image2dMultisampleArray, FormatKind::Integral) => {
+            UniformType::IImage2dMultisampleArray
+        },
+        (ImageDimensions::Image2dMultisampleArray, FormatKind::Unsigned) => {
+            UniformType::UImage2dMultisampleArray
+        },
+    };
+
+    *ty == expected
+}
+
+#[derive(Copy, Clone)]
+enum ImageDimensions {
+    Image1d,
+    Image2d,
+    Image2dRect,
+    Image3d,
+    ImageCube,
+    Image1dArray,
+    Image2dArray,
+    Image2dMultisample,
+    Image2dMultisampleArray,
+}
+
+/// Indicates which kinds of accesses must see the stores done by the shaders before the barrier.
+///
+/// Shaders that store to images do so asynchronously. A barrier must be inserted with
+/// `memory_barrier` before using the result, for example before sampling the texture or
+/// reading it.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub struct MemoryBarrier {
+    /// Vertex attributes sourced from buffers.
+    pub vertex_attrib_array: bool,
+
+    /// Indices sourced from buffers.
+    pub element_array: bool,
+
+    /// Uniform blocks sourced from buffers.
+    pub uniform: bool,
+
+    /// Textures sampled from shaders.
+    pub texture_fetch: bool,
+
+    /// Images loaded from and stored to by shaders.
+    pub shader_image_access: bool,
+
+    /// Indirect commands sourced from buffers.
+    pub command: bool,
+
+    /// Reads and writes of pixel buffers.
+    pub pixel_buffer: bool,
+
+    /// Writes to textures and reads of textures to RAM.
+    pub texture_update: bool,
+
+    /// Reads and writes of buffers, including mapping.
+    pub buffer_update: bool,
+
+    /// Drawing to a framebuffer and reading from it.
+    pub framebuffer: bool,
+
+    /// Transform feedback buffers.
+    pub transform_feedback: bool,
+
+    /// Atomic counters sourced from buffers.
+    pub atomic_counter: bool,
+}
+
+impl MemoryBarrier {
+    /// Returns a barrier that covers all kinds of accesses.
+    pub fn all() -> MemoryBarrier {
+        MemoryBarrier {
+            vertex_attrib_array: true,
+            element_array: true,
+            uniform: true,
+            texture_fetch: true,
+            shader_image_access: true,
+            command: true,
+            pixel_buffer: true,
+            texture_update: true,
+            buffer_update: true,
+            framebuffer: true,
+            transform_feedback: true,
+            atomic_counter: true,
+        }
+    }
+}
+
+impl ToGlEnum for MemoryBarrier {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        let mut bits = 0;
+        if self.vertex_attrib_array { bits |= gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT; }
+        if self.element_array { bits |= gl::ELEMENT_ARRAY_BARRIER_BIT; }
+        if self.uniform { bits |= gl::UNIFORM_BARRIER_BIT; }
+        if self.texture_fetch { bits |= gl::TEXTURE_FETCH_BARRIER_BIT; }
+        if self.shader_image_access { bits |= gl::SHADER_IMAGE_ACCESS_BARRIER_BIT; }
+        if self.command { bits |= gl::COMMAND_BARRIER_BIT; }
+        if self.pixel_buffer { bits |= gl::PIXEL_BUFFER_BARRIER_BIT; }
+        if self.texture_update { bits |= gl::TEXTURE_UPDATE_BARRIER_BIT; }
+        if self.buffer_update { bits |= gl::BUFFER_UPDATE_BARRIER_BIT; }
+        if self.framebuffer { bits |= gl::FRAMEBUFFER_BARRIER_BIT; }
+        if self.transform_feedback { bits |= gl::TRANSFORM_FEEDBACK_BARRIER_BIT; }
+        if self.atomic_counter { bits |= gl::ATOMIC_COUNTER_BARRIER_BIT; }
+        bits
+    }
+}
@@ -91,14 +91,16 @@ let uniforms = uniform! {
 
 */
 pub use self::buffer::UniformBuffer;
+pub use self::image_unit::{ImageUnit, ImageUnitAccess, ImageUnitFormat, MemoryBarrier};
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter};
-pub use self::sampler::{Sampler, SamplerBehavior};
+pub use self::sampler::{Sampler, SamplerBehavior, DepthTextureComparison};
 pub use self::uniforms::{EmptyUniforms, UniformsStorage};
 pub use self::value::{UniformValue, AsUniformValue, UniformType};
 
 use program;
 
 mod buffer;
+mod image_unit;
 mod sampler;
 mod uniforms;
 mod value;
@@ -1,6 +1,9 @@
 use ToGlEnum;
 use gl;
 
+use std::hash::{Hash, Hasher};
+use std::mem;
+
 /// Function to use for out-of-bounds samples.
 ///
 /// This is how GL must handle samples that are outside the texture.
@@ -16,7 +19,12 @@ pub enum SamplerWrapFunction {
     Clamp,
 
     /// Same as Mirror, but only for one repetition,
-    MirrorClamp
+    MirrorClamp,
+
+    /// Samples outside of the texture have the value of the border color of the sampler.
+    ///
+    /// Drawing returns `DrawError::BorderClampNotSupported` on OpenGL ES before version 3.2.
+    BorderClamp,
 }
 
 impl ToGlEnum for SamplerWrapFunction {
@@ -26,6 +34,7 @@ impl ToGlEnum for SamplerWrapFunction {
             SamplerWrapFunction::Mirror => gl::MIRRORED_REPEAT,
             SamplerWrapFunction::Clamp => gl::CLAMP_TO_EDGE,
             SamplerWrapFunction::MirrorClamp => gl::MIRROR_CLAMP_TO_EDGE,
+            SamplerWrapFunction::BorderClamp => gl::CLAMP_TO_BORDER,
         }
     }
 }
@@ -88,6 +97,53 @@ impl ToGlEnum for MinifySamplerFilter {
     }
 }
 
+/// The function that is used to compare the value of a depth texture with the reference value
+/// passed by the shader.
+///
+/// The result of the sampling is `1.0` if the comparison passes and `0.0` otherwise, or the
+/// average of several comparisons if the texture is filtered.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DepthTextureComparison {
+    /// Passes if the reference value is inferior or equal to the value of the texture.
+    LessOrEqual,
+
+    /// Passes if the reference value is superior or equal to the value of the texture.
+    GreaterOrEqual,
+
+    /// Passes if the reference value is strictly inferior to the value of the texture.
+    Less,
+
+    /// Passes if the reference value is strictly superior to the value of the texture.
+    Greater,
+
+    /// Passes if the reference value is equal to the value of the texture.
+    Equal,
+
+    /// Passes if the reference value is different from the value of the texture.
+    NotEqual,
+
+    /// Always passes.
+    Always,
+
+    /// Never passes.
+    Never,
+}
+
+impl ToGlEnum for DepthTextureComparison {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            DepthTextureComparison::LessOrEqual => gl::LEQUAL,
+            DepthTextureComparison::GreaterOrEqual => gl::GEQUAL,
+            DepthTextureComparison::Less => gl::LESS,
+            DepthTextureComparison::Greater => gl::GREATER,
+            DepthTextureComparison::Equal => gl::EQUAL,
+            DepthTextureComparison::NotEqual => gl::NOTEQUAL,
+            DepthTextureComparison::Always => gl::ALWAYS,
+            DepthTextureComparison::Never => gl::NEVER,
+        }
+    }
+}
+
 /// A sampler.
 pub struct Sampler<'t, T: 't>(pub &'t T, pub SamplerBehavior);
 
@@ -120,12 +176,40 @@ impl<'t, T: 't> Sampler<'t, T> {
         self.1.max_anisotropy = level;
         self
     }
+
+    /// Changes the color of the border, used with `SamplerWrapFunction::BorderClamp`.
+    pub fn border_color(mut self, color: (f32, f32, f32, f32)) -> Sampler<'t, T> {
+        self.1.border_color = color;
+        self
+    }
+
+    /// Changes the lowest and the highest level of detail that can be selected.
+    pub fn lod_range(mut self, min: f32, max: f32) -> Sampler<'t, T> {
+        self.1.min_lod = min;
+        self.1.max_lod = max;
+        self
+    }
+
+    /// Changes the value that is added to the level of detail before selecting a mipmap.
+    pub fn lod_bias(mut self, bias: f32) -> Sampler<'t, T> {
+        self.1.lod_bias = bias;
+        self
+    }
+
+    /// Enables or disables the comparison of the values of a depth texture with a reference.
+    ///
+    /// This must be enabled to use a depth texture with a `sampler*Shadow` uniform, and
+    /// disabled otherwise.
+    pub fn depth_texture_comparison(mut self, comparison: Option<DepthTextureComparison>)
+                                    -> Sampler<'t, T>
+    {
+        self.1.depth_texture_comparison = comparison;
+        self
+    }
 }
 
 /// Behavior of a sampler.
-// TODO: GL_TEXTURE_BORDER_COLOR, GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD, GL_TEXTURE_LOD_BIAS,
-//       GL_TEXTURE_COMPARE_MODE, GL_TEXTURE_COMPARE_FUNC
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy)]
 pub struct SamplerBehavior {
     /// Functions to use for the X, Y, and Z coordinates.
     pub wrap_function: (SamplerWrapFunction, SamplerWrapFunction, SamplerWrapFunction),
@@ -146,6 +230,70 @@ pub struct SamplerBehavior {
     /// If you set the value to a value higher than what the hardware supports, it will
     /// be clamped.
     pub max_anisotropy: u16,
+
+    /// Color of the border, used with `SamplerWrapFunction::BorderClamp`.
+    ///
+    /// ## Compatibility
+    ///
+    /// This parameter is ignored by OpenGL ES before version 3.2.
+    pub border_color: (f32, f32, f32, f32),
+
+    /// Lowest level of detail that can be selected. A lower value means a more detailed
+    /// mipmap, `0.0` being the main level.
+    pub min_lod: f32,
+
+    /// Highest level of detail that can be selected.
+    pub max_lod: f32,
+
+    /// Value added to the level of detail before selecting the mipmap.
+    ///
+    /// ## Compatibility
+    ///
+    /// This parameter is ignored by OpenGL ES.
+    pub lod_bias: f32,
+
+    /// If `Some`, the values of a depth texture are compared with a reference value instead
+    /// of being returned. Required by `sampler*Shadow` uniforms.
+    pub depth_texture_comparison: Option<DepthTextureComparison>,
+}
+
+impl SamplerBehavior {
+    /// Returns the bits of the floating-point parameters, so that behaviors can be compared
+    /// and hashed.
+    fn get_float_bits(&self) -> [u32; 7] {
+        unsafe {
+            [
+                mem::transmute(self.border_color.0), mem::transmute(self.border_color.1),
+                mem::transmute(self.border_color.2), mem::transmute(self.border_color.3),
+                mem::transmute(self.min_lod), mem::transmute(self.max_lod),
+                mem::transmute(self.lod_bias),
+            ]
+        }
+    }
+}
+
+impl PartialEq for SamplerBehavior {
+    fn eq(&self, other: &SamplerBehavior) -> bool {
+        self.wrap_function == other.wrap_function &&
+        self.minify_filter == other.minify_filter &&
+        self.magnify_filter == other.magnify_filter &&
+        self.max_anisotropy == other.max_anisotropy &&
+        self.depth_texture_comparison == other.depth_texture_comparison &&
+        self.get_float_bits() == other.get_float_bits()
+    }
+}
+
+impl Eq for SamplerBehavior {}
+
+impl Hash for SamplerBehavior {
+    fn hash<H>(&self, state: &mut H) where H: Hasher {
+        self.wrap_function.hash(state);
+        self.minify_filter.hash(state);
+        self.magnify_filter.hash(state);
+        self.max_anisotropy.hash(state);
+        self.depth_texture_comparison.hash(state);
+        self.get_float_bits().hash(state);
+    }
 }
 
 impl Default for SamplerBehavior {
@@ -159,6 +307,11 @@ impl Default for SamplerBehavior {
             minify_filter: MinifySamplerFilter::LinearMipmapLinear,
             magnify_filter: MagnifySamplerFilter::Linear,
             max_anisotropy: 1,
+            border_color: (0.0, 0.0, 0.0, 0.0),
+            min_lod: -1000.0,
+            max_lod: 1000.0,
+            lod_bias: 0.0,
+            depth_texture_comparison: None,
         }
     }
 }
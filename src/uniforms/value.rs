@@ -2,6 +2,9 @@ use program;
 use texture;
 use uniforms::UniformBlock;
 use uniforms::SamplerBehavior;
+use texture::DepthStencilTextureMode;
+use uniforms::ImageUnit;
+use uniforms::image_unit;
 
 use buffer::BufferViewAnySlice;
 
@@ -171,6 +174,8 @@ pub enum UniformValue<'a> {
     IntegralTexture2d(&'a texture::IntegralTexture2d, Option<SamplerBehavior>),
     UnsignedTexture2d(&'a texture::UnsignedTexture2d, Option<SamplerBehavior>),
     DepthTexture2d(&'a texture::DepthTexture2d, Option<SamplerBehavior>),
+    StencilTexture2d(&'a texture::StencilTexture2d, Option<SamplerBehavior>),
+    DepthStencilTexture2d(&'a texture::DepthStencilTexture2d, Option<SamplerBehavior>),
     Texture2dMultisample(&'a texture::Texture2dMultisample, Option<SamplerBehavior>),
     SrgbTexture2dMultisample(&'a texture::SrgbTexture2dMultisample, Option<SamplerBehavior>),
     IntegralTexture2dMultisample(&'a texture::IntegralTexture2dMultisample, Option<SamplerBehavior>),
@@ -202,6 +207,36 @@ pub enum UniformValue<'a> {
     IntegralTexture2dMultisampleArray(&'a texture::IntegralTexture2dMultisampleArray, Option<SamplerBehavior>),
     UnsignedTexture2dMultisampleArray(&'a texture::UnsignedTexture2dMultisampleArray, Option<SamplerBehavior>),
     DepthTexture2dMultisampleArray(&'a texture::DepthTexture2dMultisampleArray, Option<SamplerBehavior>),
+    Cubemap(&'a texture::Cubemap, Option<SamplerBehavior>),
+    CompressedCubemap(&'a texture::CompressedCubemap, Option<SamplerBehavior>),
+    SrgbCubemap(&'a texture::SrgbCubemap, Option<SamplerBehavior>),
+    CompressedSrgbCubemap(&'a texture::CompressedSrgbCubemap, Option<SamplerBehavior>),
+    IntegralCubemap(&'a texture::IntegralCubemap, Option<SamplerBehavior>),
+    UnsignedCubemap(&'a texture::UnsignedCubemap, Option<SamplerBehavior>),
+    DepthCubemap(&'a texture::DepthCubemap, Option<SamplerBehavior>),
+    CubemapArray(&'a texture::CubemapArray, Option<SamplerBehavior>),
+    CompressedCubemapArray(&'a texture::CompressedCubemapArray, Option<SamplerBehavior>),
+    SrgbCubemapArray(&'a texture::SrgbCubemapArray, Option<SamplerBehavior>),
+    CompressedSrgbCubemapArray(&'a texture::CompressedSrgbCubemapArray, Option<SamplerBehavior>),
+    IntegralCubemapArray(&'a texture::IntegralCubemapArray, Option<SamplerBehavior>),
+    UnsignedCubemapArray(&'a texture::UnsignedCubemapArray, Option<SamplerBehavior>),
+    DepthCubemapArray(&'a texture::DepthCubemapArray, Option<SamplerBehavior>),
+    RectangleTexture(&'a texture::RectangleTexture, Option<SamplerBehavior>),
+    DepthRectangleTexture(&'a texture::DepthRectangleTexture, Option<SamplerBehavior>),
+    /// A texture bound to an image unit.
+    Image(ImageUnit<'a>),
+}
+
+/// Returns true if the sampler compares the values of a depth texture with a reference value.
+///
+/// Comparison must be enabled for `sampler*Shadow` uniforms and disabled for the others.
+fn is_comparison(sampler: &Option<SamplerBehavior>) -> bool {
+    sampler.as_ref().map(|s| s.depth_texture_comparison.is_some()).unwrap_or(false)
+}
+
+/// Returns true if sampling the depth-stencil texture returns its stencil component.
+fn is_stencil(texture: &texture::DepthStencilTexture2d) -> bool {
+    texture.get_depth_stencil_texture_mode() == DepthStencilTextureMode::Stencil
 }
 
 impl<'a> Clone for UniformValue<'a> {
@@ -229,14 +264,37 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::CompressedSrgbTexture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::IntegralTexture1d(_, _), UniformType::ISampler1d) => true,
             (&UniformValue::UnsignedTexture1d(_, _), UniformType::USampler1d) => true,
-            (&UniformValue::DepthTexture1d(_, _), UniformType::Sampler1d) => true,
+            (&UniformValue::DepthTexture1d(_, ref s), UniformType::Sampler1d) => !is_comparison(s),
+            (&UniformValue::DepthTexture1d(_, ref s),
+             UniformType::Sampler1dShadow) => is_comparison(s),
             (&UniformValue::Texture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::CompressedTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::SrgbTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::CompressedSrgbTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::IntegralTexture2d(_, _), UniformType::ISampler2d) => true,
             (&UniformValue::UnsignedTexture2d(_, _), UniformType::USampler2d) => true,
-            (&UniformValue::DepthTexture2d(_, _), UniformType::Sampler2d) => true,
+            (&UniformValue::DepthTexture2d(_, ref s), UniformType::Sampler2d) => !is_comparison(s),
+            (&UniformValue::DepthTexture2d(_, ref s),
+             UniformType::Sampler2dShadow) => is_comparison(s),
+            (&UniformValue::StencilTexture2d(_, _), UniformType::USampler2d) => true,
+            (&UniformValue::DepthStencilTexture2d(t, ref s), UniformType::Sampler2d) => {
+                !is_stencil(t) && !is_comparison(s)
+            },
+            (&UniformValue::DepthStencilTexture2d(t, ref s), UniformType::Sampler2dShadow) => {
+                !is_stencil(t) && is_comparison(s)
+            },
+            (&UniformValue::DepthStencilTexture2d(t, _), UniformType::USampler2d) => {
+                is_stencil(t)
+            },
+            (&UniformValue::Texture2dMultisample(_, _), UniformType::Sampler2dMultisample) => true,
+            (&UniformValue::SrgbTexture2dMultisample(_, _),
+             UniformType::Sampler2dMultisample) => true,
+            (&UniformValue::IntegralTexture2dMultisample(_, _),
+             UniformType::ISampler2dMultisample) => true,
+            (&UniformValue::UnsignedTexture2dMultisample(_, _),
+             UniformType::USampler2dMultisample) => true,
+            (&UniformValue::DepthTexture2dMultisample(_, _),
+             UniformType::Sampler2dMultisample) => true,
             (&UniformValue::Texture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::CompressedTexture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::SrgbTexture3d(_, _), UniformType::Sampler3d) => true,
@@ -250,14 +308,56 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::CompressedSrgbTexture1dArray(_, _), UniformType::Sampler1dArray) => true,
             (&UniformValue::IntegralTexture1dArray(_, _), UniformType::ISampler1dArray) => true,
             (&UniformValue::UnsignedTexture1dArray(_, _), UniformType::USampler1dArray) => true,
-            (&UniformValue::DepthTexture1dArray(_, _), UniformType::Sampler1dArray) => true,
+            (&UniformValue::DepthTexture1dArray(_, ref s),
+             UniformType::Sampler1dArray) => !is_comparison(s),
+            (&UniformValue::DepthTexture1dArray(_, ref s),
+             UniformType::Sampler1dArrayShadow) => is_comparison(s),
             (&UniformValue::Texture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::CompressedTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::SrgbTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::CompressedSrgbTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
             (&UniformValue::IntegralTexture2dArray(_, _), UniformType::ISampler2dArray) => true,
             (&UniformValue::UnsignedTexture2dArray(_, _), UniformType::USampler2dArray) => true,
-            (&UniformValue::DepthTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
+            (&UniformValue::DepthTexture2dArray(_, ref s),
+             UniformType::Sampler2dArray) => !is_comparison(s),
+            (&UniformValue::DepthTexture2dArray(_, ref s),
+             UniformType::Sampler2dArrayShadow) => is_comparison(s),
+            (&UniformValue::Texture2dMultisampleArray(_, _),
+             UniformType::Sampler2dMultisampleArray) => true,
+            (&UniformValue::SrgbTexture2dMultisampleArray(_, _),
+             UniformType::Sampler2dMultisampleArray) => true,
+            (&UniformValue::IntegralTexture2dMultisampleArray(_, _),
+             UniformType::ISampler2dMultisampleArray) => true,
+            (&UniformValue::UnsignedTexture2dMultisampleArray(_, _),
+             UniformType::USampler2dMultisampleArray) => true,
+            (&UniformValue::DepthTexture2dMultisampleArray(_, _),
+             UniformType::Sampler2dMultisampleArray) => true,
+            (&UniformValue::Cubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::CompressedCubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::SrgbCubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::CompressedSrgbCubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::IntegralCubemap(_, _), UniformType::ISamplerCube) => true,
+            (&UniformValue::UnsignedCubemap(_, _), UniformType::USamplerCube) => true,
+            (&UniformValue::DepthCubemap(_, ref s), UniformType::SamplerCube) => !is_comparison(s),
+            (&UniformValue::DepthCubemap(_, ref s),
+             UniformType::SamplerCubeShadow) => is_comparison(s),
+            (&UniformValue::CubemapArray(_, _), UniformType::SamplerCubeArray) => true,
+            (&UniformValue::CompressedCubemapArray(_, _), UniformType::SamplerCubeArray) => true,
+            (&UniformValue::SrgbCubemapArray(_, _), UniformType::SamplerCubeArray) => true,
+            (&UniformValue::CompressedSrgbCubemapArray(_, _),
+             UniformType::SamplerCubeArray) => true,
+            (&UniformValue::IntegralCubemapArray(_, _), UniformType::ISamplerCubeArray) => true,
+            (&UniformValue::UnsignedCubemapArray(_, _), UniformType::USamplerCubeArray) => true,
+            (&UniformValue::DepthCubemapArray(_, ref s),
+             UniformType::SamplerCubeArray) => !is_comparison(s),
+            (&UniformValue::DepthCubemapArray(_, ref s),
+             UniformType::SamplerCubeArrayShadow) => is_comparison(s),
+            (&UniformValue::RectangleTexture(_, _), UniformType::Sampler2dRect) => true,
+            (&UniformValue::DepthRectangleTexture(_, ref s),
+             UniformType::Sampler2dRect) => !is_comparison(s),
+            (&UniformValue::DepthRectangleTexture(_, ref s),
+             UniformType::Sampler2dRectShadow) => is_comparison(s),
+            (&UniformValue::Image(ref unit), ref ty) => image_unit::is_usable_with(unit, ty),
             _ => false,
         }
     }
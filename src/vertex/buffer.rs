@@ -176,14 +176,7 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     ///
     /// Returns `None` if the backend doesn't support instancing.
     pub fn per_instance_if_supported(&self) -> Option<PerInstance> {
-        // TODO: don't check this here
-        if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
-            !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
-        {
-            return None;
-        }
-
-        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings))
+        self.per_instances_if_supported(1)
     }
 
     /// Creates a marker that instructs glium to use multiple instances.
@@ -200,6 +193,43 @@ impl<T> VertexBuffer<T> where T: Send + Copy + 'static {
     pub fn per_instance(&self) -> PerInstance {
         self.per_instance_if_supported().unwrap()
     }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to advance to the
+    /// next element of this buffer only every `divisor` instances.
+    ///
+    /// For example with a divisor of `16`, the first element of the buffer is passed to
+    /// instances 0 to 15, the second element to instances 16 to 31, etc.
+    ///
+    /// Returns `None` if the backend doesn't support instancing.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `divisor` is `0`.
+    pub fn per_instances_if_supported(&self, divisor: u32) -> Option<PerInstance> {
+        assert!(divisor >= 1, "The instances divisor must be at least 1");
+
+        // TODO: don't check this here
+        if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
+            !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
+        {
+            return None;
+        }
+
+        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings, divisor))
+    }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to advance to the
+    /// next element of this buffer only every `divisor` instances.
+    ///
+    /// See `per_instances_if_supported`.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `gl_instancing` feature is enabled.
+    #[cfg(feature = "gl_instancing")]
+    pub fn per_instances(&self, divisor: u32) -> PerInstance {
+        self.per_instances_if_supported(divisor).unwrap()
+    }
 }
 
 impl<T> From<BufferView<T>> for VertexBuffer<T> where T: Vertex + Send + Copy + 'static {
@@ -229,7 +259,7 @@ impl<T> DerefMut for VertexBuffer<T> where T: Send + Copy + 'static {
 
 impl<'a, T> IntoVerticesSource<'a> for &'a VertexBuffer<T> where T: Send + Copy + 'static {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
 
@@ -249,7 +279,7 @@ impl<'a, T> DerefMut for VertexBufferSlice<'a, T> where T: Send + Copy + 'static
 
 impl<'a, T> IntoVerticesSource<'a> for VertexBufferSlice<'a, T> where T: Copy + Send + 'static {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
 
@@ -267,6 +297,29 @@ pub struct VertexBufferAny {
 }
 
 impl VertexBufferAny {
+    /// Builds a new vertex buffer from raw data.
+    ///
+    /// `elements_size` is the number of bytes between two consecutive vertices, and `bindings`
+    /// describes the layout of each vertex.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the length of `data` is not a multiple of `elements_size`.
+    ///
+    /// # Unsafety
+    ///
+    /// The bindings are not checked against the data.
+    pub unsafe fn new_raw<F>(facade: &F, data: &[u8], bindings: VertexFormat,
+                             elements_size: usize) -> VertexBufferAny
+                             where F: Facade
+    {
+        VertexBufferAny {
+            buffer: BufferViewAny::new_raw(facade, data, elements_size, BufferType::ArrayBuffer,
+                                           false).unwrap(),
+            bindings: bindings,
+        }
+    }
+
     /// Returns the number of bytes between two consecutive elements in the buffer.
     pub fn get_elements_size(&self) -> usize {
         self.buffer.get_elements_size()
@@ -296,14 +349,7 @@ impl VertexBufferAny {
     ///
     /// Returns `None` if the backend doesn't support instancing.
     pub fn per_instance_if_supported(&self) -> Option<PerInstance> {
-        // TODO: don't check this here
-        if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
-            !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
-        {
-            return None;
-        }
-
-        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings))
+        self.per_instances_if_supported(1)
     }
 
     /// Creates a marker that instructs glium to use multiple instances.
@@ -320,6 +366,43 @@ impl VertexBufferAny {
     pub fn per_instance(&self) -> PerInstance {
         self.per_instance_if_supported().unwrap()
     }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to advance to the
+    /// next element of this buffer only every `divisor` instances.
+    ///
+    /// For example with a divisor of `16`, the first element of the buffer is passed to
+    /// instances 0 to 15, the second element to instances 16 to 31, etc.
+    ///
+    /// Returns `None` if the backend doesn't support instancing.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `divisor` is `0`.
+    pub fn per_instances_if_supported(&self, divisor: u32) -> Option<PerInstance> {
+        assert!(divisor >= 1, "The instances divisor must be at least 1");
+
+        // TODO: don't check this here
+        if !(self.buffer.get_context().get_version() >= &Version(Api::Gl, 3, 3)) &&
+            !self.buffer.get_context().get_extensions().gl_arb_instanced_arrays
+        {
+            return None;
+        }
+
+        Some(PerInstance(self.buffer.as_slice_any(), &self.bindings, divisor))
+    }
+
+    /// Creates a marker that instructs glium to use multiple instances, and to advance to the
+    /// next element of this buffer only every `divisor` instances.
+    ///
+    /// See `per_instances_if_supported`.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `gl_instancing` feature is enabled.
+    #[cfg(feature = "gl_instancing")]
+    pub fn per_instances(&self, divisor: u32) -> PerInstance {
+        self.per_instances_if_supported(divisor).unwrap()
+    }
 }
 
 impl<T> From<VertexBuffer<T>> for VertexBufferAny where T: Send + Copy + 'static {
@@ -337,6 +420,6 @@ impl<T> From<BufferView<T>> for VertexBufferAny where T: Vertex + Send + Copy +
 
 impl<'a> IntoVerticesSource<'a> for &'a VertexBufferAny {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, false)
+        VerticesSource::VertexBuffer(self.buffer.as_slice_any(), &self.bindings, None)
     }
 }
@@ -69,6 +69,8 @@ Each source can be:
  - A vertex buffer where each element corresponds to an instance, by
    caling `vertex_buffer.per_instance()`.
  - The same with a slice, by calling `vertex_buffer.slice(start .. end).unwrap().per_instance()`.
+ - A vertex buffer where each element corresponds to several consecutive instances, by
+   calling `vertex_buffer.per_instances(n)`.
  - A marker indicating a number of vertex sources, with `glium::vertex::EmptyVertexAttributes`.
  - A marker indicating a number of instances, with `glium::vertex::EmptyInstanceAttributes`.
 
@@ -109,6 +111,10 @@ frame.draw((vertex_buffer.slice(6 .. 24).unwrap(), vertex_buffer2.slice(128 .. 1
 frame.draw((&vertex_buffer, vertex_buffer2.per_instance_if_supported().unwrap()), &indices,
            &program, &uniforms, &Default::default()).unwrap();
 
+// advancing to the next element of `vertex_buffer2` only every 16 instances
+frame.draw((&vertex_buffer, vertex_buffer2.per_instances_if_supported(16).unwrap()), &indices,
+           &program, &uniforms, &Default::default()).unwrap();
+
 // instancing without any per-instance attribute
 frame.draw((&vertex_buffer, glium::vertex::EmptyInstanceAttributes { len: 36 }), &indices,
            &program, &uniforms, &Default::default()).unwrap();
@@ -118,7 +124,8 @@ Note that if you use `index::EmptyIndices` as indices the length of all vertex s
 be the same, or a `DrawError::VerticesSourcesLengthMismatch` will be produced.
 
 In all situation, the length of all per-instance sources must match, or
-`DrawError::InstancesCountMismatch` will be retured.
+`DrawError::InstancesCountMismatch` will be retured. When a per-instance source uses a divisor
+of `n`, its length must be equal to the number of instances divided by `n` and rounded up.
 
 # Transform feedback
 
@@ -153,9 +160,10 @@ pub enum VerticesSource<'a> {
     ///
     /// The second parameter is the number of vertices in the buffer.
     ///
-    /// The third parameter tells whether or not this buffer is "per instance" (true) or
-    /// "per vertex" (false).
-    VertexBuffer(BufferViewAnySlice<'a>, &'a VertexFormat, bool),
+    /// The third parameter tells whether or not this buffer is "per instance" (`Some`) or
+    /// "per vertex" (`None`). If it is per instance, the value is the number of instances
+    /// that are drawn before advancing to the next element of the buffer.
+    VertexBuffer(BufferViewAnySlice<'a>, &'a VertexFormat, Option<u32>),
 
     /// A marker indicating a "phantom list of attributes".
     Marker {
@@ -204,11 +212,19 @@ impl<'a> IntoVerticesSource<'a> for EmptyInstanceAttributes {
 }
 
 /// Marker that instructs glium that the buffer is to be used per instance.
-pub struct PerInstance<'a>(BufferViewAnySlice<'a>, &'a VertexFormat);
+pub struct PerInstance<'a>(BufferViewAnySlice<'a>, &'a VertexFormat, u32);
+
+impl<'a> PerInstance<'a> {
+    /// Returns the number of instances that are drawn before advancing to the next element
+    /// of the buffer.
+    pub fn get_divisor(&self) -> u32 {
+        self.2
+    }
+}
 
 impl<'a> IntoVerticesSource<'a> for PerInstance<'a> {
     fn into_vertices_source(self) -> VerticesSource<'a> {
-        VerticesSource::VertexBuffer(self.0, self.1, true)
+        VerticesSource::VertexBuffer(self.0, self.1, Some(self.2))
     }
 }
 
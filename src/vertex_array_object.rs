@@ -19,8 +19,10 @@ use version::Version;
 /// Stores and handles vertex attributes.
 pub struct VertexAttributesSystem {
     // we maintain a list of VAOs for each vertexbuffer-indexbuffer-program association
-    // the key is a (buffers-list-with-offset, program) ; the buffers list must be sorted
-    vaos: RefCell<HashMap<(Vec<(gl::types::GLuint, usize)>, Handle), VertexArrayObject>>,
+    // the key is a (buffers-list-with-offset-and-divisor, program) ; the buffers list must
+    // be sorted
+    vaos: RefCell<HashMap<(Vec<(gl::types::GLuint, usize, Option<u32>)>, Handle),
+                          VertexArrayObject>>,
 }
 
 /// Object allowing one to bind vertex attributes to the current context.
@@ -55,7 +57,7 @@ impl VertexAttributesSystem {
     /// purge its VAOs cache.
     pub fn purge_buffer(ctxt: &mut CommandContext, id: gl::types::GLuint) {
         VertexAttributesSystem::purge_if(ctxt, |&(ref buffers, _)| {
-            buffers.iter().find(|&&(b, _)| b == id).is_some()
+            buffers.iter().find(|&&(b, _, _)| b == id).is_some()
         })
     }
 
@@ -100,7 +102,8 @@ impl VertexAttributesSystem {
 
     /// Purges VAOs that match a certain condition.
     fn purge_if<F>(ctxt: &mut CommandContext, mut condition: F)
-                   where F: FnMut(&(Vec<(gl::types::GLuint, usize)>, Handle)) -> bool
+                   where F: FnMut(&(Vec<(gl::types::GLuint, usize, Option<u32>)>, Handle))
+                                  -> bool
     {
         let mut vaos = ctxt.vertex_array_objects.vaos.borrow_mut();
 
@@ -146,9 +149,9 @@ impl<'a, 'c, 'd: 'c> Binder<'a, 'c, 'd> {
         {
             // VAOs are supported
             let mut buffers_list: Vec<_> = self.vertex_buffers.iter()
-                                                              .map(|&(v, _, o, _, _)| (v, o))
+                                                              .map(|&(v, _, o, _, d)| (v, o, d))
                                                               .collect();
-            buffers_list.push((self.element_array_buffer, 0));
+            buffers_list.push((self.element_array_buffer, 0, None));
             buffers_list.sort();
 
             let program_id = self.program.get_id();
@@ -0,0 +1,168 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::program::{ComputeShader, ComputeCommand};
+use glium::texture::UncompressedFloatFormat;
+use glium::uniforms::{ImageUnit, ImageUnitAccess, ImageUnitFormat, MemoryBarrier};
+
+mod support;
+
+fn build_fill_shader(display: &glium::Display) -> Option<ComputeShader> {
+    let shader = ComputeShader::from_source(display, "
+        #version 430
+
+        layout(local_size_x = 4, local_size_y = 2) in;
+        layout(rgba8) writeonly uniform image2D image;
+
+        void main() {
+            imageStore(image, ivec2(gl_GlobalInvocationID.xy), vec4(1.0, 0.0, 1.0, 1.0));
+        }
+    ");
+
+    // ignoring the test if compute shaders are not supported
+    match shader {
+        Ok(s) => Some(s),
+        Err(glium::ProgramCreationError::ShaderTypeNotSupported) => None,
+        Err(glium::ProgramCreationError::CompilationError(_)) => None,
+        Err(e) => panic!("{:?}", e)
+    }
+}
+
+fn build_image(display: &glium::Display) -> glium::Texture2d {
+    let image = glium::Texture2d::empty_with_format(display, UncompressedFloatFormat::U8U8U8U8,
+                                                    false, 16, 16).unwrap();
+    image.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    image
+}
+
+#[test]
+fn compute_shader_not_supported() {
+    let display = support::build_display();
+
+    if ComputeShader::is_supported(&display) {
+        return;
+    }
+
+    match ComputeShader::from_source(&display, "") {
+        Err(glium::ProgramCreationError::ShaderTypeNotSupported) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compute_shader_work_group_size() {
+    let display = support::build_display();
+
+    let shader = match build_fill_shader(&display) {
+        Some(s) => s,
+        None => return
+    };
+
+    assert_eq!(shader.get_work_group_size(), (4, 2, 1));
+    assert!(shader.get_uniform("image").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compute_shader_execute() {
+    let display = support::build_display();
+
+    let shader = match build_fill_shader(&display) {
+        Some(s) => s,
+        None => return
+    };
+
+    let image = build_image(&display);
+
+    // only the left half of the image is filled
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).access(ImageUnitAccess::Write)
+    };
+    shader.execute(&uniforms, 2, 8, 1).unwrap();
+
+    display.memory_barrier(MemoryBarrier { texture_update: true, .. Default::default() });
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.read();
+    assert_eq!(data[0][0], (255, 0, 255, 255));
+    assert_eq!(data[15][7], (255, 0, 255, 255));
+    assert_eq!(data[15][8], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compute_shader_execute_indirect() {
+    let display = support::build_display();
+
+    let shader = match build_fill_shader(&display) {
+        Some(s) => s,
+        None => return
+    };
+
+    let image = build_image(&display);
+
+    let command = ComputeCommand { num_groups_x: 4, num_groups_y: 8, num_groups_z: 1 };
+    let buffer = glium::buffer::BufferView::new(&display, &[command],
+                                                glium::buffer::BufferType::DispatchIndirectBuffer,
+                                                false).unwrap();
+
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).access(ImageUnitAccess::Write)
+    };
+    shader.execute_indirect(&uniforms, buffer.as_slice()).unwrap();
+
+    display.memory_barrier(MemoryBarrier { texture_update: true, .. Default::default() });
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.read();
+    assert_eq!(data[0][0], (255, 0, 255, 255));
+    assert_eq!(data[15][15], (255, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compute_shader_wrong_uniform_type() {
+    let display = support::build_display();
+
+    let shader = match build_fill_shader(&display) {
+        Some(s) => s,
+        None => return
+    };
+
+    let uniforms = uniform! {
+        image: 5i32
+    };
+
+    match shader.execute(&uniforms, 1, 1, 1) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compute_shader_too_many_work_groups() {
+    let display = support::build_display();
+
+    let shader = match build_fill_shader(&display) {
+        Some(s) => s,
+        None => return
+    };
+
+    let image = build_image(&display);
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).access(ImageUnitAccess::Write)
+    };
+
+    match shader.execute(&uniforms, 0xffffffff, 1, 1) {
+        Err(glium::DrawError::TooManyWorkGroups) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,156 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::CubeLayer;
+
+mod support;
+
+fn build_faces() -> Vec<Vec<Vec<(u8, u8, u8, u8)>>> {
+    (0 .. 6u8).map(|face| vec![vec![(face, 0, 0, 255); 2]; 2]).collect()
+}
+
+#[test]
+fn cubemap_creation() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Cubemap::new(&display, build_faces());
+
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+    assert_eq!(texture.get_depth(), None);
+    assert_eq!(texture.get_array_size(), None);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn cubemap_wrong_number_of_faces() {
+    let display = support::build_display();
+
+    let mut faces = build_faces();
+    faces.pop();
+
+    glium::texture::Cubemap::new(&display, faces);
+}
+
+#[test]
+fn cubemap_read_faces() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Cubemap::with_mipmaps(&display, build_faces(), false);
+
+    let faces = [CubeLayer::PositiveX, CubeLayer::NegativeX, CubeLayer::PositiveY,
+                 CubeLayer::NegativeY, CubeLayer::PositiveZ, CubeLayer::NegativeZ];
+
+    for (index, &face) in faces.iter().enumerate() {
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.main_level().face(face).read();
+        assert_eq!(data[0][0], (index as u8, 0, 0, 255));
+        assert_eq!(data[1][1], (index as u8, 0, 0, 255));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn cubemap_write_face() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Cubemap::with_mipmaps(&display, build_faces(), false);
+
+    let face = texture.main_level().face(CubeLayer::NegativeY);
+    face.write(glium::Rect { bottom: 1, left: 1, width: 1, height: 1 },
+               vec![vec![(128u8, 64u8, 32u8, 16u8)]]);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = face.read();
+    assert_eq!(data[0][0], (3, 0, 0, 255));
+    assert_eq!(data[1][1], (128, 64, 32, 16));
+
+    // the other faces are left untouched
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.main_level().face(CubeLayer::PositiveY)
+                                                  .read();
+    assert_eq!(data[1][1], (2, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn cubemap_face_as_surface() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Cubemap::empty(&display, 16);
+
+    let face = texture.main_level().face(CubeLayer::PositiveZ);
+    face.as_surface().clear_color(1.0, 0.0, 1.0, 1.0);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = face.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn cubemap_sample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform samplerCube texture;
+
+                void main() {
+                    gl_FragColor = textureCube(texture, vec3(0.0, -1.0, 0.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture = glium::texture::Cubemap::with_mipmaps(&display, build_faces(), false);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (3, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn cubemap_array_layers() {
+    let display = support::build_display();
+
+    let mut faces = build_faces();
+    faces.extend(build_faces().into_iter());
+
+    let texture = match glium::texture::CubemapArray::with_mipmaps_if_supported(&display, faces,
+                                                                                 false)
+    {
+        None => return,
+        Some(t) => t
+    };
+
+    assert_eq!(texture.get_array_size(), Some(2));
+    assert!(texture.layer(1).is_some());
+    assert!(texture.layer(2).is_none());
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,176 @@
+#![cfg(feature = "gltf")]
+
+extern crate glium;
+
+mod support;
+
+use glium::gltf::{self, GltfError};
+use glium::index::{IndexType, PrimitiveType};
+
+// a triangle made of three `vec3` positions followed by three `u16` indices
+const TRIANGLE_GLTF: &'static str = r#"{
+    "asset": { "version": "2.0" },
+    "scene": 0,
+    "scenes": [{ "nodes": [0] }],
+    "nodes": [
+        { "name": "parent", "translation": [1.0, 2.0, 3.0], "children": [1] },
+        { "name": "child", "mesh": 0, "scale": [2.0, 2.0, 2.0] }
+    ],
+    "meshes": [{
+        "name": "triangle",
+        "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1, "material": 0 }]
+    }],
+    "materials": [{
+        "name": "red",
+        "pbrMetallicRoughness": { "baseColorFactor": [1.0, 0.0, 0.0, 1.0] },
+        "alphaMode": "MASK"
+    }],
+    "accessors": [
+        { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+        { "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }
+    ],
+    "bufferViews": [
+        { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+        { "buffer": 0, "byteOffset": 36, "byteLength": 6 }
+    ],
+    "buffers": [{
+        "byteLength": 42,
+        "uri": "data:;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAAAAABAAIA"
+    }]
+}"#;
+
+#[test]
+fn load_gltf() {
+    let display = support::build_display();
+
+    let scene = gltf::load_from_memory(&display, TRIANGLE_GLTF.as_bytes(), None).unwrap();
+
+    assert_eq!(scene.meshes.len(), 1);
+    assert_eq!(scene.meshes[0].name, Some("triangle".to_string()));
+
+    let primitive = &scene.meshes[0].primitives[0];
+    assert_eq!(primitive.primitive_type, PrimitiveType::TrianglesList);
+    assert_eq!(primitive.material, Some(0));
+    assert_eq!(primitive.vertex_buffer.len(), 3);
+    assert_eq!(primitive.vertex_buffer.get_elements_size(), 12);
+    assert_eq!(primitive.vertex_buffer.get_bindings()[0].0, "POSITION");
+    assert_eq!(primitive.index_buffer.as_ref().unwrap().get_indices_type(), IndexType::U16);
+
+    assert_eq!(scene.materials.len(), 1);
+    assert_eq!(scene.materials[0].base_color_factor, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(scene.materials[0].alpha_mode, gltf::AlphaMode::Mask);
+    assert_eq!(scene.materials[0].alpha_cutoff, 0.5);
+
+    // the transformation of the child is combined with the one of its parent
+    assert_eq!(scene.draw_list.len(), 1);
+    assert_eq!(scene.draw_list[0].mesh, 0);
+    assert_eq!(scene.draw_list[0].node_name, Some("child".to_string()));
+    assert_eq!(scene.draw_list[0].transform, [
+        [2.0, 0.0, 0.0, 0.0],
+        [0.0, 2.0, 0.0, 0.0],
+        [0.0, 0.0, 2.0, 0.0],
+        [1.0, 2.0, 3.0, 1.0],
+    ]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_glb() {
+    let display = support::build_display();
+
+    // moving the content of the buffer to the binary chunk
+    let uri_start = TRIANGLE_GLTF.find(",\n        \"uri\"").unwrap();
+    let uri_end = TRIANGLE_GLTF.find("AIA\"").unwrap() + 4;
+    let mut json = format!("{}{}", &TRIANGLE_GLTF[.. uri_start],
+                           &TRIANGLE_GLTF[uri_end ..]).into_bytes();
+    while json.len() % 4 != 0 { json.push(b' '); }
+
+    let mut binary = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0x3f, 0, 0, 0, 0, 0, 0,
+                          0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0x3f, 0, 0, 0, 0, 0, 0, 1, 0, 2, 0];
+    while binary.len() % 4 != 0 { binary.push(0); }
+
+    let total = 12 + 8 + json.len() + 8 + binary.len();
+    let mut data = Vec::new();
+    data.extend(b"glTF".iter().cloned());
+    push_u32(&mut data, 2);
+    push_u32(&mut data, total as u32);
+    push_u32(&mut data, json.len() as u32);
+    push_u32(&mut data, 0x4E4F534A);
+    data.extend(json.into_iter());
+    push_u32(&mut data, binary.len() as u32);
+    push_u32(&mut data, 0x004E4942);
+    data.extend(binary.into_iter());
+
+    let scene = gltf::load_from_memory(&display, &data, None).unwrap();
+
+    assert_eq!(scene.meshes.len(), 1);
+    assert_eq!(scene.meshes[0].primitives[0].vertex_buffer.len(), 3);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn wrong_version() {
+    let display = support::build_display();
+
+    let data = TRIANGLE_GLTF.replace("\"2.0\"", "\"1.0\"");
+
+    match gltf::load_from_memory(&display, data.as_bytes(), None) {
+        Err(GltfError::UnsupportedFeature(_)) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn required_extension() {
+    let display = support::build_display();
+
+    let data = TRIANGLE_GLTF.replace("\"scene\": 0,",
+                                     "\"scene\": 0, \"extensionsRequired\": [\"EXT_foo\"],");
+
+    match gltf::load_from_memory(&display, data.as_bytes(), None) {
+        Err(GltfError::UnsupportedFeature(_)) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalid_json() {
+    let display = support::build_display();
+
+    match gltf::load_from_memory(&display, b"{ \"asset\": ", None) {
+        Err(GltfError::InvalidJson(_)) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn accessor_out_of_range() {
+    let display = support::build_display();
+
+    let data = TRIANGLE_GLTF.replace("\"count\": 3, \"type\": \"VEC3\"",
+                                     "\"count\": 4, \"type\": \"VEC3\"");
+
+    match gltf::load_from_memory(&display, data.as_bytes(), None) {
+        Err(GltfError::InvalidGltf(_)) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    }
+
+    display.assert_no_error(None);
+}
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend([value as u8, (value >> 8) as u8, (value >> 16) as u8,
+                 (value >> 24) as u8].iter().cloned());
+}
@@ -0,0 +1,160 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::UncompressedFloatFormat;
+use glium::uniforms::{ImageUnit, ImageUnitAccess, ImageUnitFormat, MemoryBarrier};
+
+mod support;
+
+fn build_store_program(display: &glium::Display) -> Option<glium::Program> {
+    let program = glium::Program::from_source(display,
+        "
+            #version 420
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 420
+
+            layout(rgba8) writeonly uniform image2D image;
+            out vec4 color;
+
+            void main() {
+                imageStore(image, ivec2(gl_FragCoord.xy), vec4(1.0, 0.0, 1.0, 1.0));
+                color = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring the test in case of compilation error (version 4.20 may not be supported)
+    program.ok()
+}
+
+#[test]
+fn image_store() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_store_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let image = glium::Texture2d::empty_with_format(&display, UncompressedFloatFormat::U8U8U8U8,
+                                                    false, 64, 64).unwrap();
+    image.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let output = glium::Texture2d::empty(&display, 64, 64);
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).access(ImageUnitAccess::Write)
+    };
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    display.memory_barrier(MemoryBarrier { texture_update: true, .. Default::default() });
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.read();
+    assert_eq!(data[0][0], (255, 0, 255, 255));
+    assert_eq!(data[63][63], (255, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn image_wrong_format_kind() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_store_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let image = glium::Texture2d::empty(&display, 64, 64);
+
+    // an unsigned format requires an `uimage2D`
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::R32UI)
+    };
+
+    let output = glium::Texture2d::empty(&display, 64, 64);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn image_layer_of_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_store_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let image = glium::texture::Texture2dArray::empty_with_format_if_supported(&display,
+                                                    UncompressedFloatFormat::U8U8U8U8,
+                                                    false, 64, 64, 2);
+    let image = match image {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    // binding all the layers would require an `image2DArray`
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8)
+    };
+
+    let output = glium::Texture2d::empty(&display, 64, 64);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).layer(Some(1))
+    };
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    display.memory_barrier(MemoryBarrier::all());
+    display.assert_no_error(None);
+}
+
+#[test]
+fn image_layer_out_of_range() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_store_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let image = glium::texture::Texture2dArray::empty_with_format_if_supported(&display,
+                                                    UncompressedFloatFormat::U8U8U8U8,
+                                                    false, 64, 64, 2);
+    let image = match image {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let uniforms = uniform! {
+        image: ImageUnit::new(&image, ImageUnitFormat::RGBA8).layer(Some(2))
+    };
+
+    let output = glium::Texture2d::empty(&display, 64, 64);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::ImageUnitLayerOutOfRange) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
@@ -361,3 +361,428 @@ fn multidraw_array() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn multidraw_array_draw_count() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let multidraw = glium::index::DrawCommandsNoIndicesBuffer::empty_if_supported(&display, 2);
+    let mut multidraw = match multidraw {
+        Some(buf) => buf,
+        None => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandNoIndices {
+            count: 0,
+            instance_count: 1,
+            first_index: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandNoIndices {
+            count: 4,
+            instance_count: 1,
+            first_index: 0,
+            base_instance: 0,
+        },
+    ]);
+
+    let draw_count = glium::buffer::BufferView::new(&display, &[1u32],
+                                                    glium::buffer::BufferType::ArrayBuffer, false)
+                                                    .unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let indices = multidraw.with_primitive_type_and_draw_count_if_supported(
+                                        PrimitiveType::TriangleStrip, draw_count.as_slice(), 2);
+    let indices = match indices {
+        Some(i) => i,
+        None => return
+    };
+
+    // only the first command, which draws nothing, must be executed
+    texture.as_surface().draw(&vb, indices.clone(), &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 0, 0));
+        }
+    }
+
+    draw_count.write(&[2]);
+    texture.as_surface().draw(&vb, indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_vertex() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [0.0, 0.0] }, Vertex { position: [0.0, 0.0] },
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          vec![0u16, 1, 2, 2, 1, 3]);
+
+    let params = glium::DrawParameters {
+        base_vertex: 2,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_vertex_noindices() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [0.0, 0.0] }, Vertex { position: [0.0, 0.0] },
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::index::NoIndices(PrimitiveType::TriangleStrip);
+
+    let params = glium::DrawParameters {
+        base_vertex: 2,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn base_vertex_out_of_range() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::index::NoIndices(PrimitiveType::TriangleStrip);
+
+    let params = glium::DrawParameters {
+        base_vertex: 5,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params) {
+        Err(glium::DrawError::BaseVertexOutOfRange) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          vec![0u16, 1, 2, 2, 1, 3]);
+
+    let mut multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 3,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+    ]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, multidraw.with_index_buffer(&indices),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_slice() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    // the first three indices are not part of the slice
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          vec![3u16, 3, 3, 0, 1, 2, 2, 1, 3]);
+
+    let mut multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 3,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+    ]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, multidraw.with_index_buffer(indices.slice(3 .. 9).unwrap()),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_slice_out_of_range() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          vec![3u16, 3, 3, 0, 1, 2, 2, 1, 3]);
+
+    // the second command reads past the end of the slice
+    let mut multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 3,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+    ]);
+
+    // slices that don't start at the beginning of the buffer are drawn one command at a time
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, multidraw.with_index_buffer(indices.slice(3 .. 6)
+                                                                            .unwrap()),
+                                    &program, &uniform!{}, &Default::default())
+    {
+        Err(glium::DrawError::DrawCommandIndicesOutOfRange) => (),
+        Err(glium::DrawError::MultidrawIndirectNotSupported) => return,
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn primitive_restart() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                          vec![0u16, 1, 2, 0xffff, 2, 1, 3]);
+
+    let params = glium::DrawParameters {
+        primitive_restart_index: true,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params) {
+        Ok(_) => (),
+        Err(glium::DrawError::FixedIndexRestartingNotSupported) => return,
+        e => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn primitive_restart_wrong_primitives() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, vec![
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          vec![0u16, 1, 2, 0xffff, 2, 1, 3]);
+
+    let params = glium::DrawParameters {
+        primitive_restart_index: true,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params) {
+        Err(glium::DrawError::PrimitiveRestartWithWrongPrimitives) => (),
+        Err(glium::DrawError::FixedIndexRestartingNotSupported) => return,
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn triangle_strip_to_list() {
+    let list = index::to_triangles_list(PrimitiveType::TriangleStrip, &[0, 1, 2, 3, 3, 4, 5],
+                                        None);
+
+    // the winding of odd triangles is reversed and the degenerate triangles are removed
+    assert_eq!(list, vec![0, 1, 2, 2, 1, 3, 3, 4, 5]);
+}
+
+#[test]
+fn triangle_fan_to_list_with_restart() {
+    let list = index::to_triangles_list(PrimitiveType::TriangleFan,
+                                        &[0, 1, 2, 3, 0xffffffff, 4, 5, 6],
+                                        Some(0xffffffff));
+
+    assert_eq!(list, vec![0, 1, 2, 0, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn generate_adjacency() {
+    // two triangles sharing the edge 1-2
+    let adjacency = index::generate_adjacency(&[0, 1, 2, 2, 1, 3]);
+
+    assert_eq!(adjacency, vec![0, 2, 1, 3, 2, 1, 2, 0, 1, 2, 3, 1]);
+}
+
+#[test]
+fn optimize_vertex_cache_keeps_triangles() {
+    let original = [0, 1, 2, 3, 4, 5, 2, 1, 6, 0, 2, 7, 5, 4, 8, 1, 0, 9];
+
+    let mut optimized = original;
+    index::optimize_vertex_cache(&mut optimized);
+
+    let mut original = original.chunks(3).map(|t| t.to_vec()).collect::<Vec<_>>();
+    let mut optimized = optimized.chunks(3).map(|t| t.to_vec()).collect::<Vec<_>>();
+    original.sort();
+    optimized.sort();
+    assert_eq!(original, optimized);
+}
+
+#[test]
+fn shrink_indices() {
+    let display = support::build_display();
+
+    let indices = index::shrink(&[0, 1, 255], false);
+    assert_eq!(indices, index::ShrunkIndices::U8(vec![0, 1, 255]));
+
+    // 255 is reserved for the restart index
+    let indices = index::shrink(&[0, 1, 255, 0xffffffff], true);
+    assert_eq!(indices, index::ShrunkIndices::U16(vec![0, 1, 255, 0xffff]));
+
+    let indices = index::shrink(&[0, 70000], false);
+    assert_eq!(indices.get_type(), index::IndexType::U32);
+
+    let buffer = index::shrink(&[0, 1, 2], false).into_index_buffer(&display,
+                                                                    PrimitiveType::TrianglesList);
+    assert_eq!(buffer.get_indices_type(), index::IndexType::U8);
+
+    display.assert_no_error(None);
+}
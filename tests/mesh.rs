@@ -0,0 +1,169 @@
+#![cfg(feature = "mesh")]
+
+extern crate glium;
+
+mod support;
+
+use glium::mesh::{Mesh, MeshError};
+
+const QUAD_OBJ: &'static str = "
+# a quad made of two triangles
+mtllib quad.mtl
+v -1.0 -1.0 0.0
+v 1.0 -1.0 0.0
+v 1.0 1.0 0.0
+v -1.0 1.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+g first
+usemtl red
+f 1/1 2/2 3/3
+g second
+f 1/1 3/3 4/4
+";
+
+#[test]
+fn obj_load() {
+    let mesh = Mesh::from_obj(QUAD_OBJ.as_bytes()).unwrap();
+
+    // the vertices shared by the two triangles are deduplicated
+    assert_eq!(mesh.len(), 4);
+    assert_eq!(mesh.indices.len(), 6);
+    assert!(mesh.texcoords.is_some());
+    assert!(mesh.colors.is_none());
+
+    // the normals are generated
+    for normal in mesh.normals.as_ref().unwrap().iter() {
+        assert_eq!(normal, &[0.0, 0.0, 1.0]);
+    }
+
+    assert_eq!(mesh.groups.len(), 2);
+    assert_eq!(mesh.groups[0].name, "first");
+    assert_eq!(mesh.groups[0].material, Some("red".to_string()));
+    assert_eq!(mesh.groups[0].indices, 0 .. 3);
+    assert_eq!(mesh.groups[1].name, "second");
+    assert_eq!(mesh.groups[1].material, Some("red".to_string()));
+    assert_eq!(mesh.groups[1].indices, 3 .. 6);
+    assert_eq!(mesh.material_libraries, vec!["quad.mtl".to_string()]);
+}
+
+#[test]
+fn obj_polygon_triangulated() {
+    let mesh = Mesh::from_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf -4 -3 -2 -1\n".as_bytes())
+                   .unwrap();
+
+    assert_eq!(mesh.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+}
+
+#[test]
+fn obj_index_out_of_range() {
+    match Mesh::from_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nf 1 2 4\n".as_bytes()) {
+        Err(MeshError::ParseError { line: Some(4), .. }) => (),
+        e => panic!("{:?}", e)
+    }
+}
+
+#[test]
+fn obj_invalid_number() {
+    match Mesh::from_obj("v 0 0 0\nv 1 zero 0\n".as_bytes()) {
+        Err(MeshError::ParseError { line: Some(2), .. }) => (),
+        e => panic!("{:?}", e)
+    }
+}
+
+#[test]
+fn ply_ascii_load() {
+    let data = "ply
+format ascii 1.0
+comment a quad
+element vertex 4
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+element face 1
+property list uchar int vertex_indices
+end_header
+-1 -1 0 255 0 0
+1 -1 0 255 0 0
+1 1 0 255 0 0
+-1 1 0 255 0 0
+4 0 1 2 3
+";
+
+    let mesh = Mesh::from_ply(data.as_bytes()).unwrap();
+
+    assert_eq!(mesh.len(), 4);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert!(mesh.texcoords.is_none());
+    assert_eq!(mesh.colors.as_ref().unwrap()[0], [1.0, 0.0, 0.0, 1.0]);
+
+    for normal in mesh.normals.as_ref().unwrap().iter() {
+        assert_eq!(normal, &[0.0, 0.0, 1.0]);
+    }
+}
+
+#[test]
+fn ply_binary_load() {
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\n\
+                     property float x\nproperty float y\nproperty float z\n\
+                     element face 1\nproperty list uchar uint vertex_indices\n\
+                     end_header\n".to_vec();
+
+    let floats: [u32; 9] = [0x00000000, 0x00000000, 0x00000000,      // 0.0, 0.0, 0.0
+                            0x3f800000, 0x00000000, 0x00000000,      // 1.0, 0.0, 0.0
+                            0x00000000, 0x3f800000, 0x00000000];     // 0.0, 1.0, 0.0
+
+    for value in floats.iter() {
+        data.extend([*value as u8, (*value >> 8) as u8, (*value >> 16) as u8,
+                     (*value >> 24) as u8].iter().cloned());
+    }
+
+    data.push(3);
+    data.extend([0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0].iter().cloned());
+
+    let mesh = Mesh::from_ply(&data[..]).unwrap();
+
+    assert_eq!(mesh.positions, vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+}
+
+#[test]
+fn ply_truncated() {
+    let data = "ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+end_header
+0 0 0
+1 0
+";
+
+    match Mesh::from_ply(data.as_bytes()) {
+        Err(MeshError::ParseError { .. }) => (),
+        e => panic!("{:?}", e)
+    }
+}
+
+#[test]
+fn upload() {
+    let display = support::build_display();
+
+    let mesh = Mesh::from_obj(QUAD_OBJ.as_bytes()).unwrap();
+    let vertex_buffer = mesh.to_vertex_buffer(&display);
+    let index_buffer = mesh.to_index_buffer(&display);
+
+    assert_eq!(vertex_buffer.len(), 4);
+    assert_eq!(vertex_buffer.get_elements_size(), 8 * 4);
+    assert_eq!(vertex_buffer.get_bindings().len(), 3);
+    assert_eq!(index_buffer.get_indices_type(), glium::index::IndexType::U16);
+
+    display.assert_no_error(None);
+}
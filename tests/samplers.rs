@@ -79,3 +79,348 @@ fn magnify_nearest_filtering() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_without_sampler_after_sampler() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D texture;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, vec2(0.51, 0.0));
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                uniform lowp sampler2D texture;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, vec2(0.51, 0.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data);
+
+    let output = support::build_renderable_texture(&display);
+
+    let uniforms = uniform! {
+        texture: texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+    };
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    // the texture must be sampled with its own linear filtering again
+    let uniforms = uniform! { texture: &texture };
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert!(data[0][0].0 < 255);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn border_clamp_color() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(2.0, 2.0));
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let texture = glium::texture::Texture2d::new(&display, vec![vec![(0u8, 0u8, 0u8)]]);
+
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .wrap_function(glium::uniforms::SamplerWrapFunction::BorderClamp)
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                        .border_color((1.0, 0.0, 0.0, 1.0))
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::BorderClampNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+fn build_shadow_program(display: &glium::Display) -> Option<glium::Program> {
+    match glium::Program::from_source(display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform sampler2DShadow texture;
+            out vec4 color;
+
+            void main() {
+                float lit = texture(texture, vec3(0.5, 0.5, 0.25));
+                color = vec4(lit, lit, lit, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => None,
+        Ok(p) => Some(p),
+        e => Some(e.unwrap())
+    }
+}
+
+#[test]
+fn depth_texture_comparison() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_shadow_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let texture = match glium::texture::DepthTexture2d::new_if_supported(&display,
+                                                                        vec![vec![0.5f32]])
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let output = support::build_renderable_texture(&display);
+
+    for &(comparison, expected) in &[
+        (glium::uniforms::DepthTextureComparison::LessOrEqual, (255, 255, 255, 255)),
+        (glium::uniforms::DepthTextureComparison::Greater, (0, 0, 0, 255))
+    ] {
+        let uniforms = uniform! {
+            texture: texture.sampled()
+                            .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                            .depth_texture_comparison(Some(comparison))
+        };
+
+        output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+        assert_eq!(data[0][0], expected);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shadow_sampler_without_comparison() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_shadow_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let texture = match glium::texture::DepthTexture2d::new_if_supported(&display,
+                                                                        vec![vec![0.5f32]])
+    {
+        Some(t) => t,
+        None => return
+    };
+
+    let uniforms = uniform! {
+        texture: &texture
+    };
+
+    let output = support::build_renderable_texture(&display);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sampler_behavior_equality() {
+    let a = glium::uniforms::SamplerBehavior {
+        lod_bias: 0.5,
+        .. Default::default()
+    };
+
+    let b = glium::uniforms::SamplerBehavior {
+        lod_bias: 0.5,
+        .. Default::default()
+    };
+
+    assert_eq!(a, b);
+    assert!(a != Default::default());
+}
+
+fn build_stencil_program(display: &glium::Display) -> Option<glium::Program> {
+    match glium::Program::from_source(display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform usampler2D texture;
+            out vec4 color;
+
+            void main() {
+                uint stencil = texture(texture, vec2(0.5, 0.5)).r;
+                color = vec4(float(stencil) / 255.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => None,
+        Ok(p) => Some(p),
+        e => Some(e.unwrap())
+    }
+}
+
+#[test]
+fn depth_stencil_texture_stencil_sampling() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_stencil_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display, 1, 1) {
+        Some(t) => t,
+        None => return
+    };
+
+    let color = glium::texture::Texture2d::empty(&display, 1, 1);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(
+                                                                &display, &color, &texture);
+    framebuffer.clear_stencil(5);
+
+    match texture.set_depth_stencil_texture_mode(glium::texture::DepthStencilTextureMode::Stencil) {
+        Ok(_) => (),
+        Err(glium::texture::StencilTexturingNotSupportedError) => return,
+    };
+
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (5, 0, 0, 255));
+
+    // switching back to the depth component makes the texture unusable with a `usampler2D`
+    texture.set_depth_stencil_texture_mode(glium::texture::DepthStencilTextureMode::Depth)
+           .unwrap();
+
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_stencil_texture_depth_with_usampler() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_stencil_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display, 1, 1) {
+        Some(t) => t,
+        None => return
+    };
+
+    let uniforms = uniform! {
+        texture: &texture
+    };
+
+    let output = support::build_renderable_texture(&display);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,238 @@
+#[macro_use]
+extern crate glium;
+
+use glium::texture::container::{self, ContainerError};
+use glium::texture::{CompressedFormat, CompressedSrgbFormat, TextureFormat};
+
+mod support;
+
+fn push_u32(data: &mut Vec<u8>, value: u32) {
+    data.push(value as u8);
+    data.push((value >> 8) as u8);
+    data.push((value >> 16) as u8);
+    data.push((value >> 24) as u8);
+}
+
+fn push_u64(data: &mut Vec<u8>, value: u64) {
+    push_u32(data, value as u32);
+    push_u32(data, (value >> 32) as u32);
+}
+
+/// Builds a DDS file with the given four CC and content.
+fn build_dds(four_cc: &[u8], width: u32, height: u32, levels: u32, dx10: Option<(u32, u32)>,
+             content: &[u8]) -> Vec<u8>
+{
+    let mut data = b"DDS ".to_vec();
+    push_u32(&mut data, 124);
+    push_u32(&mut data, 0x1 | 0x2 | 0x4 | 0x1000 | 0x20000);
+    push_u32(&mut data, height);
+    push_u32(&mut data, width);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, levels);
+    for _ in 0 .. 11 { push_u32(&mut data, 0); }
+
+    // pixel format
+    push_u32(&mut data, 32);
+    push_u32(&mut data, 0x4);
+    data.extend(four_cc.iter().cloned());
+    for _ in 0 .. 5 { push_u32(&mut data, 0); }
+
+    for _ in 0 .. 5 { push_u32(&mut data, 0); }
+
+    if let Some((dxgi_format, array_size)) = dx10 {
+        push_u32(&mut data, dxgi_format);
+        push_u32(&mut data, 3);
+        push_u32(&mut data, 0);
+        push_u32(&mut data, array_size);
+        push_u32(&mut data, 0);
+    }
+
+    data.extend(content.iter().cloned());
+    data
+}
+
+#[test]
+fn dds_dxt1() {
+    let data = build_dds(b"DXT1", 4, 4, 1, None, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    let image = container::load_from_memory(&data).unwrap();
+
+    assert_eq!(image.format, TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha));
+    assert_eq!(image.width, 4);
+    assert_eq!(image.height, 4);
+    assert_eq!(image.array_size, None);
+    assert_eq!(image.levels, vec![vec![1, 2, 3, 4, 5, 6, 7, 8]]);
+}
+
+#[test]
+fn dds_dx10_array_mipmaps() {
+    // two layers of 8x8 with two mipmaps, stored layer by layer
+    let layer1 = (0 .. 32 + 8).map(|v| v as u8).collect::<Vec<_>>();
+    let layer2 = (100 .. 132 + 8).map(|v| v as u8).collect::<Vec<_>>();
+    let content = layer1.iter().chain(layer2.iter()).cloned().collect::<Vec<_>>();
+
+    // DXGI_FORMAT_BC1_UNORM_SRGB
+    let data = build_dds(b"DX10", 8, 8, 2, Some((72, 2)), &content);
+    let image = container::load_from_memory(&data).unwrap();
+
+    assert_eq!(image.format,
+               TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha));
+    assert_eq!(image.array_size, Some(2));
+    assert_eq!(image.levels.len(), 2);
+    assert_eq!(&image.levels[0][.. 32], &layer1[.. 32]);
+    assert_eq!(&image.levels[0][32 ..], &layer2[.. 32]);
+    assert_eq!(&image.levels[1][.. 8], &layer1[32 ..]);
+    assert_eq!(&image.levels[1][8 ..], &layer2[32 ..]);
+}
+
+#[test]
+fn ktx_dxt5() {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+    push_u32(&mut data, 0x04030201);
+    push_u32(&mut data, 0);             // glType
+    push_u32(&mut data, 1);             // glTypeSize
+    push_u32(&mut data, 0);             // glFormat
+    push_u32(&mut data, 0x83F3);        // GL_COMPRESSED_RGBA_S3TC_DXT5_EXT
+    push_u32(&mut data, 0x1908);        // GL_RGBA
+    push_u32(&mut data, 4);
+    push_u32(&mut data, 4);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 16);
+    data.extend((0 .. 16u8));
+
+    let image = container::load_from_memory(&data).unwrap();
+
+    assert_eq!(image.format, TextureFormat::CompressedFormat(CompressedFormat::S3tcDxt5Alpha));
+    assert_eq!(image.array_size, None);
+    assert_eq!(image.levels, vec![(0 .. 16u8).collect::<Vec<_>>()]);
+}
+
+#[test]
+fn ktx2_bc7() {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+    push_u32(&mut data, 146);           // VK_FORMAT_BC7_SRGB_BLOCK
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 4);
+    push_u32(&mut data, 4);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 0);
+    for _ in 0 .. 4 { push_u32(&mut data, 0); }
+    for _ in 0 .. 2 { push_u64(&mut data, 0); }
+
+    // level index
+    push_u64(&mut data, 104);
+    push_u64(&mut data, 16);
+    push_u64(&mut data, 16);
+    data.extend((0 .. 16u8));
+
+    let image = container::load_from_memory(&data).unwrap();
+
+    assert_eq!(image.format, TextureFormat::CompressedSrgbFormat(CompressedSrgbFormat::Bptc));
+    assert_eq!(image.levels, vec![(0 .. 16u8).collect::<Vec<_>>()]);
+}
+
+#[test]
+fn invalid_container() {
+    match container::load_from_memory(b"hello world!") {
+        Err(ContainerError::InvalidFile(_)) => (),
+        _ => panic!()
+    };
+
+    let data = build_dds(b"DXT1", 4, 4, 1, None, &[1, 2, 3]);
+    match container::load_from_memory(&data) {
+        Err(ContainerError::InvalidFile(_)) => (),
+        _ => panic!()
+    };
+}
+
+#[test]
+fn dds_too_many_mipmaps() {
+    // a 4x4 image has at most three mipmaps
+    let data = build_dds(b"DXT1", 4, 4, 4, None, &[0; 8 * 4]);
+    match container::load_from_memory(&data) {
+        Err(ContainerError::InvalidFile(_)) => (),
+        _ => panic!()
+    };
+}
+
+#[test]
+fn dds_size_overflow() {
+    // DXGI_FORMAT_BC1_UNORM
+    let data = build_dds(b"DX10", 0xffffffff, 0xffffffff, 1, Some((71, 0xffffffff)), &[0; 8]);
+    match container::load_from_memory(&data) {
+        Err(ContainerError::InvalidFile(_)) => (),
+        _ => panic!()
+    };
+}
+
+#[test]
+fn dds_upload() {
+    let display = support::build_display();
+
+    let data = build_dds(b"DXT1", 8, 8, 2, None, &[0; 32 + 8]);
+    let image = container::load_from_memory(&data).unwrap();
+
+    let texture = match image.clone().into_compressed_texture_2d(&display) {
+        Ok(t) => t,
+        Err(ContainerError::FormatNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(texture.get_width(), 8);
+    assert_eq!(texture.get_height(), Some(8));
+    assert_eq!(texture.get_mipmap_levels(), 2);
+
+    match image.into_compressed_srgb_texture_2d_array(&display) {
+        Ok(t) => assert_eq!(t.get_array_size(), Some(1)),
+        Err(ContainerError::FormatNotSupported) => (),
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn ktx2_astc() {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+    push_u32(&mut data, 171);           // VK_FORMAT_ASTC_8x8_UNORM_BLOCK
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 16);
+    push_u32(&mut data, 8);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 0);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 1);
+    push_u32(&mut data, 0);
+    for _ in 0 .. 4 { push_u32(&mut data, 0); }
+    for _ in 0 .. 2 { push_u64(&mut data, 0); }
+
+    // two blocks of 8x8 pixels
+    push_u64(&mut data, 104);
+    push_u64(&mut data, 32);
+    push_u64(&mut data, 32);
+    data.extend((0 .. 32u8));
+
+    let image = container::load_from_memory(&data).unwrap();
+
+    assert_eq!(image.format, TextureFormat::CompressedFormat(CompressedFormat::Astc8x8));
+    assert_eq!(image.width, 16);
+    assert_eq!(image.height, 8);
+    assert_eq!(image.levels, vec![(0 .. 32u8).collect::<Vec<_>>()]);
+
+    let display = support::build_display();
+
+    match image.into_compressed_srgb_texture_2d(&display) {
+        Ok(t) => assert_eq!(t.get_width(), 16),
+        Err(ContainerError::FormatNotSupported) => (),
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,106 @@
+#[macro_use]
+extern crate glium;
+
+use glium::texture::{self, Texture2d};
+
+mod support;
+
+#[test]
+fn texture_2d_copy_region() {
+    let display = support::build_display();
+
+    let source = Texture2d::new(&display, vec![
+        vec![(0u8, 0u8, 0u8, 0u8), (1u8, 1u8, 1u8, 1u8), (2u8, 2u8, 2u8, 2u8)],
+        vec![(3u8, 3u8, 3u8, 3u8), (4u8, 4u8, 4u8, 4u8), (5u8, 5u8, 5u8, 5u8)],
+    ]);
+
+    let dest = Texture2d::new(&display, vec![vec![(9u8, 9u8, 9u8, 9u8); 3]; 2]);
+
+    texture::copy_region(source.main_level(),
+                         &glium::Rect { left: 1, bottom: 0, width: 2, height: 2 },
+                         dest.main_level(), (0, 0));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = dest.read();
+    assert_eq!(data, vec![
+        vec![(1, 1, 1, 1), (2, 2, 2, 2), (9, 9, 9, 9)],
+        vec![(4, 4, 4, 4), (5, 5, 5, 5), (9, 9, 9, 9)],
+    ]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_copy_region_same_texture() {
+    let display = support::build_display();
+
+    let texture = Texture2d::new(&display, vec![
+        vec![(1u8, 2u8, 3u8, 4u8), (0u8, 0u8, 0u8, 0u8)],
+    ]);
+
+    texture::copy_region(texture.main_level(),
+                         &glium::Rect { left: 0, bottom: 0, width: 1, height: 1 },
+                         texture.main_level(), (1, 0));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(1, 2, 3, 4), (1, 2, 3, 4)]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_array_layer_copy_region() {
+    let display = support::build_display();
+
+    let source = match texture::Texture2dArray::new_if_supported(&display, vec![
+        vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2],
+        vec![vec![(7u8, 6u8, 5u8, 4u8); 2]; 2],
+    ]) {
+        None => return,
+        Some(t) => t
+    };
+
+    let dest = Texture2d::new(&display, vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2]);
+
+    texture::copy_region(source.layer(1).unwrap().main_level(),
+                         &glium::Rect { left: 0, bottom: 0, width: 2, height: 2 },
+                         dest.main_level(), (0, 0));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = dest.read();
+    assert_eq!(data, vec![vec![(7, 6, 5, 4); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn texture_2d_copy_region_out_of_range() {
+    let display = support::build_display();
+
+    let source = Texture2d::empty(&display, 4, 4);
+    let dest = Texture2d::empty(&display, 2, 2);
+
+    texture::copy_region(source.main_level(),
+                         &glium::Rect { left: 0, bottom: 0, width: 4, height: 4 },
+                         dest.main_level(), (0, 0));
+}
+
+#[test]
+fn compressed_texture_2d_copy_region() {
+    let display = support::build_display();
+
+    // the driver chooses the compressed format, or falls back to an uncompressed one
+    let source = texture::CompressedTexture2d::new(&display,
+                                                   vec![vec![(255u8, 0u8, 0u8, 255u8); 8]; 8]);
+    let dest = texture::CompressedTexture2d::new(&display,
+                                                 vec![vec![(0u8, 255u8, 0u8, 255u8); 8]; 8]);
+
+    texture::copy_region(source.main_level(),
+                         &glium::Rect { left: 0, bottom: 0, width: 8, height: 8 },
+                         dest.main_level(), (0, 0));
+
+    let expected: Vec<Vec<(u8, u8, u8, u8)>> = source.read();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = dest.read();
+    assert_eq!(data, expected);
+
+    display.assert_no_error(None);
+}
@@ -208,12 +208,15 @@ empty_texture_test!(empty_compressedtexture1darray, CompressedTexture1dArray, [6
 empty_texture_test!(empty_compressedtexture2d, CompressedTexture2d, [64, 32], 64, Some(32), None, None);
 empty_texture_test!(empty_compressedtexture2darray, CompressedTexture2dArray, [64, 32, 16], 64, Some(32), None, Some(16));
 empty_texture_test!(empty_compressedtexture3d, CompressedTexture3d, [64, 32, 16], 64, Some(32), Some(16), None);*/
+empty_texture_test!(empty_cubemap, Cubemap, [64], 64, Some(64), None, None);
+empty_texture_test!(empty_cubemaparray, maybe CubemapArray, [64, 2], 64, Some(64), None, Some(2));
 empty_texture_test!(empty_depthstenciltexture1d, maybe DepthStencilTexture1d, [64], 64, None, None, None);
 empty_texture_test!(empty_depthstenciltexture1darray, maybe DepthStencilTexture1dArray, [64, 32], 64, None, None, Some(32));
 empty_texture_test!(empty_depthstenciltexture2d, maybe DepthStencilTexture2d, [64, 32], 64, Some(32), None, None);
 empty_texture_test!(empty_depthstenciltexture2darray, maybe DepthStencilTexture2dArray, [64, 32, 16], 64, Some(32), None, Some(16));
 // TODO: non-working
 //empty_texture_test!(empty_depthstenciltexture3d, DepthStencilTexture3d, [64, 32, 16], 64, Some(32), Some(16), None);
+empty_texture_test!(empty_depthcubemap, maybe DepthCubemap, [64], 64, Some(64), None, None);
 empty_texture_test!(empty_depthtexture1d, maybe DepthTexture1d, [64], 64, None, None, None);
 empty_texture_test!(empty_depthtexture1darray, maybe DepthTexture1dArray, [64, 32], 64, None, None, Some(32));
 empty_texture_test!(empty_depthtexture2d, maybe DepthTexture2d, [64, 32], 64, Some(32), None, None);
@@ -293,3 +296,195 @@ fn zero_sized_texture_3d_creation() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_2d_empty_mipmaps_max() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                        glium::texture::MipmapsOption::EmptyMipmapsMax(2), 16, 16);
+    assert_eq!(texture.get_mipmap_levels(), 3);
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                        glium::texture::MipmapsOption::EmptyMipmaps, 16, 16);
+    assert_eq!(texture.get_mipmap_levels(), 5);
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                        glium::texture::MipmapsOption::NoMipmap, 16, 16);
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_with_mipmap_data() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::with_mipmap_data(&display, vec![
+        vec![vec![(255u8, 0u8, 0u8, 255u8); 4]; 4],
+        vec![vec![(0u8, 255u8, 0u8, 255u8); 2]; 2],
+        vec![vec![(0u8, 0u8, 255u8, 255u8); 1]; 1],
+    ]).unwrap();
+
+    assert_eq!(texture.get_mipmap_levels(), 3);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.mipmap(1).unwrap().read();
+    assert_eq!(data, vec![vec![(0, 255, 0, 255); 2]; 2]);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.mipmap(2).unwrap().read();
+    assert_eq!(data, vec![vec![(0, 0, 255, 255)]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_creation_u16u16u16u16() {
+    let display = support::build_display();
+
+    // OpenGL ES can't upload unsigned shorts to a floating-point texture, so the data is
+    // converted on the CPU
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(65535u16, 0u16, 0u16, 65535u16), (0u16, 65535u16, 0u16, 65535u16)],
+        vec![(0u16, 0u16, 65535u16, 65535u16), (65535u16, 65535u16, 65535u16, 0u16)],
+    ]).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![
+        vec![(255, 0, 0, 255), (0, 255, 0, 255)],
+        vec![(0, 0, 255, 255), (255, 255, 255, 0)],
+    ]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_creation_u16u16u16u16_float_format() {
+    let display = support::build_display();
+
+    // OpenGL ES 3 only accepts floats for floating-point internal formats
+    let texture = match glium::texture::Texture2d::with_format_if_supported(&display, vec![
+        vec![(65535u16, 0u16, 32768u16, 65535u16)],
+    ], glium::texture::UncompressedFloatFormat::F16F16F16F16, false)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let data: Option<Vec<Vec<(f32, f32, f32, f32)>>> = texture.mipmap(0).unwrap()
+                                                               .read_if_supported();
+    if let Some(data) = data {
+        let (r, g, b, a) = data[0][0];
+        assert_eq!((r, g, a), (1.0, 0.0, 1.0));
+        assert!((b - 0.5).abs() < 0.01);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_creation_i16i16i16i16() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(32767i16, 0i16, 0i16, 32767i16), (0i16, 32767i16, 0i16, 32767i16)],
+    ]).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data, vec![vec![(255, 0, 0, 255), (0, 255, 0, 255)]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_generate_mipmaps_after_drawing() {
+    use glium::Surface;
+
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                        glium::texture::MipmapsOption::EmptyMipmaps, 4, 4);
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+    if texture.generate_mipmaps().is_err() {
+        return;
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.mipmap(2).unwrap().read();
+    assert_eq!(data, vec![vec![(255, 0, 0, 255)]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_etc2_creation() {
+    let display = support::build_display();
+
+    let texture = glium::texture::CompressedTexture2d::with_format_if_supported(&display, vec![
+        vec![(0, 0, 0, 0), (0, 0, 0, 0)],
+        vec![(0, 0, 0, 0), (0, 0, 0, 0u8)],
+    ], glium::texture::CompressedFormat::Etc2Rgba8, false);
+
+    let texture = match texture {
+        Ok(t) => t,
+        Err(glium::texture::TextureMaybeSupportedCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn empty_compressed_srgb_texture_2d_astc() {
+    let display = support::build_display();
+
+    let texture = glium::texture::CompressedSrgbTexture2d::empty_with_format_if_supported(
+                                &display, glium::texture::CompressedSrgbFormat::Astc8x8, false,
+                                64, 32);
+
+    let texture = match texture {
+        Ok(t) => t,
+        Err(glium::texture::TextureMaybeSupportedCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(texture.get_width(), 64);
+    assert_eq!(texture.get_height(), Some(32));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_with_compressed_data() {
+    let display = support::build_display();
+
+    let level0 = vec![0u8; 32];
+    let level1 = vec![0u8; 8];
+
+    let texture = match glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                                glium::texture::CompressedFormat::RgtcFormatU,
+                                &[&level0[..], &level1[..]], 8, 8)
+    {
+        Ok(t) => t,
+        Err(glium::texture::TextureMaybeSupportedCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(texture.get_width(), 8);
+    assert_eq!(texture.get_height(), Some(8));
+    assert_eq!(texture.get_mipmap_levels(), 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn compressed_texture_2d_with_compressed_data_wrong_size() {
+    let display = support::build_display();
+
+    let data = vec![0u8; 7];
+    let _ = glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                                glium::texture::CompressedFormat::RgtcFormatU,
+                                &[&data[..]], 4, 4);
+}
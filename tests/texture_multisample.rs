@@ -0,0 +1,150 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::{Texture2dMultisample, DepthTexture2dMultisample};
+
+mod support;
+
+#[test]
+fn multisample_get_samples() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    assert!(texture.get_samples() >= 4);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_sample_positions() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    let positions = texture.get_sample_positions().unwrap();
+    assert_eq!(positions.len() as u32, texture.get_samples());
+
+    for &(x, y) in positions.iter() {
+        assert!(x >= 0.0 && x <= 1.0);
+        assert!(y >= 0.0 && y <= 1.0);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_resolve() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    texture.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let target = glium::Texture2d::empty(&display, 16, 16);
+    texture.resolve_into(&target).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(data, vec![vec![(0, 255, 0, 255); 16]; 16]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn multisample_resolve_wrong_dimensions() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => panic!()
+    };
+
+    let target = glium::Texture2d::empty(&display, 8, 8);
+    let _ = texture.resolve_into(&target);
+}
+
+#[test]
+fn multisample_resolve_incompatible_format() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    let target = match glium::Texture2d::empty_with_format(&display,
+                                     glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                                     glium::texture::MipmapsOption::NoMipmap, 16, 16)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.resolve_into(&target) {
+        Err(glium::texture::ResolveError::IncompatibleFormat) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_resolve_sample() {
+    let display = support::build_display();
+
+    let texture = match Texture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    texture.as_surface().clear_color(1.0, 0.0, 1.0, 1.0);
+
+    let target = glium::Texture2d::empty(&display, 16, 16);
+    texture.resolve_sample_into(texture.get_samples() - 1, &target).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(data, vec![vec![(255, 0, 255, 255); 16]; 16]);
+
+    // the second copy reuses the program built by the first one
+    texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    texture.resolve_sample_into(0, &target).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(data, vec![vec![(0, 0, 255, 255); 16]; 16]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_multisample_resolve() {
+    let display = support::build_display();
+
+    let texture = match DepthTexture2dMultisample::empty_if_supported(&display, 16, 16, 4) {
+        Some(t) => t,
+        None => return
+    };
+
+    let target = match glium::texture::DepthTexture2d::empty_if_supported(&display, 16, 16) {
+        Some(t) => t,
+        None => return
+    };
+
+    match texture.resolve_into(&target) {
+        Ok(_) => (),
+        Err(glium::texture::ResolveError::IncompatibleFormat) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
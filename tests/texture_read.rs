@@ -148,3 +148,329 @@ read_texture_test!(read_unsignedtexture2darray, maybe UnsignedTexture2dArray, (u
 read_texture_test!(read_unsignedtexture3d, maybe UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn texture_2d_read_mipmap() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display,
+                                                 vec![vec![(0u8, 255u8, 0u8, 255u8); 4]; 4]);
+    assert!(texture.get_mipmap_levels() >= 2);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.mipmap(1).unwrap().read();
+    assert_eq!(read_back, vec![vec![(0, 255, 0, 255); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_read_float() {
+    let display = support::build_display();
+
+    let data = vec![
+        vec![(0.5f32, 2.0f32, -1.0f32, 8.0f32), (16.0f32, 0.25f32, 1.0f32, 0.0f32)],
+    ];
+
+    let texture = glium::texture::Texture2d::with_format(&display, data.clone(),
+                                              glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                                              false).unwrap();
+
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = texture.main_level().read();
+    assert_eq!(read_back, data);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_texture_2d_read() {
+    let display = support::build_display();
+
+    let data = vec![vec![0.0f32, 0.25f32], vec![0.5f32, 1.0f32]];
+
+    let texture = match glium::texture::DepthTexture2d::new_if_supported(&display, data.clone()) {
+        None => return,
+        Some(t) => t
+    };
+
+    let read_back: Vec<Vec<f32>> = texture.main_level().read();
+    assert_eq!(read_back, data);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_texture_2d_read_if_supported() {
+    let display = support::build_display();
+
+    let data = vec![vec![0.0f32, 0.25f32], vec![0.5f32, 1.0f32]];
+
+    let texture = match glium::texture::DepthTexture2d::new_if_supported(&display, data.clone()) {
+        None => return,
+        Some(t) => t
+    };
+
+    // OpenGL ES can't read depth textures, in which case `None` is returned instead of panicking
+    let read_back: Option<Vec<Vec<f32>>> = texture.main_level().read_if_supported();
+    if let Some(read_back) = read_back {
+        assert_eq!(read_back, data);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_stencil_texture_2d_read_wrong_format() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty_if_supported(&display, 2, 2) {
+        None => return,
+        Some(t) => t
+    };
+
+    // depth-stencil textures can only be read as `u32`
+    let read_back: Option<Vec<Vec<f32>>> = texture.main_level().read_if_supported();
+    assert!(read_back.is_none());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn integral_texture_2d_read() {
+    let display = support::build_display();
+
+    let data = vec![vec![-5i32, 7i32], vec![1024i32, -65536i32]];
+
+    let texture = match glium::texture::IntegralTexture2d::new_if_supported(&display,
+                                                                            data.clone())
+    {
+        None => return,
+        Some(t) => t
+    };
+
+    let read_back: Vec<Vec<i32>> = texture.main_level().read();
+    assert_eq!(read_back, data);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_array_read_layer() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::new_if_supported(&display, vec![
+        vec![vec![(1u8, 2u8, 3u8, 4u8); 2]; 2],
+        vec![vec![(5u8, 6u8, 7u8, 8u8); 2]; 2],
+    ]) {
+        None => return,
+        Some(t) => t
+    };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.layer(1).unwrap().main_level().read();
+    assert_eq!(read_back, vec![vec![(5, 6, 7, 8); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_3d_read() {
+    let display = support::build_display();
+
+    let data = vec![
+        vec![vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)]],
+        vec![vec![(8u8, 9u8, 10u8, 11u8), (12u8, 13u8, 14u8, 15u8)]],
+    ];
+
+    let texture = match glium::texture::Texture3d::new_if_supported(&display, data.clone()) {
+        None => return,
+        Some(t) => t
+    };
+
+    let read_back: Vec<Vec<Vec<(u8, u8, u8, u8)>>> = texture.main_level().read();
+    assert_eq!(read_back, data);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_1d_read() {
+    let display = support::build_display();
+
+    let data = vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)];
+
+    let texture = match glium::texture::Texture1d::new_if_supported(&display, data.clone()) {
+        None => return,
+        Some(t) => t
+    };
+
+    let read_back: Vec<(u8, u8, u8, u8)> = texture.main_level().read();
+    assert_eq!(read_back, data);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_read_compressed_data() {
+    let display = support::build_display();
+
+    // four blocks of 4x4 pixels
+    let data = (0 .. 64u8).collect::<Vec<_>>();
+
+    let texture = match glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                                glium::texture::CompressedFormat::S3tcDxt5Alpha,
+                                &[&data[..]], 8, 8)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.read_compressed_data() {
+        Some((format, read_back)) => {
+            assert_eq!(format, glium::texture::CompressedFormat::S3tcDxt5Alpha);
+            assert_eq!(read_back, data);
+        },
+        None => return
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_array_read_compressed_data_layer() {
+    let display = support::build_display();
+
+    // two layers of 4x4 pixels with a 2x2 mipmap
+    let level0 = (0 .. 32u8).collect::<Vec<_>>();
+    let level1 = (32 .. 64u8).collect::<Vec<_>>();
+
+    let texture = match glium::texture::CompressedSrgbTexture2dArray::with_compressed_data(
+                                &display, glium::texture::CompressedSrgbFormat::S3tcDxt5Alpha,
+                                &[&level0[..], &level1[..]], 4, 4, 2)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    assert_eq!(texture.get_mipmap_levels(), 2);
+
+    match texture.layer(1).unwrap().mipmap(1).unwrap().read_compressed_data() {
+        Some((_, read_back)) => assert_eq!(read_back, &level1[16 ..]),
+        None => return
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_into_image_u8() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)],
+    ]);
+
+    match texture.into_image().unwrap() {
+        glium::texture::TextureImage::Rgba8(image) => {
+            assert_eq!(image.into_raw(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        },
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_into_image_u16() {
+    let display = support::build_display();
+
+    let data = vec![vec![(0u16, 256u16, 4096u16, 65535u16), (1u16, 2u16, 3u16, 4u16)]];
+
+    let texture = glium::texture::Texture2d::with_format(&display, data,
+                                              glium::texture::UncompressedFloatFormat::U16U16U16U16,
+                                              false).unwrap();
+
+    match texture.into_image().unwrap() {
+        glium::texture::TextureImage::Rgba16(image) => {
+            assert_eq!(image.into_raw(), vec![0, 256, 4096, 65535, 1, 2, 3, 4]);
+        },
+        // the backend can't tell us the internal format
+        glium::texture::TextureImage::Rgba8(_) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_into_image_float() {
+    let display = support::build_display();
+
+    let data = vec![
+        vec![(0.5f32, 2.0f32, -1.0f32, 8.0f32), (16.0f32, 0.25f32, 1.0f32, 0.0f32)],
+    ];
+
+    let texture = glium::texture::Texture2d::with_format(&display, data,
+                                              glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                                              false).unwrap();
+
+    match texture.into_image().unwrap() {
+        glium::texture::TextureImage::RgbaF32(image) => {
+            assert_eq!(image.into_raw(), vec![0.5, 2.0, -1.0, 8.0, 16.0, 0.25, 1.0, 0.0]);
+        },
+        glium::texture::TextureImage::Rgba8(_) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn depth_texture_2d_into_image() {
+    let display = support::build_display();
+
+    let data = vec![vec![0.0f32, 0.25f32], vec![0.5f32, 1.0f32]];
+
+    let texture = match glium::texture::DepthTexture2d::new_if_supported(&display, data) {
+        None => return,
+        Some(t) => t
+    };
+
+    match texture.into_image() {
+        Some(glium::texture::TextureImage::LumaF32(image)) => {
+            // images are stored from top to bottom
+            assert_eq!(image.into_raw(), vec![0.5, 1.0, 0.0, 0.25]);
+        },
+        // depth textures can't be read on OpenGL ES
+        None => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn unsigned_texture_2d_into_image() {
+    let display = support::build_display();
+
+    let data = vec![vec![(0u32, 1u32, 2u32, 3u32), (65536u32, 5u32, 6u32, 7u32)]];
+
+    let texture = match glium::texture::UnsignedTexture2d::new_if_supported(&display, data) {
+        None => return,
+        Some(t) => t
+    };
+
+    match texture.into_image() {
+        Some(glium::texture::TextureImage::RgbaU32(image)) => {
+            assert_eq!(image.into_raw(), vec![0, 1, 2, 3, 65536, 5, 6, 7]);
+        },
+        // the backend can't read integral textures
+        None => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
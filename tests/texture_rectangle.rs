@@ -0,0 +1,161 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::Rect;
+use glium::texture::RectangleTexture;
+use glium::texture::DepthRectangleTexture;
+
+mod support;
+
+#[test]
+fn rectangle_texture_creation() {
+    let display = support::build_display();
+
+    let texture = match RectangleTexture::new_if_supported(&display, vec![
+        vec![(0u8, 0u8, 0u8, 0u8); 3],
+        vec![(0u8, 0u8, 0u8, 0u8); 3],
+    ]) {
+        Some(t) => t,
+        None => return
+    };
+
+    assert_eq!(texture.get_width(), 3);
+    assert_eq!(texture.get_height(), Some(2));
+    assert_eq!(texture.get_depth(), None);
+    assert_eq!(texture.get_array_size(), None);
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn rectangle_texture_creation_if_supported() {
+    let display = support::build_display();
+
+    let texture = RectangleTexture::empty_if_supported(&display, 3, 2);
+
+    // OpenGL ES doesn't have rectangle textures, while OpenGL 3.1 always supports them
+    let version = display.get_opengl_version();
+    if version.0 == glium::Api::GlEs {
+        assert!(texture.is_none());
+    } else if version >= glium::Version(glium::Api::Gl, 3, 1) {
+        assert!(texture.is_some());
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn rectangle_texture_write_read() {
+    let display = support::build_display();
+
+    let texture = match RectangleTexture::empty_if_supported(&display, 3, 2) {
+        Some(t) => t,
+        None => return
+    };
+
+    texture.write(Rect { left: 0, bottom: 0, width: 3, height: 2 }, vec![
+        vec![(1u8, 2u8, 3u8, 4u8); 3],
+        vec![(5u8, 6u8, 7u8, 8u8); 3],
+    ]);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back, vec![
+        vec![(1, 2, 3, 4); 3],
+        vec![(5, 6, 7, 8); 3],
+    ]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn rectangle_texture_as_surface() {
+    let display = support::build_display();
+
+    let texture = match RectangleTexture::empty_if_supported(&display, 5, 3) {
+        Some(t) => t,
+        None => return
+    };
+
+    texture.as_surface().clear_color(1.0, 0.0, 1.0, 1.0);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back, vec![vec![(255, 0, 255, 255); 5]; 3]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn rectangle_texture_sampling() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = match RectangleTexture::new_if_supported(&display, vec![
+        vec![(255u8, 0u8, 0u8, 255u8), (0u8, 255u8, 0u8, 255u8)],
+        vec![(0u8, 0u8, 255u8, 255u8), (255u8, 255u8, 255u8, 255u8)],
+    ]) {
+        Some(t) => t,
+        None => return
+    };
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 140
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 140
+
+            uniform sampler2DRect texture;
+            out vec4 color;
+
+            void main() {
+                // coordinates are in pixels and not between 0 and 1
+                color = texture(texture, vec2(1.5, 0.5));
+            }
+        ",
+        None);
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // the default sampler uses mipmaps and repeats, which must be adjusted for rectangle textures
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: texture.sampled() },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_rectangle_texture_creation() {
+    let display = support::build_display();
+
+    let texture = match DepthRectangleTexture::empty_if_supported(&display, 7, 4) {
+        None => return,
+        Some(t) => t
+    };
+
+    assert_eq!(texture.get_width(), 7);
+    assert_eq!(texture.get_height(), Some(4));
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,176 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::texture::{ComponentSwizzle, MipmapsOption, SrgbFormat, TextureViewCreationError};
+use glium::texture::UncompressedFloatFormat;
+
+mod support;
+
+#[test]
+fn texture_2d_array_layer_view() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::with_format_if_supported(&display, vec![
+        vec![vec![(1u8, 2u8, 3u8, 4u8); 2]; 2],
+        vec![vec![(5u8, 6u8, 7u8, 8u8); 2]; 2],
+    ], UncompressedFloatFormat::U8U8U8U8, MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let view = match glium::texture::Texture2d::new_view(&texture,
+                                                         UncompressedFloatFormat::U8U8U8U8,
+                                                         0 .. 1, 1 .. 2)
+    {
+        Ok(v) => v,
+        Err(TextureViewCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(view.get_width(), 2);
+    assert_eq!(view.get_height(), Some(2));
+    assert_eq!(view.get_array_size(), None);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = view.read();
+    assert_eq!(read_back, vec![vec![(5, 6, 7, 8); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn srgb_texture_linear_view() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::SrgbTexture2d::with_format_if_supported(&display, vec![
+        vec![(10u8, 20u8, 30u8, 40u8); 2],
+        vec![(10u8, 20u8, 30u8, 40u8); 2],
+    ], SrgbFormat::U8U8U8U8, MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let view = match glium::texture::Texture2d::new_view(&texture,
+                                                         UncompressedFloatFormat::U8U8U8U8,
+                                                         0 .. 1, 0 .. 1)
+    {
+        Ok(v) => v,
+        Err(TextureViewCreationError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = view.read();
+    assert_eq!(read_back, vec![vec![(10, 20, 30, 40); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn view_incompatible_format() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                                               UncompressedFloatFormat::U8U8U8U8,
+                                                               MipmapsOption::NoMipmap,
+                                                               2, 2).unwrap();
+
+    match glium::texture::Texture2d::new_view(&texture, UncompressedFloatFormat::F32F32F32F32,
+                                              0 .. 1, 0 .. 1)
+    {
+        Err(TextureViewCreationError::IncompatibleFormat) => (),
+        Err(TextureViewCreationError::NotSupported) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn view_incompatible_texture_type() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                                               UncompressedFloatFormat::U8U8U8U8,
+                                                               MipmapsOption::NoMipmap,
+                                                               2, 2).unwrap();
+
+    match glium::texture::Texture3d::new_view(&texture, UncompressedFloatFormat::U8U8U8U8,
+                                              0 .. 1, 0 .. 1)
+    {
+        Err(TextureViewCreationError::IncompatibleTextureType) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn view_out_of_range() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                                               UncompressedFloatFormat::U8U8U8U8,
+                                                               MipmapsOption::NoMipmap,
+                                                               2, 2).unwrap();
+
+    let _ = glium::texture::Texture2d::new_view(&texture, UncompressedFloatFormat::U8U8U8U8,
+                                                0 .. 2, 0 .. 1);
+}
+
+#[test]
+fn swizzle_sample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0, 255, 0, 0), (0, 255, 0, 0)],
+        vec![(0, 255, 0, 0), (0, 255, 0, 0u8)],
+    ]);
+
+    match texture.set_swizzle((ComponentSwizzle::Green, ComponentSwizzle::Zero,
+                               ComponentSwizzle::Zero, ComponentSwizzle::One))
+    {
+        Ok(_) => (),
+        Err(_) => return
+    };
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
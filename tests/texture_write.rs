@@ -24,3 +24,107 @@ fn texture_2d_write() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_2d_write_main_level() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]);
+
+    texture.main_level().write(glium::Rect { bottom: 0, left: 1, width: 1, height: 1 },
+                               vec![vec![(2u8, 4u8, 8u8)]]);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (2, 4, 8, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_write_mipmap() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display, true, 4, 4);
+    assert!(texture.get_mipmap_levels() >= 2);
+
+    texture.mipmap(1).unwrap().write(glium::Rect { bottom: 0, left: 0, width: 2, height: 2 },
+                                     vec![vec![(1u8, 2u8, 3u8, 4u8); 2]; 2]);
+    texture.mipmap(1).unwrap().write(glium::Rect { bottom: 1, left: 1, width: 1, height: 1 },
+                                     vec![vec![(5u8, 6u8, 7u8, 8u8)]]);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.mipmap(1).unwrap().read();
+    assert_eq!(data, vec![
+        vec![(1, 2, 3, 4), (1, 2, 3, 4)],
+        vec![(1, 2, 3, 4), (5, 6, 7, 8)],
+    ]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn texture_2d_write_mipmap_out_of_range() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display, true, 4, 4);
+
+    // the second level is only 2x2
+    texture.mipmap(1).unwrap().write(glium::Rect { bottom: 0, left: 0, width: 4, height: 4 },
+                                     vec![vec![(1u8, 2u8, 3u8, 4u8); 4]; 4]);
+}
+
+#[test]
+fn texture_2d_array_write_layer() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::new_if_supported(&display, vec![
+        vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2],
+        vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2],
+    ]) {
+        None => return,
+        Some(t) => t
+    };
+
+    texture.layer(1).unwrap().main_level()
+           .write(glium::Rect { bottom: 1, left: 0, width: 2, height: 1 },
+                  vec![vec![(255u8, 0u8, 255u8, 255u8); 2]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_1d_write() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture1d::new_if_supported(&display, vec![
+        (0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)
+    ]) {
+        None => return,
+        Some(t) => t
+    };
+
+    texture.main_level().write(1, vec![(255u8, 0u8, 0u8, 255u8), (0u8, 255u8, 0u8, 255u8)]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_3d_write() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture3d::new_if_supported(&display,
+                                                vec![vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2]; 2])
+    {
+        None => return,
+        Some(t) => t
+    };
+
+    texture.main_level().write((1, 0, 1), vec![vec![vec![(255u8, 0u8, 0u8, 255u8)]; 2]]);
+
+    display.assert_no_error(None);
+}